@@ -0,0 +1,181 @@
+//! Archive workflow: status change + move into the archive folder
+//!
+//! Archiving sets `elysium_status: archived`, records the archive date and
+//! original folder, and moves the note into `folders.archive`. Unarchiving
+//! reverses it. Both go through `plan_rename`, so wikilinks keep resolving.
+
+use anyhow::{bail, Result};
+use std::path::{Path, PathBuf};
+
+use super::frontmatter::{elysium_key, FieldValue, FrontmatterEditor};
+use super::paths::{relative_path, VaultPaths};
+use super::rename::{plan_rename, RenamePlan};
+
+pub const ARCHIVED_STATUS: &str = "archived";
+/// Date the note was archived (YYYY-MM-DD)
+pub const ARCHIVED_DATE_FIELD: &str = "archived_date";
+/// Vault-relative folder the note was archived from
+pub const ARCHIVED_FROM_FIELD: &str = "archived_from";
+
+/// Plan archiving the note at `note_path` (absolute path)
+pub fn plan_archive(paths: &VaultPaths, note_path: &Path, date: &str) -> Result<RenamePlan> {
+    let folders = &paths.config.folders;
+    let rel = relative_path(&paths.root, note_path);
+    let status = current_status(note_path)?;
+
+    if folders.is_archived(&rel, status.as_deref()) && folders.in_archive_folder(&rel) {
+        bail!("Note is already archived: {}", rel);
+    }
+
+    let target = if folders.in_archive_folder(&rel) {
+        note_path.to_path_buf()
+    } else {
+        paths
+            .root
+            .join(folders.archive.trim_matches('/'))
+            .join(file_name(note_path))
+    };
+    let from_folder = parent_folder(&rel);
+
+    let mut plan = plan_rename(paths, note_path, &target)?;
+    plan.update_note(|content| {
+        let mut editor = FrontmatterEditor::new(content);
+        editor.set(&elysium_key("status"), text(ARCHIVED_STATUS));
+        editor.set(&elysium_key(ARCHIVED_DATE_FIELD), text(date));
+        if !folders.in_archive_folder(&rel) {
+            editor.set(&elysium_key(ARCHIVED_FROM_FIELD), text(&from_folder));
+        }
+        editor.to_content()
+    })?;
+    Ok(plan)
+}
+
+/// Plan restoring an archived note
+///
+/// The note moves to `folder` if given, else the folder it was archived
+/// from, else `folders.notes`. Its status becomes `status`.
+pub fn plan_unarchive(
+    paths: &VaultPaths,
+    note_path: &Path,
+    folder: Option<&str>,
+    status: &str,
+) -> Result<RenamePlan> {
+    let folders = &paths.config.folders;
+    let rel = relative_path(&paths.root, note_path);
+    let content = std::fs::read_to_string(note_path)?;
+    let editor = FrontmatterEditor::new(&content);
+    let current = editor
+        .get(&elysium_key("status"))
+        .and_then(|v| v.to_string_value());
+
+    if !folders.is_archived(&rel, current.as_deref()) {
+        bail!("Note is not archived: {}", rel);
+    }
+    if status.eq_ignore_ascii_case(ARCHIVED_STATUS) {
+        bail!("Unarchive status cannot be '{}'", ARCHIVED_STATUS);
+    }
+
+    let archived_from = editor
+        .get(&elysium_key(ARCHIVED_FROM_FIELD))
+        .and_then(|v| v.to_string_value());
+    let target_folder = match (folder, archived_from.as_deref()) {
+        (Some(f), _) => f.trim_matches('/').to_string(),
+        (None, Some(f)) => f.trim_matches('/').to_string(),
+        (None, None) if folders.in_archive_folder(&rel) => {
+            folders.notes.trim_matches('/').to_string()
+        }
+        (None, None) => parent_folder(&rel),
+    };
+    if target_folder.split('/').any(|part| part == "..") {
+        bail!("Destination folder must be inside the vault");
+    }
+    let target: PathBuf = paths.root.join(&target_folder).join(file_name(note_path));
+
+    let mut plan = plan_rename(paths, note_path, &target)?;
+    plan.update_note(|content| {
+        let mut editor = FrontmatterEditor::new(content);
+        editor.set(&elysium_key("status"), text(status));
+        editor.remove(&elysium_key(ARCHIVED_DATE_FIELD));
+        editor.remove(&elysium_key(ARCHIVED_FROM_FIELD));
+        editor.to_content()
+    })?;
+    Ok(plan)
+}
+
+fn current_status(note_path: &Path) -> Result<Option<String>> {
+    let content = std::fs::read_to_string(note_path)?;
+    Ok(FrontmatterEditor::new(&content)
+        .get(&elysium_key("status"))
+        .and_then(|v| v.to_string_value()))
+}
+
+fn parent_folder(rel: &str) -> String {
+    rel.rsplit_once('/')
+        .map(|(dir, _)| dir.to_string())
+        .unwrap_or_default()
+}
+
+fn file_name(path: &Path) -> &std::ffi::OsStr {
+    path.file_name().unwrap_or_default()
+}
+
+fn text(value: &str) -> FieldValue {
+    FieldValue::String(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn setup_vault() -> (TempDir, VaultPaths) {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("Projects")).unwrap();
+        fs::write(
+            temp.path().join("Projects/Old Plan.md"),
+            "---\nelysium_type: project\nelysium_status: done\n---\n# Old Plan\n",
+        )
+        .unwrap();
+        fs::write(temp.path().join("Index.md"), "See [[Old Plan]].\n").unwrap();
+        let paths = VaultPaths::from_root(temp.path().to_path_buf());
+        (temp, paths)
+    }
+
+    #[test]
+    fn test_archive_and_unarchive() -> Result<()> {
+        let (temp, paths) = setup_vault();
+        let note = temp.path().join("Projects/Old Plan.md");
+
+        let plan = plan_archive(&paths, &note, "2026-01-02")?;
+        assert_eq!(plan.to, temp.path().join("Archive/Old Plan.md"));
+        plan.apply()?;
+
+        let archived = temp.path().join("Archive/Old Plan.md");
+        let content = fs::read_to_string(&archived)?;
+        assert!(content.contains("elysium_status: archived\n"));
+        assert!(content.contains("elysium_archived_date: 2026-01-02\n"));
+        assert!(content.contains("elysium_archived_from: Projects\n"));
+        assert!(content.ends_with("# Old Plan\n"));
+        // Bare-name links still resolve after the move
+        assert_eq!(
+            fs::read_to_string(temp.path().join("Index.md"))?,
+            "See [[Old Plan]].\n"
+        );
+
+        assert!(plan_archive(&paths, &archived, "2026-01-03").is_err());
+
+        plan_unarchive(&paths, &archived, None, "active")?.apply()?;
+        let content = fs::read_to_string(&note)?;
+        assert!(content.contains("elysium_status: active\n"));
+        assert!(!content.contains("archived"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_unarchive_requires_archived_note() {
+        let (temp, paths) = setup_vault();
+        let note = temp.path().join("Projects/Old Plan.md");
+        assert!(plan_unarchive(&paths, &note, None, "active").is_err());
+    }
+}
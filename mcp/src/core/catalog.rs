@@ -0,0 +1,139 @@
+//! Note catalog: parsed notes kept in memory between calls
+//!
+//! `refresh` re-stats the vault and re-parses only files whose mtime or size
+//! changed, so an unchanged vault costs one directory walk and no reads.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
+use walkdir::WalkDir;
+
+use super::note::{should_exclude_path, Note};
+use super::paths::{relative_path, VaultPaths};
+
+/// File identity used to detect changes
+type Stamp = (Option<SystemTime>, u64);
+
+pub struct NoteCatalog {
+    root: PathBuf,
+    /// Parsed notes, sorted by name (same order as `collect_all_notes`)
+    notes: Arc<Vec<Note>>,
+    /// Vault-relative paths of all files (notes and attachments), sorted
+    files: Arc<Vec<String>>,
+    stamps: HashMap<PathBuf, Stamp>,
+    /// Incremented whenever notes or files change
+    generation: u64,
+}
+
+impl NoteCatalog {
+    /// Scan and parse every note in the vault
+    pub fn load(paths: &VaultPaths) -> Self {
+        let mut catalog = Self {
+            root: paths.root.clone(),
+            notes: Arc::default(),
+            files: Arc::default(),
+            stamps: HashMap::new(),
+            generation: 0,
+        };
+        catalog.refresh();
+        catalog
+    }
+
+    pub fn notes(&self) -> Arc<Vec<Note>> {
+        Arc::clone(&self.notes)
+    }
+
+    pub fn files(&self) -> Arc<Vec<String>> {
+        Arc::clone(&self.files)
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Re-scan the vault; returns true if anything changed
+    pub fn refresh(&mut self) -> bool {
+        let stamps = self.scan();
+        if stamps == self.stamps && self.generation > 0 {
+            return false;
+        }
+
+        let previous: HashMap<&PathBuf, &Note> = self.notes.iter().map(|n| (&n.path, n)).collect();
+        let mut notes = Vec::new();
+        let mut files = Vec::with_capacity(stamps.len());
+
+        for (path, stamp) in &stamps {
+            files.push(relative_path(&self.root, path));
+            if path.extension().map(|e| e == "md").unwrap_or(false) {
+                let unchanged = self.stamps.get(path) == Some(stamp);
+                match previous.get(path) {
+                    Some(note) if unchanged => notes.push((*note).clone()),
+                    _ => {
+                        if let Ok(note) = Note::load(path) {
+                            notes.push(note);
+                        }
+                    }
+                }
+            }
+        }
+
+        notes.sort_by(|a, b| a.name.cmp(&b.name));
+        files.sort();
+
+        self.notes = Arc::new(notes);
+        self.files = Arc::new(files);
+        self.stamps = stamps;
+        self.generation += 1;
+        true
+    }
+
+    fn scan(&self) -> HashMap<PathBuf, Stamp> {
+        WalkDir::new(&self.root)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file() && !should_exclude_path(&self.root, e.path()))
+            .map(|e| {
+                let stamp = e
+                    .metadata()
+                    .map(|m| (m.modified().ok(), m.len()))
+                    .unwrap_or((None, 0));
+                (e.into_path(), stamp)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_refresh_tracks_changes() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("a.md"), "# A\n").unwrap();
+        fs::write(temp.path().join("pic.png"), "").unwrap();
+        fs::create_dir_all(temp.path().join(".obsidian")).unwrap();
+        fs::write(temp.path().join(".obsidian/skip.md"), "").unwrap();
+
+        let paths = VaultPaths::from_root(temp.path().to_path_buf());
+        let mut catalog = NoteCatalog::load(&paths);
+        assert_eq!(catalog.notes().len(), 1);
+        assert_eq!(*catalog.files(), vec!["a.md", "pic.png"]);
+        let generation = catalog.generation();
+
+        assert!(!catalog.refresh());
+        assert_eq!(catalog.generation(), generation);
+
+        fs::write(temp.path().join("a.md"), "# A\n\nEdited body.\n").unwrap();
+        fs::write(temp.path().join("b.md"), "# B\n").unwrap();
+        assert!(catalog.refresh());
+        assert_eq!(catalog.generation(), generation + 1);
+
+        let notes = catalog.notes();
+        assert_eq!(notes.len(), 2);
+        assert!(notes[0].content.contains("Edited body."));
+    }
+}
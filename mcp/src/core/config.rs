@@ -43,6 +43,10 @@ pub struct Config {
     /// Inbox configuration (plugin format - root level object)
     #[serde(default)]
     pub inbox: InboxConfig,
+
+    /// Search ranking (hybrid fusion and BM25 field boosts)
+    #[serde(default)]
+    pub search: RankingConfig,
 }
 
 /// Inbox configuration (from plugin)
@@ -229,6 +233,40 @@ pub struct FoldersConfig {
 
     #[serde(default = "default_archive_folder")]
     pub archive: String,
+
+    /// Folder placement rules checked by the audit (None: built-in defaults)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<Vec<FolderRule>>,
+}
+
+/// Notes whose `field` has one of `values` must live under one of `folders`
+///
+/// `field` is a frontmatter field without the `elysium_` prefix (`type`,
+/// `status`, `area`, ...); values match case-insensitively. Folders are
+/// vault-relative prefixes, `""` meaning anywhere.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FolderRule {
+    pub field: String,
+    pub values: Vec<String>,
+    pub folders: Vec<String>,
+}
+
+impl FolderRule {
+    /// Whether a note with this field value is covered by the rule
+    pub fn applies_to(&self, value: &str) -> bool {
+        self.values.iter().any(|v| v.eq_ignore_ascii_case(value))
+    }
+
+    /// Whether a vault-relative path is inside one of the allowed folders
+    pub fn allows(&self, rel_path: &str) -> bool {
+        self.folders.iter().any(|folder| {
+            let folder = folder.trim_matches('/');
+            folder.is_empty()
+                || rel_path
+                    .strip_prefix(folder)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
 }
 
 fn default_notes_folder() -> String {
@@ -249,6 +287,38 @@ impl Default for FoldersConfig {
             notes: default_notes_folder(),
             projects: default_projects_folder(),
             archive: default_archive_folder(),
+            rules: None,
+        }
+    }
+}
+
+impl FoldersConfig {
+    /// Whether a vault-relative path is inside the archive folder
+    pub fn in_archive_folder(&self, rel_path: &str) -> bool {
+        let archive = self.archive.trim_matches('/');
+        !archive.is_empty()
+            && rel_path
+                .strip_prefix(archive)
+                .is_some_and(|rest| rest.starts_with('/'))
+    }
+
+    /// Archived notes: `status: archived` or stored in the archive folder
+    pub fn is_archived(&self, rel_path: &str, status: Option<&str>) -> bool {
+        status.is_some_and(|s| s.eq_ignore_ascii_case("archived"))
+            || self.in_archive_folder(rel_path)
+    }
+
+    /// Effective folder rules, in priority order (first matching rule applies)
+    ///
+    /// Without configured rules, archived notes must be in the archive folder.
+    pub fn folder_rules(&self) -> Vec<FolderRule> {
+        match &self.rules {
+            Some(rules) => rules.clone(),
+            None => vec![FolderRule {
+                field: "status".to_string(),
+                values: vec!["archived".to_string()],
+                folders: vec![self.archive.clone()],
+            }],
         }
     }
 }
@@ -337,6 +407,64 @@ impl FeatureConfig {
     }
 }
 
+/// Search ranking overrides; unset values keep the engine defaults
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RankingConfig {
+    /// Hybrid fusion method: rrf, min_max, z_score or dbsf
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fusion: Option<String>,
+
+    #[serde(
+        default,
+        rename = "bm25Weight",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub bm25_weight: Option<f32>,
+
+    #[serde(
+        default,
+        rename = "semanticWeight",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub semantic_weight: Option<f32>,
+
+    #[serde(default, rename = "rrfK", skip_serializing_if = "Option::is_none")]
+    pub rrf_k: Option<usize>,
+
+    /// BM25 field boosts
+    #[serde(default)]
+    pub boosts: BoostConfig,
+
+    /// BM25 tokenizer: default or cjk (character bigrams for Korean,
+    /// Japanese and Chinese)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokenizer: Option<String>,
+
+    /// Expand BM25 queries with tag aliases and synonyms (default: false)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expansion: Option<bool>,
+
+    /// Score weight of expanded terms (default: 0.5)
+    #[serde(
+        default,
+        rename = "expansionWeight",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub expansion_weight: Option<f32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BoostConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gist: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<f32>,
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -345,6 +473,7 @@ impl Default for Config {
             folders: FoldersConfig::default(),
             features: FeatureConfig::default(),
             inbox: InboxConfig::default(),
+            search: RankingConfig::default(),
         }
     }
 }
@@ -473,6 +602,26 @@ mod tests {
         assert!(config.schema.lowercase_tags);
     }
 
+    #[test]
+    fn test_folder_rules() {
+        let config = Config::default();
+        let rules = config.folders.folder_rules();
+        assert_eq!(rules.len(), 1);
+        assert!(rules[0].applies_to("Archived"));
+        assert!(rules[0].allows("Archive/2024/Old.md"));
+        assert!(!rules[0].allows("Archived Notes/Old.md"));
+
+        let json = r#"{"folders": {"rules": [
+            {"field": "type", "values": ["project"], "folders": ["Projects", "Archive"]}
+        ]}}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        let rules = config.folders.folder_rules();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].field, "type");
+        assert!(rules[0].allows("Archive/Plan.md"));
+        assert!(!rules[0].allows("Plan.md"));
+    }
+
     #[test]
     fn test_parse_partial_config() {
         // Test legacy format (features.inbox as string)
@@ -515,6 +664,21 @@ mod tests {
         assert!(config.features.is_advanced_search_ready());
     }
 
+    #[test]
+    fn test_parse_search_ranking() {
+        let json = r#"{"search": {"fusion": "dbsf", "bm25Weight": 0.5, "rrfK": 20, "boosts": {"body": 2.0}, "tokenizer": "cjk", "expansion": true}}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(config.search.fusion.as_deref(), Some("dbsf"));
+        assert_eq!(config.search.bm25_weight, Some(0.5));
+        assert_eq!(config.search.semantic_weight, None);
+        assert_eq!(config.search.rrf_k, Some(20));
+        assert_eq!(config.search.boosts.body, Some(2.0));
+        assert_eq!(config.search.boosts.title, None);
+        assert_eq!(config.search.tokenizer.as_deref(), Some("cjk"));
+        assert_eq!(config.search.expansion, Some(true));
+        assert_eq!(config.search.expansion_weight, None);
+    }
+
     #[test]
     fn test_schema_sets() {
         let config = Config::default();
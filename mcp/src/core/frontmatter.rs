@@ -3,16 +3,17 @@
 //! Parses YAML frontmatter from markdown notes and validates against schema.
 //! Supports dynamic field extraction for all elysium_* prefixed fields.
 
+use chrono::NaiveDate;
 use lazy_static::lazy_static;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use super::schema::{SchemaValidator, SchemaViolation, VALID_AREAS, VALID_STATUS, VALID_TYPES};
 
 lazy_static! {
     static ref FRONTMATTER_RE: Regex = Regex::new(r"(?s)^---\r?\n(.*?)\r?\n---").unwrap();
-    // Dynamic field pattern: captures elysium_* field names and values
+    // Dynamic field pattern: captures elysium_* field names and values (lenient fallback)
     static ref ELYSIUM_FIELD_RE: Regex = Regex::new(r"(?m)^(elysium_\w+):\s*(.*)$").unwrap();
     // List pattern for [...] values
     static ref LIST_RE: Regex = Regex::new(r"^\[(.*)\]$").unwrap();
@@ -81,15 +82,80 @@ pub fn detect_folded_scalars(raw_frontmatter: &str) -> Vec<(String, char)> {
         .collect()
 }
 
-/// Field value types for dynamic frontmatter
+/// Collapse a (possibly multi-line) gist into a single line; None if empty
+fn normalize_gist(gist: &str) -> Option<String> {
+    let joined = gist
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if joined.is_empty() {
+        None
+    } else {
+        Some(joined)
+    }
+}
+
+/// Typed frontmatter value (parsed from YAML)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(untagged)]
 pub enum FieldValue {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
     String(String),
-    List(Vec<String>),
+    List(Vec<FieldValue>),
+    Map(BTreeMap<String, FieldValue>),
 }
 
 impl FieldValue {
+    /// Convert a parsed YAML value
+    pub fn from_yaml(value: serde_yaml::Value) -> Self {
+        use serde_yaml::Value;
+
+        match value {
+            Value::Null => FieldValue::Null,
+            Value::Bool(b) => FieldValue::Bool(b),
+            Value::Number(n) => match n.as_i64() {
+                Some(i) => FieldValue::Integer(i),
+                None => FieldValue::Float(n.as_f64().unwrap_or_default()),
+            },
+            Value::String(s) => FieldValue::String(s.trim().to_string()),
+            Value::Sequence(items) => {
+                FieldValue::List(items.into_iter().map(Self::from_yaml).collect())
+            }
+            Value::Mapping(map) => FieldValue::Map(
+                map.into_iter()
+                    .filter_map(|(k, v)| Some((yaml_key_to_string(&k)?, Self::from_yaml(v))))
+                    .collect(),
+            ),
+            Value::Tagged(tagged) => Self::from_yaml(tagged.value),
+        }
+    }
+
+    /// Convert a JSON value (e.g., from MCP tool parameters)
+    pub fn from_json(value: &serde_json::Value) -> Self {
+        use serde_json::Value;
+
+        match value {
+            Value::Null => FieldValue::Null,
+            Value::Bool(b) => FieldValue::Bool(*b),
+            Value::Number(n) => match n.as_i64() {
+                Some(i) => FieldValue::Integer(i),
+                None => FieldValue::Float(n.as_f64().unwrap_or_default()),
+            },
+            Value::String(s) => FieldValue::String(s.clone()),
+            Value::Array(items) => FieldValue::List(items.iter().map(Self::from_json).collect()),
+            Value::Object(map) => FieldValue::Map(
+                map.iter()
+                    .map(|(k, v)| (k.clone(), Self::from_json(v)))
+                    .collect(),
+            ),
+        }
+    }
+
     /// Get as string reference if it's a String variant
     pub fn as_str(&self) -> Option<&str> {
         match self {
@@ -98,22 +164,100 @@ impl FieldValue {
         }
     }
 
-    /// Get as list reference if it's a List variant
-    pub fn as_list(&self) -> Option<&Vec<String>> {
+    /// Get list items if it's a List variant
+    #[allow(dead_code)]
+    pub fn as_list(&self) -> Option<&[FieldValue]> {
         match self {
             FieldValue::List(l) => Some(l),
             _ => None,
         }
     }
 
-    /// Convert to owned String (for String variant)
+    /// Get as list of strings
+    ///
+    /// Scalar items are converted to text; a single scalar is treated as a
+    /// one-item list (Obsidian accepts `tags: gpu` as well as `tags: [gpu]`).
+    pub fn as_string_list(&self) -> Option<Vec<String>> {
+        match self {
+            FieldValue::List(items) => Some(
+                items
+                    .iter()
+                    .filter_map(|item| item.to_string_value())
+                    .filter(|s| !s.is_empty())
+                    .collect(),
+            ),
+            FieldValue::Null | FieldValue::Map(_) => None,
+            scalar => Some(
+                scalar
+                    .to_string_value()
+                    .filter(|s| !s.is_empty())
+                    .into_iter()
+                    .collect(),
+            ),
+        }
+    }
+
     #[allow(dead_code)]
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            FieldValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Get numeric value (Integer or Float)
+    #[allow(dead_code)]
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            FieldValue::Integer(i) => Some(*i as f64),
+            FieldValue::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+
+    /// Parse a date (`YYYY-MM-DD`, or the date part of a datetime)
+    pub fn as_date(&self) -> Option<NaiveDate> {
+        let s = self.as_str()?;
+        let date_part = s.get(..10).unwrap_or(s);
+        NaiveDate::parse_from_str(date_part, "%Y-%m-%d").ok()
+    }
+
+    /// Convert scalar value to owned String (None for null, lists and maps)
     pub fn to_string_value(&self) -> Option<String> {
         match self {
             FieldValue::String(s) => Some(s.clone()),
-            _ => None,
+            FieldValue::Bool(b) => Some(b.to_string()),
+            FieldValue::Integer(i) => Some(i.to_string()),
+            FieldValue::Float(f) => Some(f.to_string()),
+            FieldValue::Null | FieldValue::List(_) | FieldValue::Map(_) => None,
         }
     }
+
+    /// Convert to JSON value (for API output)
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            FieldValue::Null => serde_json::Value::Null,
+            FieldValue::Bool(b) => serde_json::Value::Bool(*b),
+            FieldValue::Integer(i) => serde_json::json!(i),
+            FieldValue::Float(f) => serde_json::json!(f),
+            FieldValue::String(s) => serde_json::Value::String(s.clone()),
+            FieldValue::List(l) => {
+                serde_json::Value::Array(l.iter().map(|v| v.to_json()).collect())
+            }
+            FieldValue::Map(m) => {
+                serde_json::Value::Object(m.iter().map(|(k, v)| (k.clone(), v.to_json())).collect())
+            }
+        }
+    }
+}
+
+fn yaml_key_to_string(key: &serde_yaml::Value) -> Option<String> {
+    match key {
+        serde_yaml::Value::String(s) => Some(s.clone()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        serde_yaml::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
 }
 
 /// Field presets for API output
@@ -125,23 +269,73 @@ pub struct Frontmatter {
     /// Dynamic field storage - keys are without "elysium_" prefix
     /// e.g., "type", "status", "area", "gist", "tags", "source"
     pub fields: HashMap<String, FieldValue>,
+    /// Non-elysium keys (e.g., Obsidian's `aliases`, `tags`, `cssclasses`)
+    #[allow(dead_code)]
+    pub other: HashMap<String, FieldValue>,
     /// Raw frontmatter text for debugging/re-parsing
     pub raw: String,
 }
 
 impl Frontmatter {
     /// Parse frontmatter from markdown content
-    /// Extracts all elysium_* prefixed fields dynamically
+    ///
+    /// The block is parsed as YAML. If it is not valid YAML, elysium_* fields
+    /// are still extracted line by line so a single bad line does not hide
+    /// the rest of the note's metadata (the syntax error is reported by
+    /// `validate_with_config`).
     pub fn parse(content: &str) -> Option<Self> {
         let caps = FRONTMATTER_RE.captures(content)?;
         let raw = caps.get(1)?.as_str().to_string();
 
+        match serde_yaml::from_str::<serde_yaml::Value>(&raw) {
+            Ok(serde_yaml::Value::Mapping(map)) => Some(Self::from_mapping(map, raw)),
+            Ok(_) => Some(Self {
+                raw,
+                ..Default::default()
+            }),
+            Err(_) => Some(Self::parse_lenient(raw)),
+        }
+    }
+
+    fn from_mapping(map: serde_yaml::Mapping, raw: String) -> Self {
+        let mut fields = HashMap::new();
+        let mut other = HashMap::new();
+
+        for (key, value) in map {
+            let Some(key) = yaml_key_to_string(&key) else {
+                continue;
+            };
+            let value = FieldValue::from_yaml(value);
+
+            // Remove "elysium_" prefix for cleaner key names
+            match key.strip_prefix("elysium_") {
+                Some("gist") => {
+                    if let Some(gist) = value.to_string_value().and_then(|g| normalize_gist(&g)) {
+                        fields.insert("gist".to_string(), FieldValue::String(gist));
+                    }
+                }
+                Some(stripped) => {
+                    fields.insert(stripped.to_string(), value);
+                }
+                None => {
+                    other.insert(key, value);
+                }
+            }
+        }
+
+        Self { fields, other, raw }
+    }
+
+    /// Line-based fallback for frontmatter that is not valid YAML
+    fn parse_lenient(raw: String) -> Self {
         let mut fields = HashMap::new();
 
-        // First pass: extract all elysium_* fields
         for caps in ELYSIUM_FIELD_RE.captures_iter(&raw) {
-            let full_key = caps.get(1)?.as_str();
-            let value_str = caps.get(2)?.as_str().trim();
+            let (Some(full_key), Some(value)) = (caps.get(1), caps.get(2)) else {
+                continue;
+            };
+            let full_key = full_key.as_str();
+            let value_str = value.as_str().trim();
 
             // Remove "elysium_" prefix for cleaner key names
             let key = full_key.strip_prefix("elysium_").unwrap_or(full_key);
@@ -159,18 +353,23 @@ impl Frontmatter {
             fields.insert(key.to_string(), value);
         }
 
-        Some(Self { fields, raw })
+        Self {
+            fields,
+            other: HashMap::new(),
+            raw,
+        }
     }
 
-    /// Parse a value string into FieldValue
+    /// Parse a value string into FieldValue (lenient fallback)
     fn parse_value(value_str: &str) -> FieldValue {
         // Check if it's a list [....]
         if let Some(caps) = LIST_RE.captures(value_str) {
             let inner = caps.get(1).map(|m| m.as_str()).unwrap_or("");
-            let items: Vec<String> = inner
+            let items: Vec<FieldValue> = inner
                 .split(',')
                 .map(|s| s.trim().trim_matches('"').trim_matches('\'').to_string())
                 .filter(|s| !s.is_empty())
+                .map(FieldValue::String)
                 .collect();
             FieldValue::List(items)
         } else {
@@ -180,7 +379,7 @@ impl Frontmatter {
         }
     }
 
-    /// Extract multiline gist (YAML folding support)
+    /// Extract multiline gist (lenient fallback)
     fn extract_gist(raw: &str) -> Option<String> {
         let lines: Vec<&str> = raw.lines().collect();
         let gist_line_idx = lines.iter().position(|l| l.starts_with("elysium_gist:"))?;
@@ -203,20 +402,10 @@ impl Frontmatter {
                 }
             }
 
-            let gist = folded_content.join(" ");
-            if gist.is_empty() {
-                None
-            } else {
-                Some(gist)
-            }
+            normalize_gist(&folded_content.join(" "))
         } else {
             // Single line gist
-            let gist = after_colon.trim_matches('"').trim_matches('\'').to_string();
-            if gist.is_empty() {
-                None
-            } else {
-                Some(gist)
-            }
+            normalize_gist(after_colon.trim_matches('"').trim_matches('\''))
         }
     }
 
@@ -246,16 +435,12 @@ impl Frontmatter {
 
     /// Get tags (elysium_tags)
     pub fn tags(&self) -> Vec<String> {
-        self.fields
-            .get("tags")
-            .and_then(|v| v.as_list())
-            .cloned()
-            .unwrap_or_default()
+        self.get_list("tags").unwrap_or_default()
     }
 
     /// Get source URLs (elysium_source)
     pub fn source(&self) -> Option<Vec<String>> {
-        self.fields.get("source").and_then(|v| v.as_list()).cloned()
+        self.get_list("source")
     }
 
     /// Get any field by key (without elysium_ prefix)
@@ -269,9 +454,9 @@ impl Frontmatter {
         self.fields.get(key).and_then(|v| v.as_str())
     }
 
-    /// Get list field value
-    pub fn get_list(&self, key: &str) -> Option<&Vec<String>> {
-        self.fields.get(key).and_then(|v| v.as_list())
+    /// Get list field value (as strings)
+    pub fn get_list(&self, key: &str) -> Option<Vec<String>> {
+        self.fields.get(key).and_then(|v| v.as_string_list())
     }
 
     /// Get all field keys
@@ -284,36 +469,39 @@ impl Frontmatter {
     pub fn to_json_map(&self) -> HashMap<String, serde_json::Value> {
         self.fields
             .iter()
-            .map(|(k, v)| {
-                let json_val = match v {
-                    FieldValue::String(s) => serde_json::Value::String(s.clone()),
-                    FieldValue::List(l) => serde_json::Value::Array(
-                        l.iter()
-                            .map(|s| serde_json::Value::String(s.clone()))
-                            .collect(),
-                    ),
-                };
-                (k.clone(), json_val)
-            })
+            .map(|(k, v)| (k.clone(), v.to_json()))
             .collect()
     }
 
+    /// Compare with a newer version (elysium fields without prefix, other keys as-is)
+    pub fn diff(&self, newer: &Frontmatter) -> FieldChanges {
+        let mut changes = FieldChanges::default();
+
+        for (old, new) in [(&self.fields, &newer.fields), (&self.other, &newer.other)] {
+            for (key, value) in new {
+                match old.get(key) {
+                    None => changes.added.push(key.clone()),
+                    Some(prev) if prev != value => changes.updated.push(key.clone()),
+                    Some(_) => {}
+                }
+            }
+            changes
+                .removed
+                .extend(old.keys().filter(|k| !new.contains_key(*k)).cloned());
+        }
+
+        changes.added.sort();
+        changes.updated.sort();
+        changes.removed.sort();
+        changes
+    }
+
     /// Filter fields for API output based on requested field set
     pub fn filter_fields(&self, requested: &[&str]) -> HashMap<String, serde_json::Value> {
         self.fields
             .iter()
             .filter(|(k, _)| requested.contains(&k.as_str()))
-            .map(|(k, v)| {
-                let json_val = match v {
-                    FieldValue::String(s) => serde_json::Value::String(s.clone()),
-                    FieldValue::List(l) => serde_json::Value::Array(
-                        l.iter()
-                            .map(|s| serde_json::Value::String(s.clone()))
-                            .collect(),
-                    ),
-                };
-                (k.clone(), json_val)
-            })
+            .map(|(k, v)| (k.clone(), v.to_json()))
             .collect()
     }
 
@@ -462,6 +650,341 @@ impl Frontmatter {
     }
 }
 
+// =========================================
+// Format-preserving Editing
+// =========================================
+
+/// Full frontmatter key for a field name ("status" -> "elysium_status")
+pub fn elysium_key(field: &str) -> String {
+    if field.starts_with("elysium_") {
+        field.to_string()
+    } else {
+        format!("elysium_{}", field)
+    }
+}
+
+/// Field-level difference between two versions of a frontmatter block
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct FieldChanges {
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl FieldChanges {
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.updated.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Format-preserving frontmatter editor
+///
+/// Edits work on top-level entries in place: other keys, comments, blank
+/// lines and the note body are written back untouched, and new keys are
+/// appended at the end of the block. Keys are full YAML keys
+/// (e.g., `elysium_status`, `aliases`).
+#[derive(Debug, Clone)]
+pub struct FrontmatterEditor {
+    lines: Vec<String>,
+    body: String,
+    newline: &'static str,
+    has_block: bool,
+}
+
+impl FrontmatterEditor {
+    /// Load a note's content (a frontmatter block is created on first edit if missing)
+    pub fn new(content: &str) -> Self {
+        let newline = if content.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+
+        match FRONTMATTER_RE.captures(content) {
+            Some(caps) => {
+                let inner = caps.get(1).map(|m| m.as_str()).unwrap_or("");
+                let end = caps.get(0).map(|m| m.end()).unwrap_or(0);
+                Self {
+                    lines: inner.lines().map(String::from).collect(),
+                    body: content[end..].to_string(),
+                    newline,
+                    has_block: true,
+                }
+            }
+            None => Self {
+                lines: Vec::new(),
+                body: content.to_string(),
+                newline,
+                has_block: false,
+            },
+        }
+    }
+
+    /// Check whether a top-level key exists
+    #[allow(dead_code)]
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.entry_span(key).is_some()
+    }
+
+    /// Get the current value of a key
+    pub fn get(&self, key: &str) -> Option<FieldValue> {
+        let span = self.entry_span(key)?;
+        let entry = self.lines[span].join("\n");
+        match serde_yaml::from_str::<serde_yaml::Value>(&entry).ok()? {
+            serde_yaml::Value::Mapping(map) => map
+                .into_iter()
+                .find(|(k, _)| yaml_key_to_string(k).as_deref() == Some(key))
+                .map(|(_, v)| FieldValue::from_yaml(v)),
+            _ => None,
+        }
+    }
+
+    /// Set a key, replacing its entry in place or appending it
+    ///
+    /// Returns false if the key already had this value (nothing is rewritten).
+    pub fn set(&mut self, key: &str, value: FieldValue) -> bool {
+        if self.get(key).as_ref() == Some(&value) {
+            return false;
+        }
+
+        match self.entry_span(key) {
+            Some(span) => {
+                // Keep block-style lists in block style (with the same indent)
+                let block_indent = self.lines[span.clone()]
+                    .get(1)
+                    .filter(|l| l.trim_start().starts_with('-'))
+                    .map(|l| l[..l.len() - l.trim_start().len()].to_string());
+                let rendered = render_entry(key, &value, block_indent.as_deref());
+                self.lines.splice(span, rendered);
+            }
+            None => {
+                let rendered = render_entry(key, &value, None);
+                let insert_at = self.end_of_entries();
+                self.lines.splice(insert_at..insert_at, rendered);
+            }
+        }
+        true
+    }
+
+    /// Remove a key (and its continuation lines); returns false if absent
+    pub fn remove(&mut self, key: &str) -> bool {
+        match self.entry_span(key) {
+            Some(span) => {
+                self.lines.drain(span);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Append items to a list field, skipping items already present
+    ///
+    /// A missing key becomes a new list; a scalar value becomes the first
+    /// item. Returns the items that were actually added.
+    pub fn append_to_list(&mut self, key: &str, items: &[String]) -> Vec<String> {
+        let mut list = match self.get(key) {
+            Some(FieldValue::List(existing)) => existing,
+            Some(FieldValue::Null) | None => Vec::new(),
+            Some(scalar) => vec![scalar],
+        };
+
+        let mut added = Vec::new();
+        for item in items {
+            let exists = list
+                .iter()
+                .any(|v| v.to_string_value().as_deref() == Some(item.as_str()));
+            if !exists && !added.contains(item) {
+                added.push(item.clone());
+            }
+        }
+
+        if !added.is_empty() {
+            list.extend(added.iter().cloned().map(FieldValue::String));
+            self.set(key, FieldValue::List(list));
+        }
+        added
+    }
+
+    /// Note body as stored (starts after the closing `---`)
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    /// Replace the note body (separated from the frontmatter by a blank line)
+    pub fn set_body(&mut self, body: &str) {
+        let nl = self.newline;
+        self.body = if self.has_block {
+            format!("{nl}{nl}{}", body)
+        } else {
+            body.to_string()
+        };
+    }
+
+    /// Render the full note content
+    pub fn to_content(&self) -> String {
+        let nl = self.newline;
+        if !self.has_block && self.lines.is_empty() {
+            return self.body.clone();
+        }
+
+        let block = self.lines.join(nl);
+        if self.has_block {
+            format!("---{nl}{block}{nl}---{}", self.body)
+        } else {
+            format!("---{nl}{block}{nl}---{nl}{nl}{}", self.body)
+        }
+    }
+
+    /// Line range of a top-level entry (key line + continuation lines)
+    fn entry_span(&self, key: &str) -> Option<std::ops::Range<usize>> {
+        let start = self
+            .lines
+            .iter()
+            .position(|line| entry_key(line) == Some(key))?;
+
+        let mut end = start + 1;
+        for (i, line) in self.lines.iter().enumerate().skip(start + 1) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if line.starts_with([' ', '\t', '-']) {
+                end = i + 1;
+                continue;
+            }
+            break;
+        }
+        Some(start..end)
+    }
+
+    /// Index after the last non-blank line (new keys go before trailing blanks)
+    fn end_of_entries(&self) -> usize {
+        self.lines
+            .iter()
+            .rposition(|l| !l.trim().is_empty())
+            .map(|i| i + 1)
+            .unwrap_or(0)
+    }
+}
+
+/// Key of a top-level `key: value` line (None for continuation/comment lines)
+fn entry_key(line: &str) -> Option<&str> {
+    if line.starts_with([' ', '\t', '#', '-']) {
+        return None;
+    }
+    let (key, rest) = line.split_once(':')?;
+    if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+        return None;
+    }
+    Some(key.trim().trim_matches('"').trim_matches('\''))
+}
+
+/// Render `key: value` as YAML lines
+fn render_entry(key: &str, value: &FieldValue, block_indent: Option<&str>) -> Vec<String> {
+    match value {
+        FieldValue::Null => vec![format!("{}:", key)],
+        FieldValue::List(items) if items.is_empty() => vec![format!("{}: []", key)],
+        FieldValue::List(items) => {
+            let all_scalar = items
+                .iter()
+                .all(|v| !matches!(v, FieldValue::List(_) | FieldValue::Map(_)));
+            if all_scalar && block_indent.is_none() {
+                let rendered: Vec<String> = items.iter().map(|v| yaml_scalar(v, true)).collect();
+                vec![format!("{}: [{}]", key, rendered.join(", "))]
+            } else {
+                let mut lines = vec![format!("{}:", key)];
+                render_block(value, block_indent.unwrap_or("  "), &mut lines);
+                lines
+            }
+        }
+        FieldValue::Map(map) if map.is_empty() => vec![format!("{}: {{}}", key)],
+        FieldValue::Map(_) => {
+            let mut lines = vec![format!("{}:", key)];
+            render_block(value, "  ", &mut lines);
+            lines
+        }
+        scalar => vec![format!("{}: {}", key, yaml_scalar(scalar, false))],
+    }
+}
+
+/// Render a nested list/map as block YAML at the given indent
+fn render_block(value: &FieldValue, indent: &str, lines: &mut Vec<String>) {
+    let nested = format!("{}  ", indent);
+    match value {
+        FieldValue::List(items) => {
+            for item in items {
+                match item {
+                    FieldValue::List(l) if !l.is_empty() => {
+                        lines.push(format!("{}-", indent));
+                        render_block(item, &nested, lines);
+                    }
+                    FieldValue::Map(m) if !m.is_empty() => {
+                        lines.push(format!("{}-", indent));
+                        render_block(item, &nested, lines);
+                    }
+                    _ => lines.push(format!("{}- {}", indent, yaml_inline(item))),
+                }
+            }
+        }
+        FieldValue::Map(map) => {
+            for (k, v) in map {
+                let key = yaml_scalar(&FieldValue::String(k.clone()), false);
+                match v {
+                    FieldValue::List(l) if !l.is_empty() => {
+                        lines.push(format!("{}{}:", indent, key));
+                        render_block(v, &nested, lines);
+                    }
+                    FieldValue::Map(m) if !m.is_empty() => {
+                        lines.push(format!("{}{}:", indent, key));
+                        render_block(v, &nested, lines);
+                    }
+                    FieldValue::Null => lines.push(format!("{}{}:", indent, key)),
+                    _ => lines.push(format!("{}{}: {}", indent, key, yaml_inline(v))),
+                }
+            }
+        }
+        scalar => lines.push(format!("{}{}", indent, yaml_inline(scalar))),
+    }
+}
+
+/// Inline form of a value (scalars, or empty collections)
+fn yaml_inline(value: &FieldValue) -> String {
+    match value {
+        FieldValue::List(_) => "[]".to_string(),
+        FieldValue::Map(_) => "{}".to_string(),
+        scalar => yaml_scalar(scalar, false),
+    }
+}
+
+/// Render a scalar, quoting strings that would not read back unchanged
+fn yaml_scalar(value: &FieldValue, in_flow: bool) -> String {
+    match value {
+        FieldValue::Null => "null".to_string(),
+        FieldValue::Bool(b) => b.to_string(),
+        FieldValue::Integer(i) => i.to_string(),
+        FieldValue::Float(f) => format!("{:?}", f),
+        FieldValue::String(s) if is_plain_safe(s, in_flow) => s.clone(),
+        FieldValue::String(s) => serde_json::to_string(s).unwrap_or_default(),
+        FieldValue::List(_) | FieldValue::Map(_) => yaml_inline(value),
+    }
+}
+
+fn is_plain_safe(s: &str, in_flow: bool) -> bool {
+    if s.is_empty() || s.contains(['\n', '\r']) || s.trim() != s {
+        return false;
+    }
+    if in_flow && s.contains([',', '[', ']', '{', '}']) {
+        return false;
+    }
+    if s.contains(": ") || s.contains(" #") || s.ends_with(':') {
+        return false;
+    }
+    matches!(
+        serde_yaml::from_str::<serde_yaml::Value>(s),
+        Ok(serde_yaml::Value::String(parsed)) if parsed == s
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -650,4 +1173,167 @@ elysium_tags: [a, b]"#;
         let scalars = detect_folded_scalars(yaml);
         assert!(scalars.is_empty());
     }
+
+    #[test]
+    fn test_parse_block_sequence_and_quoted_commas() {
+        let content = r#"---
+elysium_type: note
+elysium_tags:
+  - gpu
+  - "memory, shared"
+elysium_source: https://example.com
+aliases: [GPU Notes]
+---
+"#;
+        let fm = Frontmatter::parse(content).unwrap();
+        assert_eq!(fm.tags(), vec!["gpu", "memory, shared"]);
+        // Scalar list fields are treated as one-item lists
+        assert_eq!(fm.source(), Some(vec!["https://example.com".to_string()]));
+        assert_eq!(
+            fm.other.get("aliases"),
+            Some(&FieldValue::List(vec![FieldValue::String(
+                "GPU Notes".to_string()
+            )]))
+        );
+    }
+
+    #[test]
+    fn test_parse_typed_values() {
+        let content = r#"---
+elysium_type: project
+elysium_priority: 3
+elysium_progress: 0.5
+elysium_pinned: true
+elysium_due:
+elysium_gist_date: 2024-03-01
+elysium_meta:
+  owner: june
+  reviewers: [a, b]
+---
+"#;
+        let fm = Frontmatter::parse(content).unwrap();
+        assert_eq!(fm.get("priority"), Some(&FieldValue::Integer(3)));
+        assert_eq!(fm.get("progress").and_then(|v| v.as_f64()), Some(0.5));
+        assert_eq!(fm.get("pinned").and_then(|v| v.as_bool()), Some(true));
+        assert_eq!(fm.get("due"), Some(&FieldValue::Null));
+        assert_eq!(
+            fm.get("gist_date").and_then(|v| v.as_date()),
+            NaiveDate::from_ymd_opt(2024, 3, 1)
+        );
+
+        let json = fm.to_json_map();
+        assert_eq!(json["priority"], serde_json::json!(3));
+        assert_eq!(json["pinned"], serde_json::json!(true));
+        assert_eq!(json["meta"]["owner"], serde_json::json!("june"));
+        assert_eq!(json["meta"]["reviewers"], serde_json::json!(["a", "b"]));
+    }
+
+    #[test]
+    fn test_parse_literal_gist_joined() {
+        let content = "---\nelysium_gist: |\n  First line.\n  Second line.\n---\n";
+        let fm = Frontmatter::parse(content).unwrap();
+        assert_eq!(fm.gist(), Some("First line. Second line."));
+    }
+
+    #[test]
+    fn test_parse_invalid_yaml_falls_back() {
+        let content = r#"---
+elysium_type: note
+elysium_status: active
+elysium_gist: Broken: value: here
+elysium_tags: [a, b]
+---
+"#;
+        let fm = Frontmatter::parse(content).unwrap();
+        assert_eq!(fm.note_type(), Some("note"));
+        assert_eq!(fm.tags(), vec!["a", "b"]);
+
+        let errors = fm.validate_with_config(&SchemaValidator::default());
+        assert!(errors
+            .iter()
+            .any(|e| matches!(e, SchemaViolation::YamlSyntaxError { .. })));
+    }
+
+    const EDIT_NOTE: &str = "---\n# Managed by elysium\nelysium_type: note\nelysium_status: active\nelysium_tags:\n  - gpu\n  - cuda\nelysium_source: [https://example.com]\naliases: [GPU]\n---\n\n# Body\n\nKeep me.\n";
+
+    #[test]
+    fn test_editor_set_preserves_layout() {
+        let mut editor = FrontmatterEditor::new(EDIT_NOTE);
+        assert!(editor.set("elysium_status", FieldValue::String("done".to_string())));
+        // Same value: no change
+        assert!(!editor.set("elysium_type", FieldValue::String("note".to_string())));
+
+        let content = editor.to_content();
+        assert_eq!(content, EDIT_NOTE.replace("status: active", "status: done"));
+    }
+
+    #[test]
+    fn test_editor_append_and_remove() {
+        let mut editor = FrontmatterEditor::new(EDIT_NOTE);
+        let added =
+            editor.append_to_list("elysium_tags", &["cuda".to_string(), "nvidia".to_string()]);
+        assert_eq!(added, vec!["nvidia"]);
+        assert!(editor.remove("elysium_source"));
+        assert!(!editor.remove("elysium_missing"));
+
+        let content = editor.to_content();
+        // Block list style and indent are kept
+        assert!(content.contains("elysium_tags:\n  - gpu\n  - cuda\n  - nvidia\n"));
+        assert!(!content.contains("elysium_source"));
+        assert!(content.contains("# Managed by elysium\n"));
+        assert!(content.contains("aliases: [GPU]\n"));
+        assert!(content.ends_with("---\n\n# Body\n\nKeep me.\n"));
+
+        let fm = Frontmatter::parse(&content).unwrap();
+        assert_eq!(fm.tags(), vec!["gpu", "cuda", "nvidia"]);
+    }
+
+    #[test]
+    fn test_editor_adds_new_keys_with_quoting() {
+        let mut editor = FrontmatterEditor::new(EDIT_NOTE);
+        editor.set(
+            "elysium_gist",
+            FieldValue::String("Note: quoted, because of the colon".to_string()),
+        );
+        editor.set("elysium_priority", FieldValue::Integer(2));
+        editor.set("elysium_version", FieldValue::String("1.0".to_string()));
+        editor.append_to_list("elysium_related", &["a, b".to_string()]);
+
+        let content = editor.to_content();
+        assert!(content
+            .contains("aliases: [GPU]\nelysium_gist: \"Note: quoted, because of the colon\"\n"));
+
+        let fm = Frontmatter::parse(&content).unwrap();
+        assert_eq!(fm.gist(), Some("Note: quoted, because of the colon"));
+        assert_eq!(fm.get("priority"), Some(&FieldValue::Integer(2)));
+        assert_eq!(fm.get_string("version"), Some("1.0"));
+        assert_eq!(fm.get_list("related"), Some(vec!["a, b".to_string()]));
+    }
+
+    #[test]
+    fn test_editor_creates_block_when_missing() {
+        let mut editor = FrontmatterEditor::new("# Title\n");
+        assert_eq!(editor.to_content(), "# Title\n");
+
+        editor.set("elysium_status", FieldValue::String("active".to_string()));
+        assert_eq!(
+            editor.to_content(),
+            "---\nelysium_status: active\n---\n\n# Title\n"
+        );
+    }
+
+    #[test]
+    fn test_frontmatter_diff() {
+        let old = Frontmatter::parse(EDIT_NOTE).unwrap();
+        let new = Frontmatter::parse(
+            "---\nelysium_type: note\nelysium_status: done\nelysium_area: tech\naliases: [GPU]\n---\n",
+        )
+        .unwrap();
+
+        let changes = old.diff(&new);
+        assert_eq!(changes.added, vec!["area"]);
+        assert_eq!(changes.updated, vec!["status"]);
+        assert_eq!(changes.removed, vec!["source", "tags"]);
+        assert!(old.diff(&old).is_empty());
+    }
 }
@@ -0,0 +1,324 @@
+//! Link graph: outgoing links, backlinks and unresolved links between notes
+//!
+//! Nodes are vault-relative note paths. Edges come from wikilinks and
+//! `[text](note.md)` links in prose, resolved with `LinkResolver`; links to
+//! attachments and self-links are not edges.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::path::Path;
+
+use super::note::{collect_all_notes, collect_vault_files, Note};
+use super::paths::{relative_path, VaultPaths};
+use super::wikilink::{is_markdown_path, LinkResolver};
+
+/// Link direction for neighbor queries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Outgoing,
+    Incoming,
+    Both,
+}
+
+impl Direction {
+    /// Parse a direction name (None if unknown)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "out" | "outgoing" => Some(Self::Outgoing),
+            "in" | "incoming" => Some(Self::Incoming),
+            "both" | "all" => Some(Self::Both),
+            _ => None,
+        }
+    }
+}
+
+/// In/out degree of a note (distinct linked notes)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Degree {
+    pub incoming: usize,
+    pub outgoing: usize,
+}
+
+impl Degree {
+    pub fn total(&self) -> usize {
+        self.incoming + self.outgoing
+    }
+}
+
+/// Vault-wide graph summary
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphStats {
+    pub note_count: usize,
+    /// Distinct note-to-note links
+    pub link_count: usize,
+    pub unresolved_count: usize,
+    /// Notes with no incoming and no outgoing links
+    pub isolated: Vec<String>,
+    /// Notes with incoming but no outgoing links
+    pub dead_ends: Vec<String>,
+    /// Notes with the highest total degree
+    pub hubs: Vec<(String, Degree)>,
+    /// Connected component sizes (undirected), largest first
+    pub components: Vec<usize>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct LinkGraph {
+    notes: BTreeSet<String>,
+    /// source -> target -> link occurrences
+    outgoing: HashMap<String, BTreeMap<String, usize>>,
+    /// target -> source -> link occurrences
+    incoming: HashMap<String, BTreeMap<String, usize>>,
+    /// source -> unresolved link targets (as written)
+    unresolved: HashMap<String, Vec<String>>,
+}
+
+impl LinkGraph {
+    /// Scan the vault and build the graph
+    #[allow(dead_code)]
+    pub fn from_vault(paths: &VaultPaths) -> Self {
+        let notes = collect_all_notes(paths);
+        let resolver = LinkResolver::new(collect_vault_files(paths));
+        Self::build(&notes, &paths.root, &resolver)
+    }
+
+    pub fn build(notes: &[Note], vault_root: &Path, resolver: &LinkResolver) -> Self {
+        let mut graph = Self::default();
+
+        for note in notes {
+            graph.notes.insert(relative_path(vault_root, &note.path));
+        }
+
+        for note in notes {
+            let source = relative_path(vault_root, &note.path);
+            for link in note.links() {
+                match resolver.resolve(&link, &source) {
+                    Some(target) if target == source || !is_markdown_path(target) => {}
+                    Some(target) => {
+                        *graph
+                            .outgoing
+                            .entry(source.clone())
+                            .or_default()
+                            .entry(target.to_string())
+                            .or_default() += 1;
+                        *graph
+                            .incoming
+                            .entry(target.to_string())
+                            .or_default()
+                            .entry(source.clone())
+                            .or_default() += 1;
+                    }
+                    None => {
+                        let targets = graph.unresolved.entry(source.clone()).or_default();
+                        if !targets.contains(&link.target) {
+                            targets.push(link.target.clone());
+                        }
+                    }
+                }
+            }
+        }
+
+        graph
+    }
+
+    /// Notes linked from `path` with occurrence counts
+    pub fn outlinks(&self, path: &str) -> Vec<(&str, usize)> {
+        Self::edges(self.outgoing.get(path))
+    }
+
+    /// Notes linking to `path` with occurrence counts
+    pub fn backlinks(&self, path: &str) -> Vec<(&str, usize)> {
+        Self::edges(self.incoming.get(path))
+    }
+
+    /// Link targets in `path` that do not resolve to any file
+    pub fn unresolved(&self, path: &str) -> &[String] {
+        self.unresolved.get(path).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn degree(&self, path: &str) -> Degree {
+        Degree {
+            incoming: self.incoming.get(path).map_or(0, BTreeMap::len),
+            outgoing: self.outgoing.get(path).map_or(0, BTreeMap::len),
+        }
+    }
+
+    /// Notes within `depth` hops of `path` (excluding it), nearest first
+    pub fn neighbors(
+        &self,
+        path: &str,
+        depth: usize,
+        direction: Direction,
+    ) -> Vec<(String, usize)> {
+        let mut distances: BTreeMap<String, usize> = BTreeMap::new();
+        let mut queue = VecDeque::from([(path.to_string(), 0)]);
+        distances.insert(path.to_string(), 0);
+
+        while let Some((current, distance)) = queue.pop_front() {
+            if distance >= depth {
+                continue;
+            }
+            for next in self.adjacent(&current, direction) {
+                if !distances.contains_key(next) {
+                    distances.insert(next.to_string(), distance + 1);
+                    queue.push_back((next.to_string(), distance + 1));
+                }
+            }
+        }
+
+        distances.remove(path);
+        let mut result: Vec<(String, usize)> = distances.into_iter().collect();
+        result.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+        result
+    }
+
+    /// Summary statistics; `top` limits the hub list
+    pub fn stats(&self, top: usize) -> GraphStats {
+        let mut isolated = Vec::new();
+        let mut dead_ends = Vec::new();
+        let mut hubs = Vec::new();
+
+        for note in &self.notes {
+            let degree = self.degree(note);
+            match (degree.incoming, degree.outgoing) {
+                (0, 0) => isolated.push(note.clone()),
+                (_, 0) => dead_ends.push(note.clone()),
+                _ => {}
+            }
+            if degree.total() > 0 {
+                hubs.push((note.clone(), degree));
+            }
+        }
+        hubs.sort_by(|a, b| b.1.total().cmp(&a.1.total()).then_with(|| a.0.cmp(&b.0)));
+        hubs.truncate(top);
+
+        GraphStats {
+            note_count: self.notes.len(),
+            link_count: self.outgoing.values().map(BTreeMap::len).sum(),
+            unresolved_count: self.unresolved.values().map(Vec::len).sum(),
+            isolated,
+            dead_ends,
+            hubs,
+            components: self.component_sizes(),
+        }
+    }
+
+    fn component_sizes(&self) -> Vec<usize> {
+        let mut seen: BTreeSet<&str> = BTreeSet::new();
+        let mut sizes = Vec::new();
+
+        for start in &self.notes {
+            if !seen.insert(start) {
+                continue;
+            }
+            let mut size = 0;
+            let mut stack = vec![start.as_str()];
+            while let Some(current) = stack.pop() {
+                size += 1;
+                for next in self.adjacent(current, Direction::Both) {
+                    if seen.insert(next) {
+                        stack.push(next);
+                    }
+                }
+            }
+            sizes.push(size);
+        }
+
+        sizes.sort_by(|a, b| b.cmp(a));
+        sizes
+    }
+
+    fn adjacent<'a>(&'a self, path: &str, direction: Direction) -> Vec<&'a str> {
+        let mut adjacent = Vec::new();
+        if direction != Direction::Incoming {
+            adjacent.extend(self.outgoing.get(path).into_iter().flat_map(|m| m.keys()));
+        }
+        if direction != Direction::Outgoing {
+            adjacent.extend(self.incoming.get(path).into_iter().flat_map(|m| m.keys()));
+        }
+        adjacent.into_iter().map(String::as_str).collect()
+    }
+
+    fn edges(map: Option<&BTreeMap<String, usize>>) -> Vec<(&str, usize)> {
+        map.map(|m| m.iter().map(|(k, v)| (k.as_str(), *v)).collect())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::write_note;
+    use tempfile::TempDir;
+
+    fn setup_graph() -> (TempDir, LinkGraph) {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write_note(
+            root,
+            "Hub.md",
+            "[[A]] [[B]] [[A#Intro]] [[Missing]] ![[pic.png]]",
+        );
+        write_note(root, "Notes/A.md", "# Intro\n[[C]] [[A]]");
+        write_note(root, "Notes/B.md", "[link](../Hub.md)");
+        write_note(root, "Notes/C.md", "```\n[[Hub]]\n```");
+        write_note(root, "Lonely.md", "No links.");
+        write_note(root, "pic.png", "");
+
+        let graph = LinkGraph::from_vault(&VaultPaths::from_root(root.to_path_buf()));
+        (temp, graph)
+    }
+
+    #[test]
+    fn test_outlinks_backlinks_unresolved() {
+        let (_temp, graph) = setup_graph();
+
+        assert_eq!(
+            graph.outlinks("Hub.md"),
+            vec![("Notes/A.md", 2), ("Notes/B.md", 1)]
+        );
+        assert_eq!(graph.backlinks("Notes/A.md"), vec![("Hub.md", 2)]);
+        assert_eq!(graph.unresolved("Hub.md"), ["Missing".to_string()]);
+        // Self-links and links in code blocks are not edges
+        assert_eq!(graph.outlinks("Notes/C.md"), vec![]);
+        assert_eq!(
+            graph.degree("Hub.md"),
+            Degree {
+                incoming: 1,
+                outgoing: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let (_temp, graph) = setup_graph();
+
+        assert_eq!(
+            graph.neighbors("Hub.md", 2, Direction::Outgoing),
+            vec![
+                ("Notes/A.md".to_string(), 1),
+                ("Notes/B.md".to_string(), 1),
+                ("Notes/C.md".to_string(), 2)
+            ]
+        );
+        assert_eq!(
+            graph.neighbors("Notes/C.md", 1, Direction::Incoming),
+            vec![("Notes/A.md".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_stats() {
+        let (_temp, graph) = setup_graph();
+        let stats = graph.stats(1);
+
+        assert_eq!(stats.note_count, 5);
+        assert_eq!(stats.link_count, 4);
+        assert_eq!(stats.unresolved_count, 1);
+        assert_eq!(stats.isolated, vec!["Lonely.md"]);
+        assert_eq!(stats.dead_ends, vec!["Notes/C.md"]);
+        assert_eq!(stats.hubs[0].0, "Hub.md");
+        assert_eq!(stats.components, vec![4, 1]);
+    }
+}
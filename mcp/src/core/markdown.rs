@@ -0,0 +1,434 @@
+//! Markdown-aware extraction (CommonMark AST via pulldown-cmark)
+//!
+//! Links, tags and headings are only taken from real prose: fenced/indented
+//! code, inline code, HTML (including comments), math and Obsidian `%%`
+//! comments are skipped. Wikilinks in frontmatter still count, as Obsidian
+//! treats links in properties as links.
+
+use lazy_static::lazy_static;
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+use regex::Regex;
+use std::ops::Range;
+
+use super::wikilink::{parse_wikilinks, LinkEdit, Wikilink};
+
+lazy_static! {
+    // Obsidian comment: %% ... %% (may span lines)
+    static ref OBSIDIAN_COMMENT_RE: Regex = Regex::new(r"(?s)%%.*?%%").unwrap();
+    // Inline tag: #tag, #nested/tag (must contain a non-digit)
+    static ref INLINE_TAG_RE: Regex =
+        Regex::new(r"(?:^|[\s(\[,;])#([\p{L}\p{N}_/-]*[\p{L}_/-][\p{L}\p{N}_/-]*)").unwrap();
+    // Block ID at the end of a line: "text ^block-id"
+    static ref BLOCK_ID_RE: Regex = Regex::new(r"(?m)(?:^|\s)\^([A-Za-z0-9-]+)[ \t]*$").unwrap();
+}
+
+/// Standard Markdown link to a vault file: `[text](note.md)` or `![alt](image.png)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownLink {
+    /// Decoded destination path (without `#fragment`)
+    pub target: String,
+    /// Fragment after `#` (heading, or `^block`)
+    pub fragment: Option<String>,
+    pub text: String,
+    pub is_embed: bool,
+}
+
+impl MarkdownLink {
+    /// Equivalent wikilink (for resolution and validation)
+    pub fn to_wikilink(&self) -> Wikilink {
+        let (heading, block) = match self.fragment.as_deref() {
+            Some(f) => match f.strip_prefix('^') {
+                Some(block) => (None, Some(block.to_string())),
+                None => (Some(f.to_string()), None),
+            },
+            None => (None, None),
+        };
+        Wikilink {
+            target: self.target.clone(),
+            heading,
+            block,
+            alias: (!self.text.is_empty()).then(|| self.text.clone()),
+            is_embed: self.is_embed,
+        }
+    }
+}
+
+impl std::fmt::Display for MarkdownLink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_embed {
+            write!(f, "!")?;
+        }
+        write!(f, "[{}]({}", self.text, self.target)?;
+        if let Some(fragment) = &self.fragment {
+            write!(f, "#{}", fragment)?;
+        }
+        write!(f, ")")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heading {
+    pub level: u8,
+    pub text: String,
+}
+
+/// Links, tags and anchors found in a note's prose
+#[derive(Debug, Default, Clone)]
+pub struct MarkdownContent {
+    pub wikilinks: Vec<Wikilink>,
+    pub links: Vec<MarkdownLink>,
+    /// Inline `#tags` (without `#`, in order of appearance, deduplicated)
+    pub tags: Vec<String>,
+    pub headings: Vec<Heading>,
+    pub block_ids: Vec<String>,
+}
+
+fn parser_options() -> Options {
+    Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_MATH
+        | Options::ENABLE_YAML_STYLE_METADATA_BLOCKS
+}
+
+/// Parse a note and extract links, tags and anchors from its prose
+pub fn parse(content: &str) -> MarkdownContent {
+    let mut result = MarkdownContent::default();
+    let mut skipped: Vec<Range<usize>> = Vec::new();
+    let mut body_start = 0;
+
+    let mut heading: Option<Heading> = None;
+    let mut link: Option<MarkdownLink> = None;
+
+    for (event, range) in Parser::new_ext(content, parser_options()).into_offset_iter() {
+        match event {
+            Event::Start(Tag::MetadataBlock(_)) => body_start = range.end,
+            Event::Code(text) => {
+                skipped.push(range);
+                if let Some(h) = heading.as_mut() {
+                    h.text.push_str(&text);
+                }
+            }
+            ref e if is_non_prose(e) => skipped.push(range),
+            Event::Start(Tag::Heading { level, .. }) => {
+                heading = Some(Heading {
+                    level: level as u8,
+                    text: String::new(),
+                });
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some(h) = heading.take() {
+                    result.headings.push(Heading {
+                        level: h.level,
+                        text: h.text.trim().to_string(),
+                    });
+                }
+            }
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                link = vault_link(&dest_url, false);
+            }
+            Event::Start(Tag::Image { dest_url, .. }) => {
+                link = vault_link(&dest_url, true);
+            }
+            Event::End(TagEnd::Link) | Event::End(TagEnd::Image) => {
+                if let Some(mut l) = link.take() {
+                    l.text = l.text.trim().to_string();
+                    result.links.push(l);
+                }
+            }
+            Event::Text(text) => {
+                if let Some(h) = heading.as_mut() {
+                    h.text.push_str(&text);
+                }
+                if let Some(l) = link.as_mut() {
+                    l.text.push_str(&text);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let prose = mask_ranges(content, &skipped);
+    result.wikilinks = parse_wikilinks(&prose);
+
+    let body = &prose[body_start.min(prose.len())..];
+    for caps in INLINE_TAG_RE.captures_iter(body) {
+        let tag = caps[1].trim_end_matches('/').to_string();
+        if !tag.is_empty() && !result.tags.contains(&tag) {
+            result.tags.push(tag);
+        }
+    }
+    result.block_ids = BLOCK_ID_RE
+        .captures_iter(body)
+        .map(|caps| caps[1].to_string())
+        .collect();
+
+    result
+}
+
+/// Rewrite the destination of every `[text](note.md)` link accepted by
+/// `matches`
+///
+/// `new_target` receives the link and returns the new path (decoded). The
+/// `#fragment`, title and `<...>` form are kept as written; spaces are
+/// written as `%20` outside angle brackets. Reference-style links and links
+/// in code are never rewritten.
+pub fn rewrite_links<M, T>(content: &str, matches: M, new_target: T) -> (String, Vec<LinkEdit>)
+where
+    M: Fn(&MarkdownLink) -> bool,
+    T: Fn(&MarkdownLink) -> String,
+{
+    let mut edits = Vec::new();
+    let mut output = String::with_capacity(content.len());
+    let mut last = 0;
+
+    for (event, range) in Parser::new_ext(content, parser_options()).into_offset_iter() {
+        let link = match &event {
+            Event::Start(Tag::Link { dest_url, .. }) => vault_link(dest_url, false),
+            Event::Start(Tag::Image { dest_url, .. }) => vault_link(dest_url, true),
+            _ => None,
+        };
+        let Some(link) = link.filter(|l| !l.target.is_empty() && matches(l)) else {
+            continue;
+        };
+        let Some(path) = destination_path(content, range.clone()) else {
+            continue;
+        };
+        // An image inside a rewritten link's text comes after it
+        if path.start < last {
+            continue;
+        }
+
+        let new = new_target(&link);
+        let angled = content[..path.start].ends_with('<');
+        let new = if angled { new } else { new.replace(' ', "%20") };
+        if content[path.clone()] == new {
+            continue;
+        }
+        output.push_str(&content[last..path.start]);
+        output.push_str(&new);
+        last = path.end;
+        edits.push(LinkEdit {
+            line: content[..range.start].matches('\n').count() + 1,
+            before: content[range.clone()].to_string(),
+            after: format!(
+                "{}{}{}",
+                &content[range.start..path.start],
+                new,
+                &content[path.end..range.end]
+            ),
+        });
+    }
+    output.push_str(&content[last..]);
+
+    (output, edits)
+}
+
+/// Byte range of the path in an inline link's `(destination "title")`
+/// (None for reference-style links)
+fn destination_path(content: &str, link: Range<usize>) -> Option<Range<usize>> {
+    let source = &content[link.clone()];
+    if !source.ends_with(')') {
+        return None;
+    }
+    let start = link.start + source.rfind("](")? + 2;
+    let destination = &content[start..link.end - 1];
+    let trimmed = destination.trim_start();
+    let start = start + (destination.len() - trimmed.len());
+
+    let (start, raw) = match trimmed.strip_prefix('<') {
+        Some(rest) => (start + 1, &rest[..rest.find('>')?]),
+        None => (start, trimmed.split_whitespace().next()?),
+    };
+    let len = raw.find('#').unwrap_or(raw.len());
+    Some(start..start + len)
+}
+
+/// Content with code, HTML, math and `%%` comments blanked out
+///
+/// Byte offsets and line numbers are unchanged, so matches found in the
+/// masked text can be applied to the original content.
+pub fn mask_non_prose(content: &str) -> String {
+    let skipped: Vec<Range<usize>> = Parser::new_ext(content, parser_options())
+        .into_offset_iter()
+        .filter(|(event, _)| is_non_prose(event))
+        .map(|(_, range)| range)
+        .collect();
+    mask_ranges(content, &skipped)
+}
+
+/// Events whose source text is not prose (code, HTML, math)
+fn is_non_prose(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::Start(Tag::CodeBlock(_))
+            | Event::Start(Tag::HtmlBlock)
+            | Event::Code(_)
+            | Event::Html(_)
+            | Event::InlineHtml(_)
+            | Event::InlineMath(_)
+            | Event::DisplayMath(_)
+    )
+}
+
+fn mask_ranges(content: &str, ranges: &[Range<usize>]) -> String {
+    // Ranges fall on character boundaries, so the result stays valid UTF-8
+    let mut bytes = content.as_bytes().to_vec();
+    for range in ranges {
+        blank(&mut bytes, range.clone());
+    }
+    let masked = String::from_utf8_lossy(&bytes).into_owned();
+
+    let comments: Vec<Range<usize>> = OBSIDIAN_COMMENT_RE
+        .find_iter(&masked)
+        .map(|m| m.range())
+        .collect();
+    if comments.is_empty() {
+        return masked;
+    }
+    let mut bytes = masked.into_bytes();
+    for range in comments {
+        blank(&mut bytes, range);
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+fn blank(bytes: &mut [u8], range: Range<usize>) {
+    let end = range.end.min(bytes.len());
+    for b in &mut bytes[range.start.min(end)..end] {
+        if *b != b'\n' && *b != b'\r' {
+            *b = b' ';
+        }
+    }
+}
+
+/// Parse a link destination that points into the vault (None for URLs)
+fn vault_link(dest: &str, is_embed: bool) -> Option<MarkdownLink> {
+    let dest = dest.trim().trim_start_matches('<').trim_end_matches('>');
+    let has_scheme = dest
+        .split_once(':')
+        .map(|(scheme, _)| {
+            !scheme.is_empty()
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+        })
+        .unwrap_or(false);
+    if dest.is_empty() || has_scheme {
+        return None;
+    }
+
+    let (path, fragment) = match dest.split_once('#') {
+        Some((path, fragment)) => (path, Some(percent_decode(fragment))),
+        None => (dest, None),
+    };
+
+    Some(MarkdownLink {
+        target: percent_decode(path),
+        fragment: fragment.filter(|f| !f.is_empty()),
+        text: String::new(),
+        is_embed,
+    })
+}
+
+/// Decode `%XX` escapes (Obsidian writes `My%20Note.md`)
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = |b: u8| (b as char).to_digit(16);
+            if let (Some(high), Some(low)) = (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                out.push((high * 16 + low) as u8);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTE: &str = r#"---
+elysium_tags: [rust]
+related: "[[Frontmatter Link]]"
+---
+# Title with `code`
+
+Real [[Prose Link|alias]] and [doc](Other%20Note.md#Setup) and #real-tag.
+![img](assets/pic.png) [web](https://example.com) #2024 is not a tag.
+
+```rust
+let x = "[[Code Link]]"; // #code-tag
+```
+
+Inline `[[Inline Code]]` and <!-- [[Comment Link]] #comment --> done.
+
+$$
+[[Math Link]]
+$$
+
+%% [[Obsidian Comment]] %%
+
+## Section
+
+A paragraph with a block ^para-1
+"#;
+
+    #[test]
+    fn test_parse_skips_code_html_math() {
+        let md = parse(NOTE);
+        let targets: Vec<&str> = md.wikilinks.iter().map(|l| l.target.as_str()).collect();
+        assert_eq!(targets, vec!["Frontmatter Link", "Prose Link"]);
+        assert_eq!(md.wikilinks[1].alias.as_deref(), Some("alias"));
+    }
+
+    #[test]
+    fn test_parse_markdown_links() {
+        let md = parse(NOTE);
+        assert_eq!(md.links.len(), 2);
+        assert_eq!(md.links[0].target, "Other Note.md");
+        assert_eq!(md.links[0].fragment.as_deref(), Some("Setup"));
+        assert_eq!(md.links[0].text, "doc");
+        assert!(md.links[1].is_embed);
+        assert_eq!(md.links[1].to_wikilink().target, "assets/pic.png");
+    }
+
+    #[test]
+    fn test_parse_tags_headings_blocks() {
+        let md = parse(NOTE);
+        assert_eq!(md.tags, vec!["real-tag"]);
+        assert_eq!(
+            md.headings,
+            vec![
+                Heading {
+                    level: 1,
+                    text: "Title with code".to_string()
+                },
+                Heading {
+                    level: 2,
+                    text: "Section".to_string()
+                },
+            ]
+        );
+        assert_eq!(md.block_ids, vec!["para-1"]);
+    }
+
+    #[test]
+    fn test_mask_keeps_offsets() {
+        let content = "a `[[x]]` b\n```\n[[y]]\n```\n[[z]]";
+        let masked = mask_non_prose(content);
+        assert_eq!(masked.len(), content.len());
+        assert_eq!(masked.lines().count(), content.lines().count());
+        assert!(!masked.contains("[[x]]"));
+        assert!(!masked.contains("[[y]]"));
+        assert!(masked.ends_with("[[z]]"));
+    }
+}
@@ -1,6 +1,12 @@
+pub mod archive;
+pub mod catalog;
 pub mod config;
 pub mod frontmatter;
+pub mod graph;
+pub mod markdown;
 pub mod note;
 pub mod paths;
+pub mod rename;
+pub mod resolve;
 pub mod schema;
 pub mod wikilink;
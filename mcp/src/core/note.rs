@@ -7,10 +7,12 @@ use chrono::{DateTime, Local};
 use walkdir::WalkDir;
 
 use super::frontmatter::{count_frontmatter_blocks, Frontmatter};
-use super::paths::VaultPaths;
+use super::markdown::{self, MarkdownContent};
+use super::paths::{relative_path, VaultPaths};
 use super::schema::{SchemaValidator, SchemaViolation};
-use super::wikilink::extract_wikilinks;
+use super::wikilink::Wikilink;
 
+#[derive(Clone)]
 pub struct Note {
     pub path: PathBuf,
     pub name: String,
@@ -82,8 +84,28 @@ impl Note {
         violations
     }
 
-    pub fn wikilinks(&self) -> Vec<String> {
-        extract_wikilinks(&self.content)
+    /// Parse prose for links, inline tags and anchors (code/comments skipped)
+    pub fn markdown(&self) -> MarkdownContent {
+        markdown::parse(&self.content)
+    }
+
+    #[allow(dead_code)]
+    pub fn wikilinks(&self) -> Vec<Wikilink> {
+        self.markdown().wikilinks
+    }
+
+    /// All outgoing links: wikilinks plus `[text](note.md)` links
+    pub fn links(&self) -> Vec<Wikilink> {
+        let md = self.markdown();
+        let mut links = md.wikilinks;
+        links.extend(md.links.iter().map(|l| l.to_wikilink()));
+        links
+    }
+
+    /// Inline `#tags` from the body (frontmatter tags: `tags()`)
+    #[allow(dead_code)]
+    pub fn inline_tags(&self) -> Vec<String> {
+        self.markdown().tags
     }
 
     pub fn tags(&self) -> Vec<String> {
@@ -110,6 +132,15 @@ impl Note {
         self.frontmatter.as_ref()?.gist()
     }
 
+    /// Obsidian `aliases` (a list or a single name)
+    pub fn aliases(&self) -> Vec<String> {
+        self.frontmatter
+            .as_ref()
+            .and_then(|fm| fm.other.get("aliases"))
+            .and_then(|aliases| aliases.as_string_list())
+            .unwrap_or_default()
+    }
+
     /// Get source URLs (elysium_source)
     #[allow(dead_code)]
     pub fn source(&self) -> Option<Vec<String>> {
@@ -132,8 +163,10 @@ impl Note {
     }
 }
 
-fn should_exclude_path(path: &Path) -> bool {
-    path.components().any(|c| {
+/// Exclude dot-folders (`.obsidian`, `.trash`, ...) below the vault root
+pub(crate) fn should_exclude_path(root: &Path, path: &Path) -> bool {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    relative.components().any(|c| {
         c.as_os_str()
             .to_str()
             .map(|s| s.starts_with('.'))
@@ -147,7 +180,7 @@ pub fn collect_all_notes(paths: &VaultPaths) -> Vec<Note> {
     for entry in WalkDir::new(&paths.root).into_iter().filter_map(|e| e.ok()) {
         let path = entry.path();
 
-        if should_exclude_path(path) {
+        if should_exclude_path(&paths.root, path) {
             continue;
         }
 
@@ -162,13 +195,27 @@ pub fn collect_all_notes(paths: &VaultPaths) -> Vec<Note> {
     notes
 }
 
+/// Vault-relative paths of every file (notes and attachments), dot-folders excluded
+pub fn collect_vault_files(paths: &VaultPaths) -> Vec<String> {
+    let mut files: Vec<String> = WalkDir::new(&paths.root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && !should_exclude_path(&paths.root, e.path()))
+        .map(|e| relative_path(&paths.root, e.path()))
+        .collect();
+
+    files.sort();
+    files
+}
+
+#[allow(dead_code)]
 pub fn collect_note_names(paths: &VaultPaths) -> HashSet<String> {
     let mut names = HashSet::new();
 
     for entry in WalkDir::new(&paths.root).into_iter().filter_map(|e| e.ok()) {
         let path = entry.path();
 
-        if should_exclude_path(path) {
+        if should_exclude_path(&paths.root, path) {
             continue;
         }
 
@@ -1,6 +1,6 @@
 //! Vault path management
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use super::config::Config;
 
@@ -46,6 +46,16 @@ impl Default for VaultPaths {
     }
 }
 
+/// Vault-relative path with forward slashes (as Obsidian reports it)
+pub fn relative_path(vault_path: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(vault_path).unwrap_or(path);
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
 pub fn get_vault_root() -> PathBuf {
     if let Ok(path) = std::env::var(VAULT_PATH_ENV) {
         let vault_path = PathBuf::from(&path);
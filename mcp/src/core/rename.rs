@@ -0,0 +1,316 @@
+//! Note rename/move with vault-wide link rewriting (wikilinks and
+//! `[text](note.md)` links)
+
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::markdown::{rewrite_links, MarkdownLink};
+use super::note::{collect_all_notes, collect_vault_files};
+use super::paths::{relative_path, VaultPaths};
+use super::wikilink::{rewrite_wikilinks, LinkResolver, Wikilink};
+
+/// A single link that will be (or was) rewritten
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RenameEdit {
+    /// Vault-relative path of the file containing the link
+    pub file: String,
+    /// 1-based line number
+    pub line: usize,
+    pub before: String,
+    pub after: String,
+}
+
+/// Planned rename: the move itself plus every file whose links change
+#[derive(Debug)]
+pub struct RenamePlan {
+    pub from: PathBuf,
+    pub to: PathBuf,
+    pub edits: Vec<RenameEdit>,
+    /// Rewritten content per file (paths before the move)
+    updated_files: Vec<(PathBuf, String)>,
+}
+
+impl RenamePlan {
+    /// Number of files whose content changes
+    pub fn files_changed(&self) -> usize {
+        self.updated_files.len()
+    }
+
+    /// Rewrite the moved note's content; applied together with the move
+    pub fn update_note<F>(&mut self, update: F) -> Result<()>
+    where
+        F: FnOnce(&str) -> String,
+    {
+        match self.updated_files.iter_mut().find(|(p, _)| *p == self.from) {
+            Some((_, content)) => *content = update(content),
+            None => {
+                let content = fs::read_to_string(&self.from)
+                    .with_context(|| format!("Failed to read {}", self.from.display()))?;
+                self.updated_files
+                    .push((self.from.clone(), update(&content)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Write rewritten links, then move the note
+    ///
+    /// If any step fails, files already written are restored.
+    pub fn apply(&self) -> Result<()> {
+        let mut originals = Vec::with_capacity(self.updated_files.len());
+        for (path, _) in &self.updated_files {
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            originals.push((path, content));
+        }
+
+        let result = self.write_and_move();
+        if result.is_err() {
+            for (path, content) in originals {
+                let _ = fs::write(path, content);
+            }
+        }
+        result
+    }
+
+    fn write_and_move(&self) -> Result<()> {
+        for (path, content) in &self.updated_files {
+            fs::write(path, content)
+                .with_context(|| format!("Failed to update links in {}", path.display()))?;
+        }
+
+        if let Some(parent) = self.to.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create folder {}", parent.display()))?;
+        }
+        fs::rename(&self.from, &self.to).with_context(|| {
+            format!(
+                "Failed to move {} to {}",
+                self.from.display(),
+                self.to.display()
+            )
+        })
+    }
+}
+
+/// Plan moving the note at `from` to `to` (both absolute paths)
+///
+/// Every link that `LinkResolver` resolves to the note is rewritten.
+/// Rewritten links keep their style: path links get the new path, name
+/// links the new name (or the new path if the new name is not unique), and
+/// `./` or `../` Markdown links a path relative to their note.
+pub fn plan_rename(paths: &VaultPaths, from: &Path, to: &Path) -> Result<RenamePlan> {
+    if !from.exists() {
+        bail!("Note not found: {}", from.display());
+    }
+    // Case-only renames point at the same file on case-insensitive filesystems
+    if to.exists() && fs::canonicalize(to).ok() != fs::canonicalize(from).ok() {
+        bail!("Target already exists: {}", to.display());
+    }
+    if to.extension().and_then(|e| e.to_str()) != Some("md") {
+        bail!("Target must be a markdown file: {}", to.display());
+    }
+
+    let notes = collect_all_notes(paths);
+    let resolver = LinkResolver::new(collect_vault_files(paths));
+
+    let old_rel = relative_path(&paths.root, from);
+    let new_rel = strip_md(&relative_path(&paths.root, to)).to_string();
+    let new_stem = stem_of(to).to_string();
+    let new_stem_unique = !notes
+        .iter()
+        .any(|n| n.path != from && n.name.eq_ignore_ascii_case(&new_stem));
+
+    let new_target = |link: &Wikilink| {
+        let base = if link.target.contains('/') || !new_stem_unique {
+            new_rel.clone()
+        } else {
+            new_stem.clone()
+        };
+        if has_md_extension(&link.target) {
+            format!("{}.md", base)
+        } else {
+            base
+        }
+    };
+
+    let mut edits = Vec::new();
+    let mut updated_files = Vec::new();
+
+    for note in &notes {
+        let source = relative_path(&paths.root, &note.path);
+        // Links inside the moved note are reported under its new path
+        let file_path = if note.path == from { to } else { &note.path };
+        let file = relative_path(&paths.root, file_path);
+
+        let matches = |link: &Wikilink| resolver.resolve(link, &source) == Some(old_rel.as_str());
+        let (content, mut note_edits) = rewrite_wikilinks(&note.content, matches, new_target);
+        let (content, markdown_edits) = rewrite_links(
+            &content,
+            |link: &MarkdownLink| matches(&link.to_wikilink()),
+            |link: &MarkdownLink| {
+                if link.target.starts_with("./") || link.target.starts_with("../") {
+                    let target = if has_md_extension(&link.target) {
+                        format!("{}.md", new_rel)
+                    } else {
+                        new_rel.clone()
+                    };
+                    relative_link(&file, &target)
+                } else {
+                    new_target(&link.to_wikilink())
+                }
+            },
+        );
+        note_edits.extend(markdown_edits);
+        if note_edits.is_empty() {
+            continue;
+        }
+        note_edits.sort_by_key(|e| e.line);
+
+        edits.extend(note_edits.into_iter().map(|e| RenameEdit {
+            file: file.clone(),
+            line: e.line,
+            before: e.before,
+            after: e.after,
+        }));
+        updated_files.push((note.path.clone(), content));
+    }
+
+    Ok(RenamePlan {
+        from: from.to_path_buf(),
+        to: to.to_path_buf(),
+        edits,
+        updated_files,
+    })
+}
+
+/// Path of `target` relative to the folder of `source` (both vault-relative)
+fn relative_link(source: &str, target: &str) -> String {
+    let from: Vec<&str> = source.split('/').collect();
+    let from = &from[..from.len() - 1];
+    let to: Vec<&str> = target.split('/').collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    if common == from.len() {
+        return format!("./{}", to[common..].join("/"));
+    }
+    let mut parts = vec![".."; from.len() - common];
+    parts.extend(&to[common..]);
+    parts.join("/")
+}
+
+fn has_md_extension(target: &str) -> bool {
+    target.to_lowercase().ends_with(".md")
+}
+
+fn strip_md(target: &str) -> &str {
+    if has_md_extension(target) {
+        &target[..target.len() - 3]
+    } else {
+        target
+    }
+}
+
+fn stem_of(path: &Path) -> &str {
+    path.file_stem().and_then(|s| s.to_str()).unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::write_note;
+    use tempfile::TempDir;
+
+    fn setup_vault() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        write_note(
+            temp.path(),
+            "Notes/GPU Memory.md",
+            "# GPU\n\nSee [[gpu memory#Setup]].\n",
+        );
+        write_note(
+            temp.path(),
+            "Notes/Index.md",
+            "- [[GPU Memory]]\n- [[GPU Memory|memory notes]]\n- ![[Notes/GPU Memory.md^abc]]\n- [[Other]]\n",
+        );
+        write_note(temp.path(), "Other.md", "Nothing here.\n");
+        temp
+    }
+
+    #[test]
+    fn test_plan_and_apply_rename() -> Result<()> {
+        let temp = setup_vault();
+        let paths = VaultPaths::from_root(temp.path().to_path_buf());
+        let from = temp.path().join("Notes/GPU Memory.md");
+        let to = temp.path().join("Archive/GPU Sharing.md");
+
+        let plan = plan_rename(&paths, &from, &to)?;
+        assert_eq!(plan.files_changed(), 2);
+        assert_eq!(plan.edits.len(), 4);
+
+        let self_link = plan
+            .edits
+            .iter()
+            .find(|e| e.file == "Archive/GPU Sharing.md")
+            .unwrap();
+        assert_eq!(self_link.after, "[[GPU Sharing#Setup]]");
+
+        // Dry run: nothing written yet
+        assert!(from.exists());
+
+        plan.apply()?;
+        assert!(!from.exists());
+        assert!(to.exists());
+
+        let index = fs::read_to_string(temp.path().join("Notes/Index.md"))?;
+        assert_eq!(
+            index,
+            "- [[GPU Sharing]]\n- [[GPU Sharing|memory notes]]\n- ![[Archive/GPU Sharing.md^abc]]\n- [[Other]]\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_rename_rewrites_markdown_links() -> Result<()> {
+        let temp = setup_vault();
+        write_note(
+            temp.path(),
+            "Notes/Links.md",
+            "[memory](GPU%20Memory.md#Setup), [rel](./GPU%20Memory.md \"Title\")\n![](<Notes/GPU Memory.md>) `[code](GPU%20Memory.md)` [web](https://x.org)\n",
+        );
+        write_note(temp.path(), "Other.md", "[up](Notes/GPU%20Memory.md)\n");
+        let paths = VaultPaths::from_root(temp.path().to_path_buf());
+        let from = temp.path().join("Notes/GPU Memory.md");
+        let to = temp.path().join("Archive/GPU Sharing.md");
+
+        let plan = plan_rename(&paths, &from, &to)?;
+        let links: Vec<&RenameEdit> = plan
+            .edits
+            .iter()
+            .filter(|e| e.file == "Notes/Links.md")
+            .collect();
+        assert_eq!(links.len(), 3);
+        assert_eq!(links[0].before, "[memory](GPU%20Memory.md#Setup)");
+        plan.apply()?;
+
+        assert_eq!(
+            fs::read_to_string(temp.path().join("Notes/Links.md"))?,
+            "[memory](GPU%20Sharing.md#Setup), [rel](../Archive/GPU%20Sharing.md \"Title\")\n![](<Archive/GPU Sharing.md>) `[code](GPU%20Memory.md)` [web](https://x.org)\n"
+        );
+        assert_eq!(
+            fs::read_to_string(temp.path().join("Other.md"))?,
+            "[up](Archive/GPU%20Sharing.md)\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_rename_rejects_existing_target() {
+        let temp = setup_vault();
+        let paths = VaultPaths::from_root(temp.path().to_path_buf());
+        let from = temp.path().join("Notes/GPU Memory.md");
+        let to = temp.path().join("Other.md");
+
+        assert!(plan_rename(&paths, &from, &to).is_err());
+    }
+}
@@ -0,0 +1,356 @@
+//! Note lookup by name for tool parameters
+//!
+//! A note name is resolved by the first tier with any match:
+//! 1. vault-relative path (`.md` optional)
+//! 2. exact title (file stem)
+//! 3. alias (Obsidian `aliases`, case-insensitive)
+//! 4. title or path, case-insensitive
+//! 5. part of the title or path, case-insensitive
+//!
+//! Several matches in the deciding tier are reported as ambiguous instead of
+//! picking one. Without a match, titles and aliases within a small edit
+//! distance are suggested ("did you mean").
+//!
+//! Writes use `resolve_note_strict`, which stops at tier 4: partial matches
+//! are only suggested, so a guess never changes the wrong note.
+
+use std::fmt;
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::note::Note;
+use super::paths::relative_path;
+
+/// Most candidates listed in an error
+const MAX_CANDIDATES: usize = 10;
+
+/// Match kind and test (note, vault-relative path) of a resolution tier
+type Tier<'a> = (MatchKind, &'a dyn Fn(&Note, &str) -> bool);
+
+/// How a candidate matched the query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchKind {
+    Path,
+    Title,
+    Alias,
+    CaseInsensitive,
+    Partial,
+    /// Within edit distance (suggestion only)
+    Similar,
+}
+
+/// A note that matched (or nearly matched) the query
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Candidate {
+    pub title: String,
+    /// Vault-relative path
+    pub path: String,
+    #[serde(rename = "match")]
+    pub kind: MatchKind,
+    /// Edit distance for suggestions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distance: Option<usize>,
+}
+
+/// Why a note name did not resolve to one note
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResolveError {
+    /// Several notes match equally well
+    Ambiguous {
+        query: String,
+        candidates: Vec<Candidate>,
+    },
+    /// No note matches; similar names, closest first
+    NotFound {
+        query: String,
+        suggestions: Vec<Candidate>,
+    },
+}
+
+impl ResolveError {
+    /// Ambiguous candidates or "did you mean" suggestions
+    pub fn candidates(&self) -> &[Candidate] {
+        match self {
+            ResolveError::Ambiguous { candidates, .. } => candidates,
+            ResolveError::NotFound { suggestions, .. } => suggestions,
+        }
+    }
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResolveError::Ambiguous { query, candidates } => write!(
+                f,
+                "Note '{}' is ambiguous: {} notes match (use the path)",
+                query,
+                candidates.len()
+            ),
+            ResolveError::NotFound { query, suggestions } => match suggestions.first() {
+                Some(best) => write!(
+                    f,
+                    "Note '{}' not found. Did you mean '{}'?",
+                    query, best.title
+                ),
+                None => write!(f, "Note '{}' not found", query),
+            },
+        }
+    }
+}
+
+impl std::error::Error for ResolveError {}
+
+/// Find the note a name refers to
+pub fn resolve_note<'a>(
+    notes: &'a [Note],
+    vault_path: &Path,
+    query: &str,
+) -> Result<&'a Note, ResolveError> {
+    resolve(notes, vault_path, query, false)
+}
+
+/// Find the note a name refers to, without partial matches (for writes)
+///
+/// Notes that only contain the name are returned as suggestions.
+pub fn resolve_note_strict<'a>(
+    notes: &'a [Note],
+    vault_path: &Path,
+    query: &str,
+) -> Result<&'a Note, ResolveError> {
+    resolve(notes, vault_path, query, true)
+}
+
+fn resolve<'a>(
+    notes: &'a [Note],
+    vault_path: &Path,
+    query: &str,
+    strict: bool,
+) -> Result<&'a Note, ResolveError> {
+    let query = query.trim();
+    let query_path = query.trim_start_matches('/').trim_end_matches(".md");
+    let lower = query.to_lowercase();
+    let lower_path = query_path.to_lowercase();
+
+    let entries: Vec<(&Note, String)> = notes
+        .iter()
+        .map(|n| {
+            let path = relative_path(vault_path, &n.path);
+            (n, path)
+        })
+        .collect();
+    let without_ext = |path: &str| path.strip_suffix(".md").unwrap_or(path).to_string();
+
+    let tiers: [Tier; 5] = [
+        (MatchKind::Path, &|_, path| without_ext(path) == query_path),
+        (MatchKind::Title, &|n, _| n.name == query),
+        (MatchKind::Alias, &|n, _| {
+            n.aliases().iter().any(|a| a.to_lowercase() == lower)
+        }),
+        (MatchKind::CaseInsensitive, &|n, path| {
+            n.name.to_lowercase() == lower || without_ext(path).to_lowercase() == lower_path
+        }),
+        (MatchKind::Partial, &|n, path| {
+            !lower.is_empty()
+                && (n.name.to_lowercase().contains(&lower)
+                    || path.to_lowercase().contains(&lower_path))
+        }),
+    ];
+
+    let mut partial = Vec::new();
+    for (kind, matches) in tiers {
+        let mut found: Vec<&(&Note, String)> = entries
+            .iter()
+            .filter(|(n, path)| matches(n, path))
+            .collect();
+        if strict && kind == MatchKind::Partial {
+            found.sort_by(|a, b| a.1.cmp(&b.1));
+            partial = found
+                .into_iter()
+                .map(|(n, path)| candidate(n, path, kind, None))
+                .collect();
+            break;
+        }
+        match found.len() {
+            0 => continue,
+            1 => return Ok(found[0].0),
+            _ => {
+                found.sort_by(|a, b| a.1.cmp(&b.1));
+                let candidates = found
+                    .into_iter()
+                    .take(MAX_CANDIDATES)
+                    .map(|(n, path)| candidate(n, path, kind, None))
+                    .collect();
+                return Err(ResolveError::Ambiguous {
+                    query: query.to_string(),
+                    candidates,
+                });
+            }
+        }
+    }
+
+    // Titles and aliases within a few edits
+    let max_distance = (query.chars().count() / 3).clamp(1, 3);
+    let mut suggestions: Vec<Candidate> = entries
+        .iter()
+        .filter_map(|(n, path)| {
+            let distance = std::iter::once(n.name.clone())
+                .chain(n.aliases())
+                .map(|name| edit_distance(&name.to_lowercase(), &lower))
+                .min()?;
+            (distance <= max_distance)
+                .then(|| candidate(n, path, MatchKind::Similar, Some(distance)))
+        })
+        .collect();
+    suggestions.sort_by(|a, b| a.distance.cmp(&b.distance).then(a.path.cmp(&b.path)));
+    // Partial matches (strict mode) first
+    suggestions.retain(|s| !partial.iter().any(|p: &Candidate| p.path == s.path));
+    partial.append(&mut suggestions);
+    let mut suggestions = partial;
+    suggestions.truncate(MAX_CANDIDATES);
+
+    Err(ResolveError::NotFound {
+        query: query.to_string(),
+        suggestions,
+    })
+}
+
+fn candidate(note: &Note, path: &str, kind: MatchKind, distance: Option<usize>) -> Candidate {
+    Candidate {
+        title: note.name.clone(),
+        path: path.to_string(),
+        kind,
+        distance,
+    }
+}
+
+/// Edit distance over characters: insertions, deletions, substitutions and
+/// swaps of adjacent characters (optimal string alignment)
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    // Rows i-2, i-1 and i of the distance matrix
+    let mut before: Vec<usize> = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j - 1] + cost)
+                .min(previous[j] + 1)
+                .min(current[j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut before, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::frontmatter::Frontmatter;
+    use chrono::Local;
+    use std::path::PathBuf;
+
+    fn note(path: &str, content: &str) -> Note {
+        let path = PathBuf::from("/vault").join(path);
+        Note {
+            name: path.file_stem().unwrap().to_string_lossy().to_string(),
+            path,
+            content: content.to_string(),
+            frontmatter: Frontmatter::parse(content),
+            modified: Local::now(),
+            created: Local::now(),
+        }
+    }
+
+    fn notes() -> Vec<Note> {
+        vec![
+            note(
+                "Tech/GPU MIG.md",
+                "---\naliases: [Multi-Instance GPU]\n---\n",
+            ),
+            note("Tech/eGPU setup.md", ""),
+            note("Tech/CUDA.md", ""),
+            note("Logs/CUDA.md", ""),
+            note("Notes/Kubernetes.md", "---\naliases: k8s\n---\n"),
+        ]
+    }
+
+    fn resolve(query: &str) -> Result<String, ResolveError> {
+        let notes = notes();
+        resolve_note(&notes, Path::new("/vault"), query)
+            .map(|n| relative_path(Path::new("/vault"), &n.path))
+    }
+
+    #[test]
+    fn test_resolve_tiers() {
+        assert_eq!(resolve("GPU MIG").unwrap(), "Tech/GPU MIG.md");
+        assert_eq!(resolve("Logs/CUDA.md").unwrap(), "Logs/CUDA.md");
+        assert_eq!(resolve("multi-instance gpu").unwrap(), "Tech/GPU MIG.md");
+        assert_eq!(resolve("K8S").unwrap(), "Notes/Kubernetes.md");
+        assert_eq!(resolve("kubernetes").unwrap(), "Notes/Kubernetes.md");
+        assert_eq!(resolve("setup").unwrap(), "Tech/eGPU setup.md");
+    }
+
+    #[test]
+    fn test_resolve_ambiguous_and_suggestions() {
+        let Err(ResolveError::Ambiguous { candidates, .. }) = resolve("GPU") else {
+            panic!("GPU should be ambiguous");
+        };
+        let paths: Vec<&str> = candidates.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, vec!["Tech/GPU MIG.md", "Tech/eGPU setup.md"]);
+        assert_eq!(candidates[0].kind, MatchKind::Partial);
+
+        let error = resolve("CUDA").unwrap_err();
+        assert_eq!(error.candidates().len(), 2);
+        assert_eq!(
+            error.to_string(),
+            "Note 'CUDA' is ambiguous: 2 notes match (use the path)"
+        );
+
+        let error = resolve("Kubernetis").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Note 'Kubernetis' not found. Did you mean 'Kubernetes'?"
+        );
+        assert_eq!(error.candidates()[0].distance, Some(1));
+        assert!(resolve("zzz").unwrap_err().candidates().is_empty());
+    }
+
+    #[test]
+    fn test_resolve_strict() {
+        let notes = notes();
+        let resolve = |query| {
+            resolve_note_strict(&notes, Path::new("/vault"), query)
+                .map(|n| relative_path(Path::new("/vault"), &n.path))
+        };
+        assert_eq!(resolve("gpu mig").unwrap(), "Tech/GPU MIG.md");
+        assert_eq!(resolve("k8s").unwrap(), "Notes/Kubernetes.md");
+
+        // A partial match is only suggested
+        let Err(ResolveError::NotFound { suggestions, .. }) = resolve("setup") else {
+            panic!("strict resolution should not accept a partial match");
+        };
+        assert_eq!(suggestions[0].path, "Tech/eGPU setup.md");
+        assert_eq!(suggestions[0].kind, MatchKind::Partial);
+        assert!(matches!(
+            resolve("CUDA"),
+            Err(ResolveError::Ambiguous { .. })
+        ));
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("메모리", "메머리"), 1);
+        assert_eq!(edit_distance("alpah", "alpha"), 1);
+    }
+}
@@ -1,19 +1,390 @@
 use lazy_static::lazy_static;
 use regex::Regex;
+use serde::Serialize;
 use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use super::markdown;
 
 lazy_static! {
-    // [[target]] or [[target|display]]
-    static ref WIKILINK_RE: Regex = Regex::new(r"\[\[([^\]|]+)(?:\|[^\]]+)?\]\]").unwrap();
+    // ![[target#heading^block|alias]] split into parts (embed, target, suffix, alias)
+    static ref WIKILINK_PARTS_RE: Regex =
+        Regex::new(r"(!?)\[\[([^\]|#^]*)([#^][^\]|]*)?(?:\|([^\]]*))?\]\]").unwrap();
 }
 
-pub fn extract_wikilinks(content: &str) -> Vec<String> {
-    WIKILINK_RE
+/// A parsed wikilink: `[[target#heading|alias]]`, `[[target#^block]]`, `![[embed.png]]`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Wikilink {
+    /// Link target as written (note name, path, or file with extension); empty for same-note links
+    pub target: String,
+    /// Heading reference (nested headings keep their `#` separators: "A#B")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heading: Option<String>,
+    /// Block reference ID (without `^`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block: Option<String>,
+    /// Display text after `|`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    /// `![[...]]` embed
+    pub is_embed: bool,
+}
+
+impl Wikilink {
+    fn from_captures(caps: &regex::Captures) -> Self {
+        let suffix = caps.get(3).map(|m| m.as_str()).unwrap_or("");
+        let (heading, block) = if let Some(block) = suffix.strip_prefix("#^") {
+            (None, Some(block))
+        } else if let Some(heading) = suffix.strip_prefix('#') {
+            match heading.split_once("#^") {
+                Some((heading, block)) => (Some(heading), Some(block)),
+                None => (Some(heading), None),
+            }
+        } else {
+            (None, suffix.strip_prefix('^'))
+        };
+
+        let non_empty = |s: &str| {
+            let s = s.trim();
+            (!s.is_empty()).then(|| s.to_string())
+        };
+
+        Self {
+            // Tables escape the alias pipe: [[Note\|alias]]
+            target: caps[2].trim().trim_end_matches('\\').trim_end().to_string(),
+            heading: heading.and_then(non_empty),
+            block: block.and_then(non_empty),
+            alias: caps.get(4).and_then(|m| non_empty(m.as_str())),
+            is_embed: &caps[1] == "!",
+        }
+    }
+}
+
+impl std::fmt::Display for Wikilink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_embed {
+            write!(f, "!")?;
+        }
+        write!(f, "[[{}", self.target)?;
+        if let Some(heading) = &self.heading {
+            write!(f, "#{}", heading)?;
+        }
+        if let Some(block) = &self.block {
+            write!(f, "#^{}", block)?;
+        }
+        if let Some(alias) = &self.alias {
+            write!(f, "|{}", alias)?;
+        }
+        write!(f, "]]")
+    }
+}
+
+/// Parse every wikilink in plain text
+///
+/// Does not skip code or comments; use `markdown::parse` (or
+/// `extract_wikilinks`) for note content.
+pub fn parse_wikilinks(content: &str) -> Vec<Wikilink> {
+    WIKILINK_PARTS_RE
         .captures_iter(content)
-        .map(|c| c[1].trim().to_string())
+        .map(|caps| Wikilink::from_captures(&caps))
+        .collect()
+}
+
+/// Link targets in a note's prose (without heading/block/alias parts)
+pub fn extract_wikilinks(content: &str) -> Vec<String> {
+    markdown::parse(content)
+        .wikilinks
+        .into_iter()
+        .filter(|l| !l.target.is_empty())
+        .map(|l| l.target)
         .collect()
 }
 
+/// A rewritten wikilink occurrence
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkEdit {
+    /// 1-based line number
+    pub line: usize,
+    pub before: String,
+    pub after: String,
+}
+
+/// Rewrite the target of every wikilink accepted by `matches`
+///
+/// `new_target` receives the link and returns the replacement target text;
+/// embed markers, `#heading`/`^block` suffixes and aliases are kept as
+/// written. Same-note links (`[[#Heading]]`) and links inside code, HTML,
+/// math or comments are never rewritten.
+pub fn rewrite_wikilinks<M, T>(content: &str, matches: M, new_target: T) -> (String, Vec<LinkEdit>)
+where
+    M: Fn(&Wikilink) -> bool,
+    T: Fn(&Wikilink) -> String,
+{
+    let mut edits = Vec::new();
+    let mut output = String::with_capacity(content.len());
+    let mut last = 0;
+
+    // Match on masked text (same offsets), splice into the original
+    let prose = markdown::mask_non_prose(content);
+    for caps in WIKILINK_PARTS_RE.captures_iter(&prose) {
+        let link = Wikilink::from_captures(&caps);
+        if link.target.is_empty() || !matches(&link) {
+            continue;
+        }
+
+        let whole = caps.get(0).expect("match");
+        let escape = if caps[2].trim_end().ends_with('\\') {
+            "\\"
+        } else {
+            ""
+        };
+        let mut rewritten = format!("{}[[{}{}", &caps[1], new_target(&link), escape);
+        if let Some(suffix) = caps.get(3) {
+            rewritten.push_str(suffix.as_str());
+        }
+        if let Some(alias) = caps.get(4) {
+            rewritten.push('|');
+            rewritten.push_str(alias.as_str());
+        }
+        rewritten.push_str("]]");
+
+        if rewritten != whole.as_str() {
+            output.push_str(&content[last..whole.start()]);
+            output.push_str(&rewritten);
+            last = whole.end();
+            edits.push(LinkEdit {
+                line: content[..whole.start()].matches('\n').count() + 1,
+                before: whole.as_str().to_string(),
+                after: rewritten,
+            });
+        }
+    }
+    output.push_str(&content[last..]);
+
+    (output, edits)
+}
+
+// =========================================
+// Link Resolution
+// =========================================
+
+/// Resolves wikilink targets to vault files the way Obsidian does
+///
+/// Matching is case-insensitive. A target is tried as written (attachments
+/// such as `diagram.png`) and with `.md` appended. Path targets
+/// (`folder/Note`) match the vault-relative path or its tail; bare names
+/// prefer a file in the source note's folder, then the shortest path. `./` and
+/// `../` targets are resolved against the source note's folder.
+#[derive(Debug, Default, Clone)]
+pub struct LinkResolver {
+    /// Vault-relative paths (forward slashes)
+    files: Vec<String>,
+    /// Lowercased paths, parallel to `files`
+    lower: Vec<String>,
+    /// Lowercased file name -> indices into `files`
+    by_name: HashMap<String, Vec<usize>>,
+}
+
+impl LinkResolver {
+    /// Build from vault-relative file paths (notes and attachments)
+    pub fn new<I, S>(files: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut resolver = Self::default();
+        for file in files {
+            let file: String = file.into().replace('\\', "/");
+            let lower = file.to_lowercase();
+            let name = lower.rsplit('/').next().unwrap_or(&lower).to_string();
+            resolver
+                .by_name
+                .entry(name)
+                .or_default()
+                .push(resolver.files.len());
+            resolver.files.push(file);
+            resolver.lower.push(lower);
+        }
+        resolver
+    }
+
+    /// Resolve a link found in `source` (vault-relative path) to a vault-relative path
+    pub fn resolve(&self, link: &Wikilink, source: &str) -> Option<&str> {
+        let target = link
+            .target
+            .trim()
+            .trim_start_matches('/')
+            .replace('\\', "/");
+        if target.is_empty() {
+            return self.find_exact(&source.to_lowercase());
+        }
+        if target.starts_with("./") || target.starts_with("../") {
+            return relative_to(parent_dir(source), &target).and_then(|path| {
+                let path = path.to_lowercase();
+                self.find_exact(&path)
+                    .or_else(|| self.find_exact(&format!("{}.md", path)))
+            });
+        }
+
+        let target = target.to_lowercase();
+        self.lookup(&target, source)
+            .or_else(|| self.lookup(&format!("{}.md", target), source))
+    }
+
+    fn find_exact(&self, lower_path: &str) -> Option<&str> {
+        self.lower
+            .iter()
+            .position(|p| p == lower_path)
+            .map(|i| self.files[i].as_str())
+    }
+
+    fn lookup(&self, target: &str, source: &str) -> Option<&str> {
+        let name = target.rsplit('/').next().unwrap_or(target);
+        let candidates = self.by_name.get(name)?;
+
+        if target.contains('/') {
+            if let Some(&i) = candidates.iter().find(|&&i| self.lower[i] == target) {
+                return Some(&self.files[i]);
+            }
+            let tail = format!("/{}", target);
+            return candidates
+                .iter()
+                .filter(|&&i| self.lower[i].ends_with(&tail))
+                .min_by_key(|&&i| (self.lower[i].len(), &self.lower[i]))
+                .map(|&i| self.files[i].as_str());
+        }
+
+        let source_dir = parent_dir(&source.to_lowercase()).to_string();
+        if let Some(&i) = candidates
+            .iter()
+            .find(|&&i| parent_dir(&self.lower[i]) == source_dir)
+        {
+            return Some(&self.files[i]);
+        }
+        candidates
+            .iter()
+            .min_by_key(|&&i| (self.lower[i].matches('/').count(), &self.lower[i]))
+            .map(|&i| self.files[i].as_str())
+    }
+}
+
+fn parent_dir(path: &str) -> &str {
+    path.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("")
+}
+
+/// Join a `./` or `../` target onto `dir` (None if it leaves the vault)
+fn relative_to(dir: &str, target: &str) -> Option<String> {
+    let mut parts: Vec<&str> = dir.split('/').filter(|p| !p.is_empty()).collect();
+    for part in target.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            _ => parts.push(part),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+/// Headings and block IDs of a note (link anchors)
+#[derive(Debug, Default, Clone)]
+pub struct NoteAnchors {
+    headings: HashSet<String>,
+    blocks: HashSet<String>,
+}
+
+impl NoteAnchors {
+    pub fn from_content(content: &str) -> Self {
+        let md = markdown::parse(content);
+        Self {
+            headings: md
+                .headings
+                .iter()
+                .map(|h| normalize_heading(&h.text))
+                .collect(),
+            blocks: md.block_ids.iter().map(|b| b.to_lowercase()).collect(),
+        }
+    }
+
+    /// Check a heading reference (every level of `A#B` must exist)
+    pub fn has_heading(&self, heading: &str) -> bool {
+        heading
+            .split('#')
+            .filter(|h| !h.trim().is_empty())
+            .all(|h| self.headings.contains(&normalize_heading(h)))
+    }
+
+    pub fn has_block(&self, block: &str) -> bool {
+        self.blocks.contains(&block.trim().to_lowercase())
+    }
+}
+
+/// Obsidian drops link-breaking characters from heading links and ignores case
+fn normalize_heading(heading: &str) -> String {
+    heading
+        .replace(['#', '|', '^', ':', '%', '[', ']'], " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Why a wikilink does not resolve
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkProblem {
+    UnresolvedTarget,
+    UnknownHeading(String),
+    UnknownBlock(String),
+}
+
+impl std::fmt::Display for LinkProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnresolvedTarget => write!(f, "target not found"),
+            Self::UnknownHeading(h) => write!(f, "heading not found: #{}", h),
+            Self::UnknownBlock(b) => write!(f, "block not found: ^{}", b),
+        }
+    }
+}
+
+/// Resolve a link and check its heading/block against the target note
+///
+/// `anchors` maps vault-relative note paths to their anchors; attachments
+/// (no anchors) only need to exist.
+pub fn validate_link<'a>(
+    link: &Wikilink,
+    source: &str,
+    resolver: &'a LinkResolver,
+    anchors: &HashMap<String, NoteAnchors>,
+) -> Result<&'a str, LinkProblem> {
+    let path = resolver
+        .resolve(link, source)
+        .ok_or(LinkProblem::UnresolvedTarget)?;
+
+    if let Some(note_anchors) = anchors.get(path) {
+        if let Some(heading) = &link.heading {
+            if !note_anchors.has_heading(heading) {
+                return Err(LinkProblem::UnknownHeading(heading.clone()));
+            }
+        }
+        if let Some(block) = &link.block {
+            if !note_anchors.has_block(block) {
+                return Err(LinkProblem::UnknownBlock(block.clone()));
+            }
+        }
+    }
+    Ok(path)
+}
+
+/// Check whether a vault-relative path is a markdown note
+pub fn is_markdown_path(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .map(|e| e.eq_ignore_ascii_case("md"))
+        .unwrap_or(false)
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Default)]
 pub struct WikilinkReport {
@@ -61,3 +432,145 @@ pub fn analyze_wikilinks(
 
     report
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrite_wikilinks_keeps_suffix_and_alias() {
+        let content =
+            "See [[Old Name]] and [[old name#Setup|setup]].\n![[Old Name^abc123]] [[Other]]\n";
+        let (output, edits) = rewrite_wikilinks(
+            content,
+            |l| l.target.eq_ignore_ascii_case("old name"),
+            |_| "New Name".to_string(),
+        );
+
+        assert_eq!(
+            output,
+            "See [[New Name]] and [[New Name#Setup|setup]].\n![[New Name^abc123]] [[Other]]\n"
+        );
+        assert_eq!(edits.len(), 3);
+        assert_eq!(edits[2].line, 2);
+        assert_eq!(edits[2].before, "![[Old Name^abc123]]");
+    }
+
+    #[test]
+    fn test_rewrite_wikilinks_skips_code() {
+        let content = "[[Old]]\n```\n[[Old]]\n```\n`[[Old]]` | [[Old\\|alias]] |\n";
+        let (output, edits) = rewrite_wikilinks(content, |l| l.target == "Old", |_| "New".into());
+
+        assert_eq!(
+            output,
+            "[[New]]\n```\n[[Old]]\n```\n`[[Old]]` | [[New\\|alias]] |\n"
+        );
+        assert_eq!(edits.len(), 2);
+        assert_eq!(edits[1].line, 5);
+    }
+
+    #[test]
+    fn test_parse_wikilink_parts() {
+        let links = parse_wikilinks(
+            "[[Note#Setup|setup]] [[Note#^abc]] [[Note^def]] ![[diagram.png]] [[folder/Other#A#B]] [[#Local]]",
+        );
+
+        assert_eq!(links[0].target, "Note");
+        assert_eq!(links[0].heading.as_deref(), Some("Setup"));
+        assert_eq!(links[0].alias.as_deref(), Some("setup"));
+        assert_eq!(links[1].block.as_deref(), Some("abc"));
+        assert_eq!(links[2].block.as_deref(), Some("def"));
+        assert!(links[3].is_embed);
+        assert_eq!(links[3].target, "diagram.png");
+        assert_eq!(links[4].target, "folder/Other");
+        assert_eq!(links[4].heading.as_deref(), Some("A#B"));
+        assert_eq!(links[5].target, "");
+        assert_eq!(links[0].to_string(), "[[Note#Setup|setup]]");
+        assert_eq!(links[2].to_string(), "[[Note#^def]]");
+    }
+
+    fn link(target: &str) -> Wikilink {
+        parse_wikilinks(&format!("[[{}]]", target)).remove(0)
+    }
+
+    #[test]
+    fn test_resolver_obsidian_rules() {
+        let resolver = LinkResolver::new([
+            "Note.md",
+            "Projects/Note.md",
+            "Projects/Deep/Plan.md",
+            "Archive/Plan.md",
+            "assets/diagram.png",
+            "v1.2 release.md",
+        ]);
+
+        // Bare names: shortest path, case-insensitive
+        assert_eq!(resolver.resolve(&link("note"), "Other.md"), Some("Note.md"));
+        // Same folder as the source note
+        assert_eq!(
+            resolver.resolve(&link("Note"), "Projects/Index.md"),
+            Some("Projects/Note.md")
+        );
+        // Shortest path among duplicates
+        assert_eq!(
+            resolver.resolve(&link("Plan"), "Note.md"),
+            Some("Archive/Plan.md")
+        );
+        // Path links and partial paths
+        assert_eq!(
+            resolver.resolve(&link("Deep/Plan"), "Note.md"),
+            Some("Projects/Deep/Plan.md")
+        );
+        assert_eq!(
+            resolver.resolve(&link("projects/note.md"), "Note.md"),
+            Some("Projects/Note.md")
+        );
+        // Attachments and dotted note names
+        assert_eq!(
+            resolver.resolve(&link("diagram.png"), "Note.md"),
+            Some("assets/diagram.png")
+        );
+        assert_eq!(
+            resolver.resolve(&link("v1.2 release"), "Note.md"),
+            Some("v1.2 release.md")
+        );
+        // Relative Markdown-style paths
+        assert_eq!(
+            resolver.resolve(&link("../Note.md"), "Projects/Deep/Plan.md"),
+            Some("Projects/Note.md")
+        );
+        assert_eq!(resolver.resolve(&link("../Note"), "Note.md"), None);
+        assert_eq!(resolver.resolve(&link("Missing"), "Note.md"), None);
+        // Same-note link
+        assert_eq!(resolver.resolve(&link("#Top"), "Note.md"), Some("Note.md"));
+    }
+
+    #[test]
+    fn test_validate_link_anchors() {
+        let resolver = LinkResolver::new(["Target.md", "image.png"]);
+        let anchors = HashMap::from([(
+            "Target.md".to_string(),
+            NoteAnchors::from_content(
+                "# Intro\n\n## Setup: GPU\n\nA paragraph ^para-1\n\n```\n# Not a heading\n```\n",
+            ),
+        )]);
+        let check = |text: &str| {
+            let link = parse_wikilinks(text).remove(0);
+            validate_link(&link, "Source.md", &resolver, &anchors).map(|_| ())
+        };
+
+        assert_eq!(check("[[Target#intro]]"), Ok(()));
+        assert_eq!(check("[[Target#Intro#Setup GPU]]"), Ok(()));
+        assert_eq!(check("[[Target#^para-1]]"), Ok(()));
+        assert_eq!(check("![[image.png]]"), Ok(()));
+        assert_eq!(
+            check("[[Target#Not a heading]]"),
+            Err(LinkProblem::UnknownHeading("Not a heading".to_string()))
+        );
+        assert_eq!(
+            check("[[Target^nope]]"),
+            Err(LinkProblem::UnknownBlock("nope".to_string()))
+        );
+        assert_eq!(check("[[Nowhere]]"), Err(LinkProblem::UnresolvedTarget));
+    }
+}
@@ -11,10 +11,12 @@
 pub mod core;
 pub mod search;
 pub mod tags;
+#[cfg(test)]
+mod test_support;
 
 // Re-exports for convenience
 pub use core::frontmatter::Frontmatter;
 pub use core::note::{collect_all_notes, collect_note_names, Note};
 pub use core::paths::{get_vault_root, VaultPaths, VAULT_PATH_ENV};
 pub use core::schema::{SchemaViolation, VALID_AREAS, VALID_STATUS, VALID_TYPES};
-pub use core::wikilink::extract_wikilinks;
+pub use core::wikilink::{extract_wikilinks, parse_wikilinks, LinkResolver, Wikilink};
@@ -7,6 +7,8 @@ mod core;
 mod mcp;
 mod search;
 mod tags;
+#[cfg(test)]
+mod test_support;
 
 fn main() -> anyhow::Result<()> {
     let vault_path = core::paths::get_vault_root();
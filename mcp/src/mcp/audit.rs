@@ -1,9 +1,14 @@
 //! Audit check implementations for vault policy compliance
 
-use std::collections::HashSet;
+use std::collections::HashMap;
+use std::path::Path;
 
+use crate::core::config::FolderRule;
+use crate::core::graph::LinkGraph;
 use crate::core::note::Note;
+use crate::core::paths::relative_path;
 use crate::core::schema::SchemaValidator;
+use crate::core::wikilink::{validate_link, LinkProblem, LinkResolver, NoteAnchors};
 
 use super::types::{AuditCheckJson, AuditErrorJson};
 
@@ -23,6 +28,7 @@ pub fn check_schema(
             let entry = AuditErrorJson {
                 note: note.name.clone(),
                 message: violation.format_with_config(schema_config),
+                suggested_path: None,
             };
 
             if violation.is_warning() {
@@ -79,17 +85,43 @@ pub fn check_schema(
 /// Wikilinks validation check
 pub fn check_wikilinks(
     notes: &[Note],
-    note_names: &HashSet<String>,
+    vault_root: &Path,
+    resolver: &LinkResolver,
     verbose: bool,
 ) -> AuditCheckJson {
+    let anchors: HashMap<String, NoteAnchors> = notes
+        .iter()
+        .map(|n| {
+            (
+                relative_path(vault_root, &n.path),
+                NoteAnchors::from_content(&n.content),
+            )
+        })
+        .collect();
+
     let mut errors = Vec::new();
     for note in notes {
-        for link in note.wikilinks() {
-            if !note_names.contains(&link) {
-                errors.push(AuditErrorJson {
+        let source = relative_path(vault_root, &note.path);
+        let md = note.markdown();
+        let links = md
+            .wikilinks
+            .iter()
+            .map(|l| (l.clone(), l.to_string()))
+            .chain(md.links.iter().map(|l| (l.to_wikilink(), l.to_string())));
+
+        for (link, display) in links {
+            match validate_link(&link, &source, resolver, &anchors) {
+                Ok(_) => {}
+                Err(LinkProblem::UnresolvedTarget) => errors.push(AuditErrorJson {
+                    note: note.name.clone(),
+                    message: format!("Broken link: {}", display),
+                    suggested_path: None,
+                }),
+                Err(problem) => errors.push(AuditErrorJson {
                     note: note.name.clone(),
-                    message: format!("Broken link: [[{}]]", link),
-                });
+                    message: format!("Broken link: {} ({})", display, problem),
+                    suggested_path: None,
+                }),
             }
         }
     }
@@ -118,6 +150,7 @@ pub fn check_gist(notes: &[Note], verbose: bool) -> AuditCheckJson {
             errors.push(AuditErrorJson {
                 note: note.name.clone(),
                 message: "Missing gist".to_string(),
+                suggested_path: None,
             });
         }
     }
@@ -154,6 +187,7 @@ pub fn check_tags(notes: &[Note], verbose: bool) -> AuditCheckJson {
             errors.push(AuditErrorJson {
                 note: note.name.clone(),
                 message: "No tags".to_string(),
+                suggested_path: None,
             });
         }
     }
@@ -185,24 +219,22 @@ pub fn check_tags(notes: &[Note], verbose: bool) -> AuditCheckJson {
 /// Orphan notes check
 pub fn check_orphans(
     notes: &[Note],
-    note_names: &HashSet<String>,
+    vault_root: &Path,
+    graph: &LinkGraph,
     verbose: bool,
 ) -> AuditCheckJson {
-    let mut linked: HashSet<String> = HashSet::new();
-    for note in notes {
-        for link in note.wikilinks() {
-            if note_names.contains(&link) {
-                linked.insert(link);
-            }
-        }
-    }
-
+    // Self-links are not graph edges, so they do not count
     let mut errors = Vec::new();
-    for name in note_names {
-        if !linked.contains(name) {
+    for note in notes {
+        if graph
+            .degree(&relative_path(vault_root, &note.path))
+            .incoming
+            == 0
+        {
             errors.push(AuditErrorJson {
-                note: name.clone(),
+                note: note.name.clone(),
                 message: "Orphan note (no incoming links)".to_string(),
+                suggested_path: None,
             });
         }
     }
@@ -231,18 +263,92 @@ pub fn check_orphans(
     }
 }
 
+/// Folder-type matching check
+///
+/// Each note is checked against the first rule whose field value it has.
+pub fn check_folders(
+    notes: &[Note],
+    vault_root: &Path,
+    rules: &[FolderRule],
+    verbose: bool,
+) -> AuditCheckJson {
+    let mut errors = Vec::new();
+    for note in notes {
+        let Some(fm) = note.frontmatter.as_ref() else {
+            continue;
+        };
+        let matched = rules.iter().find_map(|rule| {
+            let values = fm.get(&rule.field)?.as_string_list()?;
+            let value = values.into_iter().find(|v| rule.applies_to(v))?;
+            Some((rule, value))
+        });
+        let Some((rule, value)) = matched else {
+            continue;
+        };
+
+        let path = relative_path(vault_root, &note.path);
+        if rule.allows(&path) {
+            continue;
+        }
+        let folder = rule
+            .folders
+            .first()
+            .map(|f| f.trim_matches('/'))
+            .unwrap_or("");
+        let file_name = path.rsplit('/').next().unwrap_or(&path);
+        let suggested = if folder.is_empty() {
+            file_name.to_string()
+        } else {
+            format!("{}/{}", folder, file_name)
+        };
+
+        errors.push(AuditErrorJson {
+            note: note.name.clone(),
+            message: format!(
+                "{} '{}' must be in {} (found in {})",
+                rule.field,
+                value,
+                rule.folders
+                    .iter()
+                    .map(|f| format!("{}/", f.trim_matches('/')))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                path
+            ),
+            suggested_path: Some(suggested),
+        });
+    }
+
+    let misplaced = errors.len();
+    AuditCheckJson {
+        id: "folders".to_string(),
+        name: "Folder-Type Matching".to_string(),
+        status: if misplaced == 0 { "pass" } else { "fail" }.to_string(),
+        errors: misplaced,
+        warnings: None,
+        details: Some(format!(
+            "{} misplaced notes ({} rules)",
+            misplaced,
+            rules.len()
+        )),
+        error_list: if verbose && !errors.is_empty() {
+            Some(errors)
+        } else {
+            None
+        },
+        warning_list: None,
+    }
+}
+
 /// Stale gists check
 pub fn check_stale_gists(notes: &[Note], verbose: bool) -> AuditCheckJson {
     let mut errors = Vec::new();
-    let gist_date_re = regex::Regex::new(r"(?m)^elysium_gist_date:\s*(\d{4}-\d{2}-\d{2})").unwrap();
-
     for note in notes {
         let gist_date = note
             .frontmatter
             .as_ref()
-            .and_then(|fm| gist_date_re.captures(&fm.raw))
-            .and_then(|caps| caps.get(1))
-            .and_then(|m| chrono::NaiveDate::parse_from_str(m.as_str(), "%Y-%m-%d").ok());
+            .and_then(|fm| fm.get("gist_date"))
+            .and_then(|v| v.as_date());
 
         if let Some(gist_date) = gist_date {
             if let Ok(metadata) = std::fs::metadata(&note.path) {
@@ -253,6 +359,7 @@ pub fn check_stale_gists(notes: &[Note], verbose: bool) -> AuditCheckJson {
                         errors.push(AuditErrorJson {
                             note: note.name.clone(),
                             message: format!("Stale gist: {} < {}", gist_date, modified_date),
+                            suggested_path: None,
                         });
                     }
                 }
@@ -0,0 +1,337 @@
+//! Long-lived vault state shared by all tool calls
+//!
+//! Config, note catalog, link graph, search engine and tag matcher are
+//! loaded once and reused. Each is revalidated cheaply before use:
+//! - config, search index and tag DB: file mtime (one `stat` each)
+//! - notes: file-change events with the `watch` feature, otherwise a
+//!   re-scan at most every `RESCAN_INTERVAL` (only changed files are parsed)
+//!
+//! Tools that write notes call `invalidate_notes` so their own changes are
+//! visible to the next call immediately.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::core::catalog::NoteCatalog;
+use crate::core::config::{Config, LEGACY_CONFIG_FILE, PLUGIN_CONFIG_PATH};
+use crate::core::graph::LinkGraph;
+use crate::core::note::Note;
+use crate::core::paths::VaultPaths;
+use crate::core::wikilink::LinkResolver;
+use crate::search::expansion::{QueryExpander, SYNONYMS_FILE};
+use crate::search::plugin_index;
+use crate::search::HybridSearchEngine;
+use crate::tags::{TagDatabase, TagEmbedder, TagMatcher};
+
+/// Minimum time between vault re-scans when no file watcher is running
+const RESCAN_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Modification time of a file (None if missing)
+type Stamp = Option<SystemTime>;
+
+fn stamp(path: &Path) -> Stamp {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Lock a mutex, recovering the data if a previous holder panicked
+pub fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[derive(Default)]
+struct CacheState {
+    config: Option<([Stamp; 2], Arc<Config>)>,
+    catalog: Option<NoteCatalog>,
+    catalog_checked: Option<Instant>,
+    /// Graph and the catalog generation it was built from
+    graph: Option<(u64, Arc<LinkGraph>)>,
+    /// Engine, the index stamp and the config it was built with
+    engine: Option<(Stamp, Arc<Config>, Arc<Mutex<HybridSearchEngine>>)>,
+    tag_matcher: Option<(Stamp, Arc<Mutex<TagMatcher>>)>,
+    /// Expander and the tag DB and synonym file stamps it was built from
+    expander: Option<([Stamp; 2], Arc<QueryExpander>)>,
+}
+
+pub struct VaultCache {
+    vault_path: PathBuf,
+    state: Mutex<CacheState>,
+    /// Set when notes may have changed since the last catalog refresh
+    notes_dirty: Arc<AtomicBool>,
+    #[cfg(feature = "watch")]
+    watcher: Option<notify::RecommendedWatcher>,
+}
+
+impl VaultCache {
+    pub fn new(vault_path: &Path) -> Self {
+        let notes_dirty = Arc::new(AtomicBool::new(false));
+
+        Self {
+            vault_path: vault_path.to_path_buf(),
+            state: Mutex::default(),
+            #[cfg(feature = "watch")]
+            watcher: watch::start(vault_path, Arc::clone(&notes_dirty)),
+            notes_dirty,
+        }
+    }
+
+    /// Vault config, reloaded when the config file changes
+    pub fn config(&self) -> Arc<Config> {
+        let stamps = [
+            stamp(&self.vault_path.join(PLUGIN_CONFIG_PATH)),
+            stamp(&self.vault_path.join(LEGACY_CONFIG_FILE)),
+        ];
+        let mut state = lock(&self.state);
+        match &state.config {
+            Some((cached, config)) if *cached == stamps => Arc::clone(config),
+            _ => {
+                let config = Arc::new(Config::load(&self.vault_path));
+                state.config = Some((stamps, Arc::clone(&config)));
+                config
+            }
+        }
+    }
+
+    pub fn vault_paths(&self) -> VaultPaths {
+        VaultPaths::from_root_with_config(self.vault_path.clone(), (*self.config()).clone())
+    }
+
+    /// All notes, sorted by name
+    pub fn notes(&self) -> Arc<Vec<Note>> {
+        let mut state = lock(&self.state);
+        self.catalog(&mut state).notes()
+    }
+
+    /// Vault-relative paths of all files (notes and attachments)
+    pub fn files(&self) -> Arc<Vec<String>> {
+        let mut state = lock(&self.state);
+        self.catalog(&mut state).files()
+    }
+
+    /// Link graph, rebuilt when the catalog changes
+    pub fn link_graph(&self) -> Arc<LinkGraph> {
+        let mut state = lock(&self.state);
+        let catalog = self.catalog(&mut state);
+        let generation = catalog.generation();
+        if let Some((built, graph)) = &state.graph {
+            if *built == generation {
+                return Arc::clone(graph);
+            }
+        }
+
+        let catalog = self.catalog(&mut state);
+        let resolver = LinkResolver::new(catalog.files().iter().cloned());
+        let graph = Arc::new(LinkGraph::build(
+            &catalog.notes(),
+            &self.vault_path,
+            &resolver,
+        ));
+        state.graph = Some((generation, Arc::clone(&graph)));
+        graph
+    }
+
+    /// Hybrid search engine, reloaded when the plugin index is re-exported
+    /// or the config (`search` ranking settings) changes
+    pub fn search_engine(&self) -> Result<Arc<Mutex<HybridSearchEngine>>> {
+        let index_stamp = plugin_index::index_stamp(&self.vault_path);
+        let config = self.config();
+        let mut state = lock(&self.state);
+        if let Some((cached, built_with, engine)) = &state.engine {
+            if *cached == index_stamp && Arc::ptr_eq(built_with, &config) {
+                return Ok(Arc::clone(engine));
+            }
+        }
+
+        let engine = HybridSearchEngine::with_ranking(&self.vault_path, &config.search)?;
+        let engine = Arc::new(Mutex::new(engine));
+        state.engine = Some((index_stamp, config, Arc::clone(&engine)));
+        Ok(engine)
+    }
+
+    /// Tag matcher, reloaded when the tag DB changes (None if not initialized)
+    pub fn tag_matcher(&self) -> Option<Arc<Mutex<TagMatcher>>> {
+        let tag_db = self.config().resolve_paths(&self.vault_path).tag_db;
+        let db_stamp = stamp(&tag_db);
+        let mut state = lock(&self.state);
+        if db_stamp.is_none() {
+            state.tag_matcher = None;
+            return None;
+        }
+        if let Some((cached, matcher)) = &state.tag_matcher {
+            if *cached == db_stamp {
+                return Some(Arc::clone(matcher));
+            }
+        }
+
+        let matcher = Self::load_tag_matcher(&tag_db).ok()?;
+        let matcher = Arc::new(Mutex::new(matcher));
+        state.tag_matcher = Some((db_stamp, Arc::clone(&matcher)));
+        Some(matcher)
+    }
+
+    /// Query expander from tag aliases and the synonym file, reloaded when
+    /// either changes
+    pub fn query_expander(&self) -> Arc<QueryExpander> {
+        let paths = self.config().resolve_paths(&self.vault_path);
+        let synonyms = paths.data_dir.join(SYNONYMS_FILE);
+        let stamps = [stamp(&paths.tag_db), stamp(&synonyms)];
+        let mut state = lock(&self.state);
+        if let Some((cached, expander)) = &state.expander {
+            if *cached == stamps {
+                return Arc::clone(expander);
+            }
+        }
+
+        let mut expander = QueryExpander::new();
+        if stamps[0].is_some() {
+            match TagDatabase::open(&paths.tag_db).and_then(|db| db.get_all_tags()) {
+                Ok(tags) => {
+                    for tag in tags {
+                        for alias in &tag.aliases {
+                            expander.add_tag_alias(alias, &tag.name);
+                        }
+                    }
+                }
+                Err(e) => eprintln!(
+                    "Warning: Tag aliases unavailable for query expansion: {}",
+                    e
+                ),
+            }
+        }
+        if let Err(e) = expander.load_synonyms(&synonyms) {
+            eprintln!("Warning: {:#}", e);
+        }
+
+        let expander = Arc::new(expander);
+        state.expander = Some((stamps, Arc::clone(&expander)));
+        expander
+    }
+
+    /// Mark notes as changed (call after writing to the vault)
+    pub fn invalidate_notes(&self) {
+        self.notes_dirty.store(true, Ordering::SeqCst);
+    }
+
+    fn load_tag_matcher(tag_db: &Path) -> Result<TagMatcher> {
+        let embedder =
+            TagEmbedder::default_multilingual().context("Failed to load tag embedder")?;
+        let database = TagDatabase::open(tag_db).context("Failed to open tag DB")?;
+        Ok(TagMatcher::new(embedder, database))
+    }
+
+    /// Catalog, refreshed if notes may have changed
+    fn catalog<'a>(&self, state: &'a mut CacheState) -> &'a mut NoteCatalog {
+        let dirty = self.notes_dirty.swap(false, Ordering::SeqCst);
+        let rescan_due = !self.is_watching()
+            && state
+                .catalog_checked
+                .is_none_or(|checked| checked.elapsed() >= RESCAN_INTERVAL);
+
+        if state.catalog.is_some() && (dirty || rescan_due) {
+            if let Some(catalog) = state.catalog.as_mut() {
+                catalog.refresh();
+            }
+            state.catalog_checked = Some(Instant::now());
+        }
+
+        state.catalog.get_or_insert_with(|| {
+            let catalog = NoteCatalog::load(&VaultPaths::from_root(self.vault_path.clone()));
+            state.catalog_checked = Some(Instant::now());
+            catalog
+        })
+    }
+
+    #[cfg(feature = "watch")]
+    fn is_watching(&self) -> bool {
+        self.watcher.is_some()
+    }
+
+    #[cfg(not(feature = "watch"))]
+    fn is_watching(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "watch")]
+mod watch {
+    use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+    use std::path::Path;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    use crate::core::note::should_exclude_path;
+
+    /// Watch the vault and flag note changes (None if watching fails)
+    pub fn start(root: &Path, dirty: Arc<AtomicBool>) -> Option<RecommendedWatcher> {
+        let vault_root = root.to_path_buf();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            let Ok(event) = res else {
+                // Missed events: rescan to be safe
+                dirty.store(true, Ordering::SeqCst);
+                return;
+            };
+            if !event.kind.is_access()
+                && event
+                    .paths
+                    .iter()
+                    .any(|p| !should_exclude_path(&vault_root, p))
+            {
+                dirty.store(true, Ordering::SeqCst);
+            }
+        })
+        .ok()?;
+
+        match watcher.watch(root, RecursiveMode::Recursive) {
+            Ok(()) => Some(watcher),
+            Err(e) => {
+                eprintln!(
+                    "Warning: File watching disabled ({}). Using periodic rescans.",
+                    e
+                );
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_notes_cached_until_invalidated() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("a.md"), "[[b]]\n").unwrap();
+        fs::write(temp.path().join("b.md"), "# B\n").unwrap();
+
+        let cache = VaultCache::new(temp.path());
+        let notes = cache.notes();
+        assert_eq!(notes.len(), 2);
+        assert!(Arc::ptr_eq(&notes, &cache.notes()));
+        let graph = cache.link_graph();
+        assert!(Arc::ptr_eq(&graph, &cache.link_graph()));
+        assert_eq!(graph.backlinks("b.md"), vec![("a.md", 1)]);
+
+        fs::write(temp.path().join("c.md"), "[[b]]\n").unwrap();
+        cache.invalidate_notes();
+        assert_eq!(cache.notes().len(), 3);
+        assert_eq!(cache.link_graph().backlinks("b.md").len(), 2);
+    }
+
+    #[test]
+    fn test_config_reloaded_on_change() {
+        let temp = TempDir::new().unwrap();
+        let cache = VaultCache::new(temp.path());
+        assert_eq!(cache.config().folders.archive, "Archive");
+        assert!(Arc::ptr_eq(&cache.config(), &cache.config()));
+
+        let config_path = temp.path().join(PLUGIN_CONFIG_PATH);
+        fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+        fs::write(&config_path, r#"{"folders": {"archive": "Old"}}"#).unwrap();
+        assert_eq!(cache.config().folders.archive, "Old");
+    }
+}
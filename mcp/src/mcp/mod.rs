@@ -3,6 +3,7 @@
 //! Provides AI-native access to vault search and note operations.
 
 mod audit;
+mod cache;
 mod helpers;
 mod params;
 mod server;
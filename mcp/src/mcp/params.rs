@@ -2,12 +2,16 @@
 
 use schemars::JsonSchema;
 use serde::Deserialize;
+use std::collections::BTreeMap;
 
 /// Parameters for vault_search tool
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct SearchParams {
-    /// Natural language search query (e.g., "GPU memory sharing methods")
-    #[schemars(description = "Natural language search query")]
+    /// Search query: natural language plus optional filters, phrases and sort
+    /// (e.g., "GPU memory sharing type:project modified:>2026-01-01 sort:modified desc")
+    #[schemars(
+        description = "Search text with optional filters: key:value (type, area, status, any field; key:!value excludes), tag:x, folder:x, modified:>YYYY-MM-DD, created:<YYYY-MM-DD, \"exact phrase\", -excluded, sort:modified|created|title|path|relevance [asc|desc]"
+    )]
     pub query: String,
     /// Maximum number of results to return (default: 5)
     #[schemars(description = "Maximum number of results (default: 5)")]
@@ -23,6 +27,24 @@ pub struct SearchParams {
     )]
     #[serde(default)]
     pub area: Option<String>,
+    /// Filter by status (active, done, archived, ...)
+    #[schemars(description = "Filter by status: active, done, archived, ...")]
+    #[serde(default)]
+    pub status: Option<String>,
+    /// Require all of these tags (nested tags match: "gpu" matches "gpu/cuda")
+    #[schemars(description = "Require all of these tags (nested tags match their parent)")]
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Only notes in this folder or its subfolders
+    #[schemars(description = "Folder prefix (e.g., 'Projects/GPU')")]
+    #[serde(default)]
+    pub folder: Option<String>,
+    /// Other frontmatter fields to match (key without elysium_ prefix -> value)
+    #[schemars(
+        description = "Match other frontmatter fields, e.g. {\"source\": \"paper\"} (key without elysium_ prefix)"
+    )]
+    #[serde(default)]
+    pub where_fields: BTreeMap<String, String>,
     /// Fields to include in output: "default" (title,path,gist), "standard" (+ type,status,area,tags), "all", or comma-separated list
     #[schemars(
         description = "Fields to include: 'default', 'standard', 'all', or comma-separated (e.g., 'title,gist,source')"
@@ -33,12 +55,109 @@ pub struct SearchParams {
     #[schemars(description = "Search mode: 'hybrid' (default), 'semantic', 'keyword'")]
     #[serde(default)]
     pub search_mode: Option<String>,
+    /// Hybrid fusion method: "rrf", "min_max", "z_score" or "dbsf" (default: vault config, else rrf)
+    #[schemars(
+        description = "Hybrid fusion: 'rrf' (rank-based), 'min_max' or 'z_score' (normalized scores), 'dbsf' (distribution-based); default from vault config search.fusion, else 'rrf'"
+    )]
+    #[serde(default)]
+    pub fusion: Option<String>,
+    /// MMR diversity from 0 (relevance only) to 1 (most diverse)
+    #[schemars(
+        description = "Diversity re-ranking (MMR) from 0 to 1: higher values skip near-duplicate notes in favor of different ones (default: off)"
+    )]
+    #[serde(default)]
+    pub diversity: Option<f32>,
+    /// Expand the query with tag aliases and synonyms (default: vault config)
+    #[schemars(
+        description = "Also match tag aliases and synonyms of query terms (e.g. 'vram' -> 'gpu') at reduced weight; the applied expansion is reported (default: vault config search.expansion, else false)"
+    )]
+    #[serde(default)]
+    pub expand: Option<bool>,
+    /// Search a malformed keyword query as plain words instead of failing
+    #[schemars(
+        description = "On a keyword syntax error (e.g. unbalanced parentheses), search the words literally and report the error as a warning instead of failing (default: false in keyword mode, true in hybrid mode)"
+    )]
+    #[serde(default)]
+    pub lenient: Option<bool>,
+    /// Include archived notes (status archived or in the archive folder)
+    #[schemars(description = "Include archived notes (default: false)")]
+    #[serde(default)]
+    pub include_archived: bool,
+    /// Include a highlighted excerpt of the note body where the query matched
+    #[schemars(
+        description = "Include a 'snippet' with matched body text highlighted as **term** (default: false)"
+    )]
+    #[serde(default)]
+    pub snippets: bool,
+    /// Include per-result score breakdown (ranks, scores, matched fields, RRF contributions)
+    #[schemars(
+        description = "Include an 'explain' breakdown per result: semantic rank/cosine, BM25 rank/score with matched fields and boosts, RRF contribution of each side (default: false)"
+    )]
+    #[serde(default)]
+    pub explain: bool,
 }
 
 pub fn default_limit() -> usize {
     5
 }
 
+/// Parameters for vault_search_eval tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SearchEvalParams {
+    /// Judged query set (vault-relative or absolute path)
+    #[schemars(
+        description = "Judged query set JSON: {\"k\": 10, \"queries\": [{\"query\": \"...\", \"relevant\": [\"path.md\", {\"path\": \"other.md\", \"grade\": 2}]}]} (vault-relative or absolute path; default: .obsidian/plugins/elysium/search_eval.json)"
+    )]
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Search modes to evaluate (default: hybrid, semantic, keyword)
+    #[schemars(
+        description = "Search modes to evaluate: 'hybrid', 'semantic', 'keyword' (default: all)"
+    )]
+    #[serde(default)]
+    pub modes: Vec<String>,
+    /// Fusion methods to evaluate in hybrid mode (default: vault config)
+    #[schemars(
+        description = "Fusion methods to compare in hybrid mode: 'rrf', 'min_max', 'z_score', 'dbsf' (default: vault config search.fusion)"
+    )]
+    #[serde(default)]
+    pub fusions: Vec<String>,
+    /// Ranking settings to compare in hybrid mode (default: vault config)
+    #[schemars(
+        description = "Ranking settings to compare in hybrid mode, one run each, overriding the vault config: [{\"name\": \"keyword-heavy\", \"bm25Weight\": 0.6, \"semanticWeight\": 0.4, \"rrfK\": 20, \"fusion\": \"rrf\"}]. Without fusion, a config runs with each of fusions (default: vault config)"
+    )]
+    #[serde(default)]
+    pub configs: Vec<EvalConfigParams>,
+    /// Metric cutoff (default: set's k, else 10)
+    #[schemars(description = "Cutoff for nDCG@k and recall@k (default: the set's k, else 10)")]
+    #[serde(default)]
+    pub k: Option<usize>,
+    /// Include metrics and ranked results per query
+    #[schemars(description = "Include metrics and top k results per query (default: false)")]
+    #[serde(default)]
+    pub per_query: bool,
+    /// Include archived notes (status archived or in the archive folder)
+    #[schemars(description = "Include archived notes (default: false)")]
+    #[serde(default)]
+    pub include_archived: bool,
+}
+
+/// Ranking overrides of one vault_search_eval run (vault config `search` keys)
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct EvalConfigParams {
+    /// Label in the report (default: config1, config2, ...)
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub fusion: Option<String>,
+    #[serde(default, rename = "bm25Weight")]
+    pub bm25_weight: Option<f32>,
+    #[serde(default, rename = "semanticWeight")]
+    pub semantic_weight: Option<f32>,
+    #[serde(default, rename = "rrfK")]
+    pub rrf_k: Option<usize>,
+}
+
 /// Parameters for vault_get_note tool
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct GetNoteParams {
@@ -56,6 +175,12 @@ pub struct GetNoteParams {
 /// Parameters for vault_list_notes tool
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct ListNotesParams {
+    /// Query with filters, phrases and sort (same syntax as vault_search)
+    #[schemars(
+        description = "Filter query, e.g. 'type:project tag:gpu status:!archived modified:>2026-01-01 \"exact phrase\" -excluded sort:modified desc'; free words must appear in the note"
+    )]
+    #[serde(default)]
+    pub query: Option<String>,
     /// Filter by note type (note, term, project, log)
     #[schemars(description = "Filter by type: note, term, project, log")]
     #[serde(default)]
@@ -74,6 +199,10 @@ pub struct ListNotesParams {
     )]
     #[serde(default)]
     pub fields: Option<String>,
+    /// Include archived notes (status archived or in the archive folder)
+    #[schemars(description = "Include archived notes (default: false)")]
+    #[serde(default)]
+    pub include_archived: bool,
 }
 
 pub fn default_list_limit() -> usize {
@@ -89,12 +218,26 @@ pub struct RelatedParams {
     pub limit: usize,
     #[schemars(description = "Boost notes with same type as source")]
     #[serde(default)]
-    #[allow(dead_code)]
     pub boost_type: bool,
     #[schemars(description = "Boost notes with same area as source")]
     #[serde(default)]
-    #[allow(dead_code)]
     pub boost_area: bool,
+    /// Boost notes linked to the source or sharing its links and tags
+    #[schemars(
+        description = "Boost notes linked to/from the source or sharing links and tags with it (linked notes are always candidates)"
+    )]
+    #[serde(default)]
+    pub boost_links: bool,
+    /// Include archived notes (status archived or in the archive folder)
+    #[schemars(description = "Include archived notes (default: false)")]
+    #[serde(default)]
+    pub include_archived: bool,
+    /// MMR diversity from 0 (similarity only) to 1 (most diverse)
+    #[schemars(
+        description = "Diversity re-ranking (MMR) from 0 to 1: higher values skip near-duplicate notes in favor of different ones (default: off)"
+    )]
+    #[serde(default)]
+    pub diversity: Option<f32>,
 }
 
 pub fn default_related_limit() -> usize {
@@ -115,6 +258,17 @@ pub struct AuditParams {
     pub verbose: bool,
 }
 
+/// Parameters for vault_reindex tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReindexParams {
+    /// Embedding mode: "htp" or "model2vec" (default: from plugin config)
+    #[schemars(
+        description = "Embedding mode: 'htp' (built-in) or 'model2vec' (default: from plugin config)"
+    )]
+    #[serde(default)]
+    pub embedding_mode: Option<String>,
+}
+
 /// Parameters for unified vault_save tool
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct SaveParams {
@@ -128,7 +282,7 @@ pub struct SaveParams {
 
     /// Save strategy: create, update, append, inbox, smart
     #[schemars(
-        description = "Save strategy: 'create' (new note), 'update' (overwrite existing), 'append' (add to existing), 'inbox' (quick capture), 'smart' (auto-detect duplicates)"
+        description = "Save strategy: 'create' (new note), 'update' (merge into existing), 'append' (add to existing), 'inbox' (quick capture), 'smart' (auto-detect duplicates)"
     )]
     #[serde(default = "default_strategy")]
     pub strategy: String,
@@ -138,6 +292,13 @@ pub struct SaveParams {
     #[serde(default)]
     pub note_type: Option<String>,
 
+    /// Note status (create defaults to active; update keeps the existing status)
+    #[schemars(
+        description = "Note status: active, done, archived (default: active for create, unchanged for update)"
+    )]
+    #[serde(default)]
+    pub status: Option<String>,
+
     /// Note area: work, tech, life, career, learning, reference
     #[schemars(description = "Note area: work, tech, life, career, learning, reference")]
     #[serde(default)]
@@ -179,6 +340,24 @@ pub struct SaveParams {
     #[schemars(description = "Enable tag discovery from content keywords (default: false)")]
     #[serde(default)]
     pub discover: bool,
+
+    /// Update: replace the note body with content (default: true)
+    #[schemars(
+        description = "Update only: replace the note body with content (default: true). Set false to change frontmatter only"
+    )]
+    #[serde(default = "default_replace_body")]
+    pub replace_body: bool,
+
+    /// Update: rebuild frontmatter from parameters instead of merging
+    #[schemars(
+        description = "Update only: rebuild frontmatter from parameters instead of merging into existing fields (default: false)"
+    )]
+    #[serde(default)]
+    pub replace_frontmatter: bool,
+}
+
+pub fn default_replace_body() -> bool {
+    true
 }
 
 pub fn default_auto_tag() -> bool {
@@ -193,6 +372,150 @@ pub fn default_strategy() -> String {
     "create".to_string()
 }
 
+/// Parameters for vault_update_fields tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct UpdateFieldsParams {
+    /// Note title or vault-relative path
+    #[schemars(
+        description = "Note title or vault-relative path (e.g., 'GPU Memory' or 'Notes/GPU Memory.md')"
+    )]
+    pub note: String,
+
+    /// Fields to set (key without elysium_ prefix -> value)
+    #[schemars(
+        description = "Fields to set, e.g. {\"status\": \"done\", \"tags\": [\"gpu\", \"cuda\"]}. Keys may omit the 'elysium_' prefix"
+    )]
+    #[serde(default)]
+    pub set: Option<BTreeMap<String, serde_json::Value>>,
+
+    /// Fields to remove
+    #[schemars(description = "Fields to remove (e.g., ['source'])")]
+    #[serde(default)]
+    pub remove: Option<Vec<String>>,
+
+    /// Items to append to list fields (duplicates are skipped)
+    #[schemars(
+        description = "Items to append to list fields, e.g. {\"tags\": [\"nvidia\"]}. Existing items are kept, duplicates skipped"
+    )]
+    #[serde(default)]
+    pub append: Option<BTreeMap<String, Vec<String>>>,
+
+    /// Preview changes without writing the file
+    #[schemars(description = "Preview changes without writing (default: false)")]
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Parameters for vault_rename tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RenameParams {
+    /// Note title or vault-relative path
+    #[schemars(description = "Note title or vault-relative path of the note to rename/move")]
+    pub note: String,
+
+    /// New note title (file name without .md)
+    #[schemars(description = "New title (file name without .md). Omit to keep the current name")]
+    #[serde(default)]
+    pub new_name: Option<String>,
+
+    /// Destination folder (vault-relative)
+    #[schemars(
+        description = "Destination folder, vault-relative (e.g., 'Projects'; '' for vault root). Omit to keep the current folder"
+    )]
+    #[serde(default)]
+    pub new_folder: Option<String>,
+
+    /// List the links that would change without touching any file
+    #[schemars(
+        description = "List files and lines that would change without writing (default: false)"
+    )]
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Parameters for vault_archive tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ArchiveParams {
+    /// Note title or vault-relative path
+    #[schemars(description = "Note title or vault-relative path of the note to archive")]
+    pub note: String,
+
+    #[schemars(description = "Report the move and link changes without writing (default: false)")]
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Parameters for vault_unarchive tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct UnarchiveParams {
+    /// Note title or vault-relative path
+    #[schemars(description = "Note title or vault-relative path of the archived note")]
+    pub note: String,
+
+    /// Destination folder (default: the folder it was archived from)
+    #[schemars(
+        description = "Destination folder, vault-relative (default: the folder it was archived from, else the notes folder)"
+    )]
+    #[serde(default)]
+    pub folder: Option<String>,
+
+    /// Status to restore (default: active)
+    #[schemars(description = "Status to set (default: active)")]
+    #[serde(default)]
+    pub status: Option<String>,
+
+    #[schemars(description = "Report the move and link changes without writing (default: false)")]
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Parameters for vault_backlinks and vault_outlinks tools
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct LinksParams {
+    /// Note title or vault-relative path
+    #[schemars(description = "Note title or vault-relative path")]
+    pub note: String,
+}
+
+/// Parameters for vault_neighbors tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct NeighborsParams {
+    /// Note title or vault-relative path
+    #[schemars(description = "Note title or vault-relative path")]
+    pub note: String,
+
+    /// Number of link hops to follow
+    #[schemars(description = "Number of link hops to follow, 1-5 (default: 1)")]
+    #[serde(default = "default_neighbor_depth")]
+    pub depth: usize,
+
+    /// Link direction: "out", "in" or "both"
+    #[schemars(
+        description = "Follow outgoing links ('out'), backlinks ('in') or both ('both', default)"
+    )]
+    #[serde(default)]
+    pub direction: Option<String>,
+
+    #[schemars(description = "Maximum number of results (default: 50)")]
+    #[serde(default = "default_list_limit")]
+    pub limit: usize,
+}
+
+pub fn default_neighbor_depth() -> usize {
+    1
+}
+
+/// Parameters for vault_graph_stats tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GraphStatsParams {
+    /// Maximum notes listed per category (hubs, dead ends, isolated)
+    #[schemars(
+        description = "Maximum notes listed for hubs, dead ends and isolated notes (default: 10)"
+    )]
+    #[serde(default = "default_related_limit")]
+    pub limit: usize,
+}
+
 /// Parameters for vault_tags_suggest tool
 #[allow(dead_code)]
 #[derive(Debug, Deserialize, JsonSchema)]
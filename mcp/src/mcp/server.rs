@@ -9,21 +9,40 @@ use rmcp::{
 use serde::Serialize;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-
-use crate::core::note::{collect_all_notes, collect_note_names};
-use crate::core::paths::VaultPaths;
+use std::sync::{Arc, Mutex};
+
+use crate::core::archive::{plan_archive, plan_unarchive};
+use crate::core::frontmatter::{elysium_key, FieldValue, Frontmatter, FrontmatterEditor};
+use crate::core::graph::{Direction, LinkGraph};
+use crate::core::note::Note;
+use crate::core::paths::{relative_path, VaultPaths};
+use crate::core::rename::{plan_rename, RenamePlan};
+use crate::core::resolve::{resolve_note, resolve_note_strict, ResolveError};
 use crate::core::schema::SchemaValidator;
-use crate::search::engine::SearchEngine;
-use crate::search::hybrid::{HybridSearchEngine, SearchMode};
-use crate::search::PluginSearchEngine;
+use crate::core::wikilink::LinkResolver;
+use crate::search::engine::{SearchEngine, SearchResult};
+use crate::search::eval::{self, EvalRun, EvalSet};
+use crate::search::expansion;
+use crate::search::filter::{Condition, SearchFilter};
+use crate::search::hybrid::{
+    FusionMethod, HybridConfig, HybridSearchEngine, ScoreExplanation, SearchMode, SearchOptions,
+};
+use crate::search::keyword::{KeywordQuery, NoteFields};
+use crate::search::mmr;
+use crate::search::query::{NoteQuery, QueryError};
+use crate::search::related::{boosted_score, NoteSignals, RelatedBoost};
+use crate::search::VaultIndexer;
 use crate::tags::keyword::KeywordExtractor;
-use crate::tags::{TagDatabase, TagEmbedder, TagMatcher};
+use crate::tags::{TagDatabase, TagMatcher};
 
 use super::audit;
+use super::cache::{lock, VaultCache};
 use super::helpers::{build_note_json, resolve_fields};
 use super::params::{
-    AuditParams, GetNoteParams, ListNotesParams, RelatedParams, SaveParams, SearchParams,
-    SuggestTagsParams, TagsAnalyzeParams, TagsSuggestParams,
+    ArchiveParams, AuditParams, EvalConfigParams, GetNoteParams, GraphStatsParams, LinksParams,
+    ListNotesParams, NeighborsParams, ReindexParams, RelatedParams, RenameParams, SaveParams,
+    SearchEvalParams, SearchParams, SuggestTagsParams, TagsAnalyzeParams, TagsSuggestParams,
+    UnarchiveParams, UpdateFieldsParams,
 };
 use super::types::{AuditResultJson, SearchResultJson};
 
@@ -32,13 +51,15 @@ use super::types::{AuditResultJson, SearchResultJson};
 pub struct VaultService {
     vault_path: PathBuf,
     db_path: PathBuf,
+    /// Shared state reused across tool calls
+    cache: Arc<VaultCache>,
     tool_router: ToolRouter<Self>,
 }
 
 impl VaultService {
     pub fn new(vault_path: PathBuf) -> Self {
-        let config = crate::core::config::Config::load(&vault_path);
-        let paths = config.resolve_paths(&vault_path);
+        let cache = Arc::new(VaultCache::new(&vault_path));
+        let paths = cache.config().resolve_paths(&vault_path);
         let db_path = paths.search_db.clone();
 
         // Check for legacy DB locations and warn if migration needed
@@ -47,6 +68,7 @@ impl VaultService {
         Self {
             vault_path,
             db_path,
+            cache,
             tool_router: Self::tool_router(),
         }
     }
@@ -97,16 +119,9 @@ impl VaultService {
         }
     }
 
-    /// Get plugin search engine (reads index exported by Obsidian plugin)
-    fn get_plugin_engine(&self) -> Result<PluginSearchEngine, McpError> {
-        PluginSearchEngine::load(&self.vault_path).map_err(|e| {
-            McpError::internal_error(format!("Failed to load plugin index: {}", e), None)
-        })
-    }
-
-    /// Get hybrid search engine (BM25 + Semantic)
-    fn get_hybrid_engine(&self) -> Result<HybridSearchEngine, McpError> {
-        HybridSearchEngine::new(&self.vault_path).map_err(|e| {
+    /// Get hybrid search engine (BM25 + Semantic), shared across calls
+    fn get_hybrid_engine(&self) -> Result<Arc<Mutex<HybridSearchEngine>>, McpError> {
+        self.cache.search_engine().map_err(|e| {
             McpError::internal_error(format!("Failed to load hybrid search engine: {}", e), None)
         })
     }
@@ -117,7 +132,7 @@ impl VaultService {
         use crate::search::SearchConfig;
 
         // Load config to check for advanced search settings
-        let config = crate::core::config::Config::load(&self.vault_path);
+        let config = self.cache.config();
         let search_config = SearchConfig {
             use_advanced: config.features.is_advanced_search_ready(),
             model_path: config.features.get_model_path().map(|p| {
@@ -136,33 +151,33 @@ impl VaultService {
     }
 
     fn get_vault_paths(&self) -> VaultPaths {
-        VaultPaths::from_root(self.vault_path.clone())
+        self.cache.vault_paths()
+    }
+
+    /// All notes in the vault (cached catalog)
+    fn get_notes(&self) -> Arc<Vec<Note>> {
+        self.cache.notes()
+    }
+
+    /// Link graph of the vault (cached until notes change)
+    fn get_link_graph(&self) -> Arc<LinkGraph> {
+        self.cache.link_graph()
     }
 
     fn get_schema_validator(&self) -> SchemaValidator {
-        let vault_paths = self.get_vault_paths();
-        SchemaValidator::from_config(&vault_paths.config.schema)
+        SchemaValidator::from_config(&self.cache.config().schema)
     }
 
     /// Get tag matcher for auto-tagging
     /// Returns None if tag DB is not initialized
-    fn get_tag_matcher(&self) -> Option<TagMatcher> {
-        let paths = self.get_resolved_paths();
-
-        if !paths.tag_db.exists() {
-            return None;
-        }
-
-        let embedder = TagEmbedder::default_multilingual().ok()?;
-        let database = TagDatabase::open(&paths.tag_db).ok()?;
-
-        Some(TagMatcher::new(embedder, database))
+    fn get_tag_matcher(&self) -> Option<Arc<Mutex<TagMatcher>>> {
+        self.cache.tag_matcher()
     }
 
     /// Get resolved paths helper
+    #[allow(dead_code)]
     fn get_resolved_paths(&self) -> crate::core::config::ResolvedPaths {
-        let config = crate::core::config::Config::load(&self.vault_path);
-        config.resolve_paths(&self.vault_path)
+        self.cache.config().resolve_paths(&self.vault_path)
     }
 
     /// Suggest tags for given text using semantic matching
@@ -179,6 +194,7 @@ impl VaultService {
             None
         };
 
+        let matcher = lock(&matcher);
         matcher
             .suggest_tags_with_discovery(text, limit, keyword_extractor.as_ref())
             .ok()
@@ -191,27 +207,116 @@ impl VaultService {
 impl VaultService {
     /// Search notes using hybrid search (BM25 + semantic)
     #[tool(
-        description = "Search Second Brain Vault using hybrid search (BM25 + semantic). Supports search modes: 'hybrid' (default), 'semantic' (HNSW only), 'keyword' (BM25 only). Returns notes with optional type/area filtering."
+        description = "Search Second Brain Vault using hybrid search (BM25 + semantic). Supports search modes: 'hybrid' (default), 'semantic' (HNSW only), 'keyword' (BM25 only). The query accepts filters, phrases and sort keys, e.g. 'gpu sharing type:project tag:gpu status:!archived modified:>2026-01-01 \"exact phrase\" -excluded sort:modified desc'. Keyword search (keyword and hybrid modes) also understands AND, OR, NOT, (groups), title:/gist:/body: field scopes and prefix* wildcards; tags:x is the tag filter, while tags:gp*, tags:\"a b\" and tags:(...) search tag text. Syntax errors report their position."
     )]
     async fn vault_search(
         &self,
         params: Parameters<SearchParams>,
     ) -> Result<CallToolResult, McpError> {
-        let mut engine = self.get_hybrid_engine()?;
-        let note_type_filter = &params.0.note_type;
-        let area_filter = &params.0.area;
-
-        // Parse search mode (default: Hybrid)
-        let search_mode = params
-            .0
-            .search_mode
-            .as_deref()
-            .map(SearchMode::from_str)
-            .unwrap_or_default();
+        let query = match NoteQuery::parse(&params.0.query) {
+            Ok(query) => query,
+            Err(e) => return Ok(query_error_result(&e)),
+        };
+        if !query.has_text() {
+            return Ok(CallToolResult::success(vec![Content::text(
+                serde_json::json!({
+                    "error": "Query has no search text besides filters (use vault_list_notes to list notes by filter)"
+                })
+                .to_string(),
+            )]));
+        }
+
+        let engine = self.get_hybrid_engine()?;
+        let mut engine = lock(&engine);
+
+        // Parse search mode (default: Hybrid) and fusion (default: vault config)
+        let mut options = SearchOptions::new(
+            params
+                .0
+                .search_mode
+                .as_deref()
+                .map(SearchMode::from_str)
+                .unwrap_or_default(),
+        );
+        options.diversity = params.0.diversity;
+        if let Some(name) = &params.0.fusion {
+            match FusionMethod::parse(name) {
+                Some(fusion) => options.fusion = Some(fusion),
+                None => {
+                    return Ok(CallToolResult::success(vec![Content::text(
+                        serde_json::json!({
+                            "error": format!(
+                                "Unknown fusion method '{}' (expected {})",
+                                name,
+                                FusionMethod::NAMES
+                            )
+                        })
+                        .to_string(),
+                    )]))
+                }
+            }
+        }
+
+        // Keyword syntax errors point into the query: its search text keeps
+        // the original positions
+        let mut syntax_error = None;
+        if options.mode != SearchMode::Semantic {
+            options.lenient = params
+                .0
+                .lenient
+                .unwrap_or(options.mode == SearchMode::Hybrid);
+        }
+        let keyword = match KeywordQuery::parse(&query.search_text) {
+            Ok(keyword) => keyword,
+            Err(e) => {
+                if options.mode != SearchMode::Semantic {
+                    if !options.lenient {
+                        return Ok(query_error_result(&e));
+                    }
+                    syntax_error = Some(e);
+                }
+                KeywordQuery::escaped(&query.search_text)
+            }
+        };
+
+        // Filters are applied inside the engines (no over-fetching)
+        let mut filter = query.filter.clone();
+        for (field, value) in [
+            ("type", &params.0.note_type),
+            ("area", &params.0.area),
+            ("status", &params.0.status),
+        ] {
+            if let Some(value) = value {
+                filter = filter.field(field, value);
+            }
+        }
+        for (field, value) in &params.0.where_fields {
+            filter = filter.field(field.trim_start_matches("elysium_"), value);
+        }
+        for tag in &params.0.tags {
+            filter = filter.tag(tag);
+        }
+        if let Some(folder) = &params.0.folder {
+            filter = filter.folder(folder);
+        }
+        if !params.0.include_archived && !filter.requires_field("status") {
+            filter = filter.exclude_archived(&self.get_vault_paths().config.folders);
+        }
 
-        // If filtering, fetch more results to account for filtered-out items
-        let has_filter = note_type_filter.is_some() || area_filter.is_some();
-        let fetch_multiplier = if has_filter { 5 } else { 1 };
+        // Dates, phrases and exclusions are not indexed: resolve them to paths.
+        // Keyword search enforces NOT and AND itself; semantic results are
+        // checked against the notes
+        let notes = self.get_notes();
+        let keyword_constraints = options.mode != SearchMode::Keyword && keyword.has_constraints();
+        if query.has_constraints() || keyword_constraints {
+            let allowed = notes
+                .iter()
+                .filter(|n| query.matches_constraints(n))
+                .filter(|n| !keyword_constraints || keyword.admits(&NoteFields::of(n)))
+                .map(|n| relative_path(&self.vault_path, &n.path))
+                .collect();
+            filter.include.push(Condition::Paths(allowed));
+        }
 
         // Clamp limit: default 5, max 100 (DoS prevention)
         let limit = params.0.limit.max(1).min(100);
@@ -221,34 +326,70 @@ impl VaultService {
             limit
         };
 
-        let fetch_limit = (limit * fetch_multiplier).min(500);
+        let text = &query.search_text;
+
+        // Query expansion (BM25 only): tag aliases and synonyms at reduced weight
+        let config = self.cache.config();
+        let ranking = &config.search;
+        let expansion = if params.0.expand.or(ranking.expansion).unwrap_or(false)
+            && options.mode != SearchMode::Semantic
+        {
+            let weight = ranking
+                .expansion_weight
+                .unwrap_or(expansion::DEFAULT_WEIGHT);
+            Some(self.cache.query_expander().expand(&query.text(), weight))
+                .filter(|e| !e.is_empty())
+        } else {
+            None
+        };
+        options.expansion = expansion.as_ref();
+
+        let search_failed =
+            |e: anyhow::Error| McpError::internal_error(format!("Search failed: {}", e), None);
+        let mut results: Vec<(SearchResult, Option<ScoreExplanation>)> = if params.0.explain {
+            engine
+                .search_explained(text, limit, options, &filter)
+                .map_err(search_failed)?
+                .into_iter()
+                .map(|(r, explanation)| (r, Some(explanation)))
+                .collect()
+        } else {
+            engine
+                .search(text, limit, options, &filter)
+                .map_err(search_failed)?
+                .into_iter()
+                .map(|r| (r, None))
+                .collect()
+        };
 
-        let results = engine
-            .search(&params.0.query, fetch_limit, search_mode)
-            .map_err(|e| McpError::internal_error(format!("Search failed: {}", e), None))?;
+        if let Some(sort) = &query.sort {
+            let by_path: HashMap<String, &Note> = notes
+                .iter()
+                .map(|n| (relative_path(&self.vault_path, &n.path), n))
+                .collect();
+            sort.apply(&mut results, |(r, _)| by_path.get(&r.path).copied());
+        }
 
         // Build dynamic JSON based on fields parameter
         let (requested_fields, is_all) = resolve_fields(&params.0.fields);
 
+        let mut snippets = if params.0.snippets {
+            let paths: Vec<String> = results.iter().map(|(r, _)| r.path.clone()).collect();
+            engine.snippets(text, &paths).map_err(|e| {
+                McpError::internal_error(format!("Snippet generation failed: {}", e), None)
+            })?
+        } else {
+            HashMap::new()
+        };
+
         let json_results: Vec<HashMap<String, serde_json::Value>> = results
             .into_iter()
-            .filter(|r| {
-                // Apply note_type filter
-                let type_match = note_type_filter
-                    .as_ref()
-                    .map_or(true, |t| r.note_type.as_ref().map_or(false, |nt| nt == t));
-                // Apply area filter
-                let area_match = area_filter
-                    .as_ref()
-                    .map_or(true, |a| r.area.as_ref().map_or(false, |na| na == a));
-                type_match && area_match
-            })
-            .take(limit)
-            .map(|r| {
+            .map(|(r, explanation)| {
                 let mut result: HashMap<String, serde_json::Value> = HashMap::new();
 
                 // Always include title, path, and score for search results
                 result.insert("title".to_string(), serde_json::Value::String(r.title));
+                let result_path = r.path.clone();
                 result.insert("path".to_string(), serde_json::Value::String(r.path));
                 result.insert("score".to_string(), serde_json::json!(r.score));
 
@@ -271,43 +412,198 @@ impl VaultService {
                         result.insert("area".to_string(), serde_json::Value::String(area));
                     }
                 }
+                if let Some(heading) = r.heading {
+                    result.insert("heading".to_string(), serde_json::Value::String(heading));
+                }
+                if let Some(snippet) = snippets.remove(&result_path) {
+                    result.insert("snippet".to_string(), serde_json::Value::String(snippet));
+                }
+                if let Some(explanation) = explanation {
+                    result.insert("explain".to_string(), serde_json::json!(explanation));
+                }
 
                 result
             })
             .collect();
 
-        let output = serde_json::to_string_pretty(&json_results).map_err(|e| {
+        // Expansion and a lenient syntax error are reported next to the
+        // results, only when they apply
+        let mut output = serde_json::json!({ "results": json_results });
+        if let Some(expansion) = &expansion {
+            output["expansion"] = serde_json::json!(expansion);
+        }
+        if let Some(e) = &syntax_error {
+            output["warning"] = serde_json::json!({
+                "message": format!("Invalid query: {}; searched the words literally", e),
+                "position": e.position,
+            });
+        }
+        let output = serde_json::to_string_pretty(&output).map_err(|e| {
             McpError::internal_error(format!("JSON serialization failed: {}", e), None)
         })?;
 
         Ok(CallToolResult::success(vec![Content::text(output)]))
     }
 
+    /// Evaluate search quality on a judged query set
+    #[tool(
+        description = "Evaluate search ranking on a judged query set (queries with relevant notes, optionally graded). Runs each search mode and hybrid fusion method (optionally with several bm25Weight/semanticWeight/rrfK configs) and reports MRR, nDCG@k and recall@k, to compare ranking settings on this vault."
+    )]
+    async fn vault_search_eval(
+        &self,
+        params: Parameters<SearchEvalParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let path = params.0.path.as_deref().unwrap_or(eval::EVAL_SET_FILE);
+        let set = match EvalSet::load(&self.vault_path.join(path)) {
+            Ok(set) => set,
+            Err(e) => return Ok(error_result(format!("{:#}", e))),
+        };
+
+        let modes: Vec<SearchMode> = if params.0.modes.is_empty() {
+            vec![
+                SearchMode::Hybrid,
+                SearchMode::Semantic,
+                SearchMode::Keyword,
+            ]
+        } else {
+            let mut modes = Vec::new();
+            for name in &params.0.modes {
+                match SearchMode::parse(name) {
+                    Some(mode) if !modes.contains(&mode) => modes.push(mode),
+                    Some(_) => {}
+                    None => {
+                        return Ok(error_result(format!(
+                            "Unknown search mode '{}' (expected {})",
+                            name,
+                            SearchMode::NAMES
+                        )))
+                    }
+                }
+            }
+            modes
+        };
+        let mut fusions = Vec::new();
+        for name in &params.0.fusions {
+            match FusionMethod::parse(name) {
+                Some(fusion) if !fusions.contains(&fusion) => fusions.push(fusion),
+                Some(_) => {}
+                None => {
+                    return Ok(error_result(format!(
+                        "Unknown fusion method '{}' (expected {})",
+                        name,
+                        FusionMethod::NAMES
+                    )))
+                }
+            }
+        }
+
+        let engine = self.get_hybrid_engine()?;
+        let mut engine = lock(&engine);
+
+        // Hybrid settings: the vault config, or each override of it
+        let configured = engine.config().clone();
+        if fusions.is_empty() {
+            fusions.push(configured.fusion);
+        }
+        let mut hybrid_runs = Vec::new();
+        let overrides = if params.0.configs.is_empty() {
+            vec![(None, EvalConfigParams::default())]
+        } else {
+            params
+                .0
+                .configs
+                .into_iter()
+                .enumerate()
+                .map(|(i, c)| {
+                    (
+                        Some(c.name.clone().unwrap_or(format!("config{}", i + 1))),
+                        c,
+                    )
+                })
+                .collect()
+        };
+        for (label, overrides) in overrides {
+            let methods = match &overrides.fusion {
+                None => fusions.clone(),
+                Some(name) => match FusionMethod::parse(name) {
+                    Some(fusion) => vec![fusion],
+                    None => {
+                        return Ok(error_result(format!(
+                            "Unknown fusion method '{}' (expected {})",
+                            name,
+                            FusionMethod::NAMES
+                        )))
+                    }
+                },
+            };
+            let config = HybridConfig {
+                bm25_weight: overrides.bm25_weight.unwrap_or(configured.bm25_weight),
+                semantic_weight: overrides
+                    .semantic_weight
+                    .unwrap_or(configured.semantic_weight),
+                rrf_k: overrides.rrf_k.unwrap_or(configured.rrf_k),
+                ..configured.clone()
+            };
+            hybrid_runs.extend(methods.into_iter().map(|fusion| EvalRun {
+                mode: SearchMode::Hybrid,
+                config: HybridConfig {
+                    fusion,
+                    ..config.clone()
+                },
+                label: label.clone(),
+            }));
+        }
+
+        // One run per mode; hybrid once per fusion method and settings
+        let mut runs = Vec::new();
+        for mode in modes {
+            if mode == SearchMode::Hybrid {
+                runs.extend(hybrid_runs.iter().cloned());
+            } else {
+                runs.push(EvalRun {
+                    mode,
+                    config: configured.clone(),
+                    label: None,
+                });
+            }
+        }
+
+        let filter = if params.0.include_archived {
+            SearchFilter::default()
+        } else {
+            SearchFilter::default().exclude_archived(&self.get_vault_paths().config.folders)
+        };
+        let report = eval::evaluate(
+            &mut engine,
+            &set,
+            &runs,
+            params.0.k,
+            &filter,
+            params.0.per_query,
+        )
+        .map_err(|e| McpError::internal_error(format!("Evaluation failed: {:#}", e), None))?;
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&report).unwrap_or_default(),
+        )]))
+    }
+
     /// Find related notes using semantic similarity
     #[tool(
-        description = "Find related notes using semantic similarity with optional type/area boosting."
+        description = "Find related notes using semantic similarity with optional type/area and link-graph boosting."
     )]
     async fn vault_related(
         &self,
         params: Parameters<RelatedParams>,
     ) -> Result<CallToolResult, McpError> {
         let vault_paths = self.get_vault_paths();
-        let notes = collect_all_notes(&vault_paths);
-        let note_name = &params.0.note;
-
-        let found = notes.iter().find(|n| {
-            n.name == *note_name
-                || n.path.file_stem().map(|s| s.to_string_lossy().to_string())
-                    == Some(note_name.clone())
-        });
-
-        let source_note = match found {
-            Some(n) => n,
-            None => {
+        let notes = self.get_notes();
+        let source_note = match resolve_note(&notes, &self.vault_path, &params.0.note) {
+            Ok(n) => n,
+            Err(e) => {
                 return Ok(CallToolResult::success(vec![Content::text(
-                    serde_json::json!({"error": format!("Note '{}' not found", note_name)})
-                        .to_string(),
-                )]));
+                    resolve_error_json(&e).to_string(),
+                )]))
             }
         };
 
@@ -321,19 +617,115 @@ impl VaultService {
             }
         };
 
-        let engine = self.get_plugin_engine()?;
+        let boost = RelatedBoost {
+            boost_type: params.0.boost_type,
+            boost_area: params.0.boost_area,
+            boost_graph: params.0.boost_links,
+        };
+        let graph = boost.boost_graph.then(|| self.get_link_graph());
+        let links_of = |path: &str| -> HashSet<String> {
+            graph.as_ref().map_or_else(HashSet::new, |graph| {
+                graph
+                    .neighbors(path, 1, Direction::Both)
+                    .into_iter()
+                    .map(|(linked, _)| linked)
+                    .collect()
+            })
+        };
+        let source_path = relative_path(&self.vault_path, &source_note.path);
+        let source = NoteSignals {
+            path: &source_path,
+            note_type: source_note.note_type(),
+            area: source_note.area(),
+            tags: source_note.tags(),
+            links: links_of(&source_path),
+        };
+
+        let engine = self.get_hybrid_engine()?;
+        let engine = lock(&engine);
+        let engine = engine.semantic_engine();
         let limit = params.0.limit.max(1).min(50);
 
-        // Note: boost_type and boost_area are currently ignored when using plugin index
-        // TODO: Implement boost in PluginSearchEngine if needed
-        let results = engine
-            .search(gist, limit + 1)
+        // Extra candidates cover the source note and filtered archived notes;
+        // boosts and diversity re-rank a larger pool
+        let fetch_limit = if params.0.include_archived {
+            limit + 1
+        } else {
+            (limit + 1) * 2
+        };
+        let diversity = params.0.diversity.filter(|d| *d > 0.0);
+        let fetch_limit = if boost.is_active() || diversity.is_some() {
+            mmr::candidate_pool(fetch_limit)
+        } else {
+            fetch_limit
+        };
+        let embedding = engine
+            .embed_query(gist)
             .map_err(|e| McpError::internal_error(format!("Search failed: {}", e), None))?;
+        let mut results = engine.search_vector(&embedding, fetch_limit, &SearchFilter::default());
+
+        // Linked notes are candidates even when their gists are less similar
+        if boost.boost_graph {
+            let found: HashSet<String> = results.iter().map(|r| r.path.clone()).collect();
+            let linked: HashSet<String> = source.links.difference(&found).cloned().collect();
+            if !linked.is_empty() {
+                let count = linked.len();
+                let mut filter = SearchFilter::default();
+                filter.include.push(Condition::Paths(linked));
+                results.extend(engine.search_vector(&embedding, count, &filter));
+            }
+        }
 
-        let filtered: Vec<SearchResultJson> = results
+        let folders = &vault_paths.config.folders;
+        let mut candidates: Vec<SearchResult> = results
             .into_iter()
             .filter(|r| r.title != source_note.name)
-            .take(limit)
+            .filter(|r| {
+                params.0.include_archived
+                    || !folders.is_archived(
+                        &r.path,
+                        engine
+                            .get_note(&r.path)
+                            .and_then(|n| n.fields.get("status"))
+                            .map(String::as_str),
+                    )
+            })
+            .collect();
+
+        if boost.is_active() {
+            for r in &mut candidates {
+                let candidate = NoteSignals {
+                    path: &r.path,
+                    note_type: r.note_type.as_deref(),
+                    area: r.area.as_deref(),
+                    tags: engine
+                        .get_note(&r.path)
+                        .and_then(|n| n.tags.clone())
+                        .unwrap_or_default(),
+                    links: links_of(&r.path),
+                };
+                let score = boosted_score(r.score, &source, &candidate, boost);
+                r.score = score;
+            }
+            candidates.sort_by(|a, b| {
+                b.score
+                    .total_cmp(&a.score)
+                    .then_with(|| a.path.cmp(&b.path))
+            });
+        }
+
+        let ranked = match diversity {
+            Some(diversity) => mmr::rerank(
+                candidates,
+                limit,
+                diversity,
+                |r| r.score,
+                |r| engine.vector(&r.path),
+            ),
+            None => candidates.into_iter().take(limit).collect(),
+        };
+        let filtered: Vec<SearchResultJson> = ranked
+            .into_iter()
             .map(|r| SearchResultJson {
                 title: r.title,
                 path: r.path,
@@ -358,26 +750,17 @@ impl VaultService {
         &self,
         params: Parameters<GetNoteParams>,
     ) -> Result<CallToolResult, McpError> {
-        let vault_paths = self.get_vault_paths();
-        let notes = collect_all_notes(&vault_paths);
-        let note_name = &params.0.note;
-
-        // Find note by title or path
-        let found = notes.into_iter().find(|n| {
-            n.name == *note_name
-                || n.path.to_string_lossy().contains(note_name)
-                || n.path.file_stem().map(|s| s.to_string_lossy().to_string())
-                    == Some(note_name.clone())
-        });
+        let notes = self.get_notes();
 
-        match found {
-            Some(n) => {
+        // Find note by path, title, alias or a unique partial match
+        match resolve_note(&notes, &self.vault_path, &params.0.note) {
+            Ok(n) => {
                 let content = std::fs::read_to_string(&n.path).map_err(|e| {
                     McpError::internal_error(format!("Failed to read note: {}", e), None)
                 })?;
 
                 // Build dynamic metadata based on fields parameter
-                let metadata = build_note_json(&n, &params.0.fields);
+                let metadata = build_note_json(n, &params.0.fields);
                 let metadata_json = serde_json::to_string_pretty(&metadata).unwrap_or_default();
 
                 let output = format!(
@@ -387,23 +770,35 @@ impl VaultService {
 
                 Ok(CallToolResult::success(vec![Content::text(output)]))
             }
-            None => Ok(CallToolResult::success(vec![Content::text(format!(
-                "Note not found: {}",
-                note_name
-            ))])),
+            Err(e) => Ok(CallToolResult::success(vec![Content::text(
+                resolve_error_json(&e).to_string(),
+            )])),
         }
     }
 
     /// List notes in the vault with optional filters
-    #[tool(description = "List notes in Second Brain Vault with optional type/area filters.")]
+    #[tool(
+        description = "List notes in Second Brain Vault with optional type/area filters or a query (e.g. 'type:project tag:gpu modified:>2026-01-01 sort:modified desc')."
+    )]
     async fn vault_list_notes(
         &self,
         params: Parameters<ListNotesParams>,
     ) -> Result<CallToolResult, McpError> {
+        let query = match params.0.query.as_deref().map(NoteQuery::parse) {
+            Some(Ok(query)) => query,
+            Some(Err(e)) => return Ok(query_error_result(&e)),
+            None => NoteQuery::default(),
+        };
+        let keyword = match KeywordQuery::parse_condition(&query.search_text) {
+            Ok(keyword) => keyword,
+            Err(e) => return Ok(query_error_result(&e)),
+        };
         let vault_paths = self.get_vault_paths();
-        let notes = collect_all_notes(&vault_paths);
+        let notes = self.get_notes();
+        let folders = &vault_paths.config.folders;
         let note_type = &params.0.note_type;
         let area = &params.0.area;
+        let include_archived = params.0.include_archived || query.filter.requires_field("status");
         // Clamp limit: default 50, max 500 (DoS prevention)
         let limit = params.0.limit.max(1).min(500);
         let limit = if limit == 1 && params.0.limit == 0 {
@@ -412,20 +807,31 @@ impl VaultService {
             limit
         };
 
-        // Build dynamic JSON based on fields parameter
-        let fields_param = &params.0.fields;
-        let filtered: Vec<HashMap<String, serde_json::Value>> = notes
-            .into_iter()
+        let mut matching: Vec<&Note> = notes
+            .iter()
             .filter(|n| {
+                let path = relative_path(&self.vault_path, &n.path);
                 note_type
                     .as_ref()
                     .map_or(true, |t| n.note_type().map_or(false, |nt| nt == t))
                     && area
                         .as_ref()
                         .map_or(true, |a| n.area().map_or(false, |na| na == a))
+                    && (include_archived || !folders.is_archived(&path, n.status()))
+                    && query.matches(&path, n)
+                    && keyword.matches(&NoteFields::of(n))
             })
+            .collect();
+        if let Some(sort) = &query.sort {
+            sort.apply(&mut matching, |n| Some(*n));
+        }
+
+        // Build dynamic JSON based on fields parameter
+        let fields_param = &params.0.fields;
+        let filtered: Vec<HashMap<String, serde_json::Value>> = matching
+            .into_iter()
             .take(limit)
-            .map(|n| build_note_json(&n, fields_param))
+            .map(|n| build_note_json(n, fields_param))
             .collect();
 
         let output = serde_json::to_string_pretty(&filtered).map_err(|e| {
@@ -440,15 +846,14 @@ impl VaultService {
         description = "Get Second Brain Vault status summary including note counts by type/area and health score (0-100)."
     )]
     async fn vault_status(&self) -> Result<CallToolResult, McpError> {
-        let vault_paths = self.get_vault_paths();
-        let notes = collect_all_notes(&vault_paths);
+        let notes = self.get_notes();
 
         let mut by_type: std::collections::HashMap<String, usize> =
             std::collections::HashMap::new();
         let mut by_area: std::collections::HashMap<String, usize> =
             std::collections::HashMap::new();
 
-        for note in &notes {
+        for note in notes.iter() {
             if let Some(t) = note.note_type() {
                 *by_type.entry(t.to_string()).or_insert(0) += 1;
             }
@@ -506,8 +911,8 @@ impl VaultService {
         params: Parameters<AuditParams>,
     ) -> Result<CallToolResult, McpError> {
         let vault_paths = self.get_vault_paths();
-        let notes = collect_all_notes(&vault_paths);
-        let note_names = collect_note_names(&vault_paths);
+        let notes = self.get_notes();
+        let resolver = LinkResolver::new(self.cache.files().iter().cloned());
         let quick = params.0.quick;
         let verbose = params.0.verbose;
 
@@ -521,10 +926,15 @@ impl VaultService {
         checks.push(schema_check);
 
         // Wikilinks check
-        let wikilinks_check = audit::check_wikilinks(&notes, &note_names, verbose);
+        let wikilinks_check = audit::check_wikilinks(&notes, &self.vault_path, &resolver, verbose);
         checks.push(wikilinks_check);
 
         if !quick {
+            // Folder-type matching check
+            let rules = vault_paths.config.folders.folder_rules();
+            let folders_check = audit::check_folders(&notes, &self.vault_path, &rules, verbose);
+            checks.push(folders_check);
+
             // Gist coverage check
             let gist_check = audit::check_gist(&notes, verbose);
             checks.push(gist_check);
@@ -534,7 +944,8 @@ impl VaultService {
             checks.push(tags_check);
 
             // Orphan notes check
-            let orphans_check = audit::check_orphans(&notes, &note_names, verbose);
+            let graph = self.get_link_graph();
+            let orphans_check = audit::check_orphans(&notes, &self.vault_path, &graph, verbose);
             checks.push(orphans_check);
 
             // Stale gists check
@@ -560,6 +971,39 @@ impl VaultService {
         Ok(CallToolResult::success(vec![Content::text(output)]))
     }
 
+    /// Rebuild the search index without Obsidian
+    #[tool(
+        description = "Rebuild the semantic search index from the vault files (same format as the Obsidian plugin export). Use when the plugin is not running or the index is missing/stale."
+    )]
+    async fn vault_reindex(
+        &self,
+        params: Parameters<ReindexParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let indexer = match params.0.embedding_mode.as_deref() {
+            Some(mode) => VaultIndexer::with_mode(&self.vault_path, mode),
+            None => VaultIndexer::new(&self.vault_path),
+        }
+        .map_err(|e| McpError::internal_error(format!("Failed to create indexer: {}", e), None))?;
+
+        let stats = indexer
+            .build()
+            .map_err(|e| McpError::internal_error(format!("Indexing failed: {}", e), None))?;
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&serde_json::json!({
+                "success": true,
+                "note_count": stats.note_count,
+                "embedding_mode": stats.embedding_mode,
+                "dimension": stats.dimension,
+                "index_size": stats.index_size,
+                "exported_at": stats.exported_at,
+                "chunked_notes": stats.chunked_notes,
+                "duration_ms": stats.duration_ms
+            }))
+            .unwrap_or_default(),
+        )]))
+    }
+
     #[tool(
         description = "Get the content of the inbox file for AI processing. Returns content and processing instructions."
     )]
@@ -632,6 +1076,7 @@ impl VaultService {
 
         std::fs::write(&inbox_path, "")
             .map_err(|e| McpError::internal_error(format!("Failed to clear inbox: {}", e), None))?;
+        self.cache.invalidate_notes();
 
         Ok(CallToolResult::success(vec![Content::text(
             serde_json::json!({
@@ -643,12 +1088,12 @@ impl VaultService {
     }
 
     #[tool(
-        description = "Unified save interface for vault notes. Supports strategies: 'create' (new note), 'update' (overwrite), 'append' (add content), 'inbox' (quick capture), 'smart' (auto-detect duplicates)."
+        description = "Unified save interface for vault notes. Supports strategies: 'create' (new note), 'update' (merge into existing note), 'append' (add content), 'inbox' (quick capture), 'smart' (auto-detect duplicates)."
     )]
     async fn vault_save(&self, params: Parameters<SaveParams>) -> Result<CallToolResult, McpError> {
         let strategy = params.0.strategy.to_lowercase();
 
-        match strategy.as_str() {
+        let result = match strategy.as_str() {
             "create" => self.save_create(&params.0).await,
             "update" => self.save_update(&params.0).await,
             "append" => self.save_append(&params.0).await,
@@ -661,119 +1106,618 @@ impl VaultService {
                 })
                 .to_string(),
             )])),
-        }
-    }
-}
-
-// Save strategy implementations
-impl VaultService {
-    fn get_target_folder(&self, _note_type: Option<&str>) -> PathBuf {
-        let vault_paths = self.get_vault_paths();
-        let folders = &vault_paths.config.folders;
+        };
 
-        // Always use Notes/ folder for flat structure (vault policy)
-        let folder = &folders.notes;
-        let target = self.vault_path.join(folder);
+        self.cache.invalidate_notes();
+        result
+    }
 
-        if !target.exists() {
-            let _ = std::fs::create_dir_all(&target);
-        }
+    #[tool(
+        description = "Update frontmatter fields of a note in place (set, remove, append to lists). Other fields, comments and the note body are preserved."
+    )]
+    async fn vault_update_fields(
+        &self,
+        params: Parameters<UpdateFieldsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
 
-        target
-    }
+        let note = match self.find_note(&params.note) {
+            Ok(note) => note,
+            Err(e) => return Ok(resolve_error_result(&e)),
+        };
 
-    async fn save_create(&self, params: &SaveParams) -> Result<CallToolResult, McpError> {
-        let filename = format!("{}.md", params.title);
-        let target_folder = self.get_target_folder(params.note_type.as_deref());
-        let note_path = target_folder.join(&filename);
-        let root_path = self.vault_path.join(&filename);
+        let mut editor = FrontmatterEditor::new(&note.content);
+        let mut changed: Vec<String> = Vec::new();
 
-        if note_path.exists() || root_path.exists() {
-            let existing_path = if note_path.exists() {
-                &note_path
-            } else {
-                &root_path
-            };
-            return Ok(CallToolResult::success(vec![Content::text(
-                serde_json::json!({
-                    "success": false,
-                    "error": format!("Note already exists: {}", existing_path.to_string_lossy()),
-                    "suggestion": "Use strategy='update' to overwrite or strategy='append' to add content"
-                })
-                .to_string(),
-            )]));
+        for (field, value) in params.set.iter().flatten() {
+            let key = elysium_key(field);
+            if editor.set(&key, FieldValue::from_json(value)) {
+                changed.push(key);
+            }
+        }
+        for field in params.remove.iter().flatten() {
+            let key = elysium_key(field);
+            if editor.remove(&key) {
+                changed.push(key);
+            }
+        }
+        for (field, items) in params.append.iter().flatten() {
+            let key = elysium_key(field);
+            if !editor.append_to_list(&key, items).is_empty() && !changed.contains(&key) {
+                changed.push(key);
+            }
         }
 
-        let frontmatter = self.build_frontmatter(params);
-        let full_content = format!("{}{}", frontmatter, params.content);
+        let new_content = editor.to_content();
+        let frontmatter = Frontmatter::parse(&new_content).unwrap_or_default();
+        let warnings: Vec<String> = frontmatter
+            .validate_with_config(&self.get_schema_validator())
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
 
-        std::fs::write(&note_path, &full_content)
-            .map_err(|e| McpError::internal_error(format!("Failed to create note: {}", e), None))?;
+        if !params.dry_run && !changed.is_empty() {
+            std::fs::write(&note.path, &new_content).map_err(|e| {
+                McpError::internal_error(format!("Failed to update note: {}", e), None)
+            })?;
+            self.cache.invalidate_notes();
+        }
 
         Ok(CallToolResult::success(vec![Content::text(
-            serde_json::json!({
+            serde_json::to_string_pretty(&serde_json::json!({
                 "success": true,
-                "action": "created",
-                "path": note_path.to_string_lossy(),
-                "folder": target_folder.file_name().unwrap_or_default().to_string_lossy(),
-                "title": params.title
-            })
-            .to_string(),
+                "path": note.path.to_string_lossy(),
+                "changed": changed,
+                "dry_run": params.dry_run,
+                "fields": frontmatter.to_json_map(),
+                "warnings": warnings
+            }))
+            .unwrap_or_default(),
         )]))
     }
 
-    async fn save_update(&self, params: &SaveParams) -> Result<CallToolResult, McpError> {
-        let vault_paths = self.get_vault_paths();
-        let notes = collect_all_notes(&vault_paths);
+    #[tool(
+        description = "Rename and/or move a note, rewriting every wikilink and [text](note.md) link to it across the vault (aliases, headings and block references are kept). Use dry_run to preview."
+    )]
+    async fn vault_rename(
+        &self,
+        params: Parameters<RenameParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
 
-        let found = notes.into_iter().find(|n| {
-            n.name == params.title
-                || n.path.file_stem().map(|s| s.to_string_lossy().to_string())
-                    == Some(params.title.clone())
-        });
+        let note = match self.find_note(&params.note) {
+            Ok(note) => note,
+            Err(e) => return Ok(resolve_error_result(&e)),
+        };
+        if params.new_name.is_none() && params.new_folder.is_none() {
+            return Ok(error_result(
+                "Provide new_name and/or new_folder".to_string(),
+            ));
+        }
 
-        match found {
-            Some(note) => {
-                let frontmatter = self.build_frontmatter(params);
-                let full_content = format!("{}{}", frontmatter, params.content);
+        let new_name = params
+            .new_name
+            .as_deref()
+            .map(|n| n.trim().trim_end_matches(".md"))
+            .unwrap_or(&note.name);
+        if new_name.is_empty() || new_name.contains(['/', '\\']) {
+            return Ok(error_result(format!("Invalid note name: {}", new_name)));
+        }
 
-                std::fs::write(&note.path, &full_content).map_err(|e| {
-                    McpError::internal_error(format!("Failed to update note: {}", e), None)
-                })?;
+        let folder = match params.new_folder.as_deref() {
+            Some(f) => self.vault_path.join(f.trim_matches('/')),
+            None => note.path.parent().unwrap_or(&self.vault_path).to_path_buf(),
+        };
+        if !folder.starts_with(&self.vault_path)
+            || folder.components().any(|c| c.as_os_str() == "..")
+        {
+            return Ok(error_result(
+                "Destination folder must be inside the vault".to_string(),
+            ));
+        }
+        let target = folder.join(format!("{}.md", new_name));
 
-                Ok(CallToolResult::success(vec![Content::text(
-                    serde_json::json!({
-                        "success": true,
-                        "action": "updated",
-                        "path": note.path.to_string_lossy(),
-                        "title": params.title
-                    })
-                    .to_string(),
-                )]))
-            }
-            None => Ok(CallToolResult::success(vec![Content::text(
+        let plan = match plan_rename(&self.get_vault_paths(), &note.path, &target) {
+            Ok(plan) => plan,
+            Err(e) => return Ok(error_result(e.to_string())),
+        };
+
+        if !params.dry_run {
+            plan.apply()
+                .map_err(|e| McpError::internal_error(format!("Rename failed: {}", e), None))?;
+            self.cache.invalidate_notes();
+        }
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&serde_json::json!({
+                "success": true,
+                "dry_run": params.dry_run,
+                "from": relative_path(&self.vault_path, &plan.from),
+                "to": relative_path(&self.vault_path, &plan.to),
+                "files_changed": plan.files_changed(),
+                "links_updated": plan.edits.len(),
+                "edits": plan.edits
+            }))
+            .unwrap_or_default(),
+        )]))
+    }
+
+    #[tool(
+        description = "Archive a note: set elysium_status to archived, record the archive date and move it into the archive folder. Wikilinks to it keep resolving. Use dry_run to preview."
+    )]
+    async fn vault_archive(
+        &self,
+        params: Parameters<ArchiveParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let note = match self.find_note(&params.note) {
+            Ok(note) => note,
+            Err(e) => return Ok(resolve_error_result(&e)),
+        };
+
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        match plan_archive(&self.get_vault_paths(), &note.path, &today) {
+            Ok(plan) => self.apply_move(plan, params.dry_run, "archived"),
+            Err(e) => Ok(error_result(e.to_string())),
+        }
+    }
+
+    #[tool(
+        description = "Restore an archived note: set its status (default: active) and move it back to the folder it was archived from. Wikilinks to it keep resolving. Use dry_run to preview."
+    )]
+    async fn vault_unarchive(
+        &self,
+        params: Parameters<UnarchiveParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let note = match self.find_note(&params.note) {
+            Ok(note) => note,
+            Err(e) => return Ok(resolve_error_result(&e)),
+        };
+
+        let status = params.status.as_deref().unwrap_or("active");
+        match plan_unarchive(
+            &self.get_vault_paths(),
+            &note.path,
+            params.folder.as_deref(),
+            status,
+        ) {
+            Ok(plan) => self.apply_move(plan, params.dry_run, status),
+            Err(e) => Ok(error_result(e.to_string())),
+        }
+    }
+
+    #[tool(
+        description = "List notes that link to a note (backlinks), with the number of links from each."
+    )]
+    async fn vault_backlinks(
+        &self,
+        params: Parameters<LinksParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let notes = self.get_notes();
+        let note = match resolve_note(&notes, &self.vault_path, &params.0.note) {
+            Ok(note) => note,
+            Err(e) => return Ok(resolve_error_result(&e)),
+        };
+        let path = relative_path(&self.vault_path, &note.path);
+        let graph = self.get_link_graph();
+        let backlinks = link_list(&graph.backlinks(&path));
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&serde_json::json!({
+                "note": path,
+                "count": backlinks.len(),
+                "backlinks": backlinks
+            }))
+            .unwrap_or_default(),
+        )]))
+    }
+
+    #[tool(
+        description = "List the notes a note links to (outgoing links), plus link targets that do not resolve to any file."
+    )]
+    async fn vault_outlinks(
+        &self,
+        params: Parameters<LinksParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let notes = self.get_notes();
+        let note = match resolve_note(&notes, &self.vault_path, &params.0.note) {
+            Ok(note) => note,
+            Err(e) => return Ok(resolve_error_result(&e)),
+        };
+        let path = relative_path(&self.vault_path, &note.path);
+        let graph = self.get_link_graph();
+        let outlinks = link_list(&graph.outlinks(&path));
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&serde_json::json!({
+                "note": path,
+                "count": outlinks.len(),
+                "outlinks": outlinks,
+                "unresolved": graph.unresolved(&path)
+            }))
+            .unwrap_or_default(),
+        )]))
+    }
+
+    #[tool(
+        description = "Find notes within N link hops of a note, following outgoing links, backlinks or both. Results are ordered by distance."
+    )]
+    async fn vault_neighbors(
+        &self,
+        params: Parameters<NeighborsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let direction = match params.direction.as_deref() {
+            None => Direction::Both,
+            Some(d) => match Direction::parse(d) {
+                Some(direction) => direction,
+                None => {
+                    return Ok(error_result(format!(
+                        "Unknown direction '{}'. Use: out, in, both",
+                        d
+                    )))
+                }
+            },
+        };
+        let note = match self.find_note(&params.note) {
+            Ok(note) => note,
+            Err(e) => return Ok(resolve_error_result(&e)),
+        };
+
+        let path = relative_path(&self.vault_path, &note.path);
+        let depth = params.depth.clamp(1, 5);
+        let graph = self.get_link_graph();
+        let neighbors = graph.neighbors(&path, depth, direction);
+
+        let results: Vec<serde_json::Value> = neighbors
+            .iter()
+            .take(params.limit)
+            .map(|(neighbor, distance)| {
+                let degree = graph.degree(neighbor);
+                serde_json::json!({
+                    "path": neighbor,
+                    "title": note_title(neighbor),
+                    "distance": distance,
+                    "incoming": degree.incoming,
+                    "outgoing": degree.outgoing
+                })
+            })
+            .collect();
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&serde_json::json!({
+                "note": path,
+                "depth": depth,
+                "total": neighbors.len(),
+                "count": results.len(),
+                "neighbors": results
+            }))
+            .unwrap_or_default(),
+        )]))
+    }
+
+    #[tool(
+        description = "Link graph statistics: note/link counts, hub notes (most connected), connected components, dead ends (no outgoing links) and isolated notes."
+    )]
+    async fn vault_graph_stats(
+        &self,
+        params: Parameters<GraphStatsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let limit = params.0.limit;
+        let stats = self.get_link_graph().stats(limit);
+
+        let hubs: Vec<serde_json::Value> = stats
+            .hubs
+            .iter()
+            .map(|(path, degree)| {
+                serde_json::json!({
+                    "path": path,
+                    "title": note_title(path),
+                    "incoming": degree.incoming,
+                    "outgoing": degree.outgoing
+                })
+            })
+            .collect();
+        let sample = |notes: &[String]| notes.iter().take(limit).cloned().collect::<Vec<_>>();
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&serde_json::json!({
+                "notes": stats.note_count,
+                "links": stats.link_count,
+                "unresolved_links": stats.unresolved_count,
+                "hubs": hubs,
+                "components": {
+                    "count": stats.components.len(),
+                    "largest": stats.components.first().copied().unwrap_or(0),
+                    "sizes": stats.components.iter().take(limit).collect::<Vec<_>>()
+                },
+                "dead_ends": {
+                    "count": stats.dead_ends.len(),
+                    "notes": sample(&stats.dead_ends)
+                },
+                "isolated": {
+                    "count": stats.isolated.len(),
+                    "notes": sample(&stats.isolated)
+                }
+            }))
+            .unwrap_or_default(),
+        )]))
+    }
+}
+
+/// Note title (file stem) of a vault-relative path
+fn note_title(path: &str) -> &str {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.strip_suffix(".md").unwrap_or(name)
+}
+
+/// JSON list of linked notes with link counts
+fn link_list(links: &[(&str, usize)]) -> Vec<serde_json::Value> {
+    links
+        .iter()
+        .map(|(path, count)| {
+            serde_json::json!({
+                "path": path,
+                "title": note_title(path),
+                "links": count
+            })
+        })
+        .collect()
+}
+
+/// Parse error of a `NoteQuery`, with the column it refers to
+fn query_error_result(error: &QueryError) -> CallToolResult {
+    CallToolResult::success(vec![Content::text(
+        serde_json::json!({
+            "error": format!("Invalid query: {}", error),
+            "position": error.position,
+        })
+        .to_string(),
+    )])
+}
+
+/// Unresolved note name: the error plus ambiguous candidates or
+/// "did you mean" suggestions
+fn resolve_error_json(error: &ResolveError) -> serde_json::Value {
+    let mut json = serde_json::json!({ "error": error.to_string() });
+    if !error.candidates().is_empty() {
+        let key = match error {
+            ResolveError::Ambiguous { .. } => "candidates",
+            ResolveError::NotFound { .. } => "did_you_mean",
+        };
+        json[key] = serde_json::json!(error.candidates());
+    }
+    json
+}
+
+/// `resolve_error_json` as a failed tool result
+fn resolve_error_result(error: &ResolveError) -> CallToolResult {
+    let mut json = resolve_error_json(error);
+    json["success"] = serde_json::json!(false);
+    CallToolResult::success(vec![Content::text(json.to_string())])
+}
+
+fn error_result(message: String) -> CallToolResult {
+    CallToolResult::success(vec![Content::text(
+        serde_json::json!({ "success": false, "error": message }).to_string(),
+    )])
+}
+
+// Save strategy implementations
+impl VaultService {
+    /// Find a note to change: no partial matches, no silent pick among
+    /// duplicates
+    fn find_note(&self, query: &str) -> Result<Note, ResolveError> {
+        resolve_note_strict(&self.get_notes(), &self.vault_path, query).cloned()
+    }
+
+    /// Apply an archive/unarchive plan (unless dry run) and report it
+    fn apply_move(
+        &self,
+        plan: RenamePlan,
+        dry_run: bool,
+        status: &str,
+    ) -> Result<CallToolResult, McpError> {
+        if !dry_run {
+            plan.apply()
+                .map_err(|e| McpError::internal_error(format!("Move failed: {}", e), None))?;
+            self.cache.invalidate_notes();
+        }
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&serde_json::json!({
+                "success": true,
+                "dry_run": dry_run,
+                "from": relative_path(&self.vault_path, &plan.from),
+                "to": relative_path(&self.vault_path, &plan.to),
+                "status": status,
+                "files_changed": plan.files_changed(),
+                "links_updated": plan.edits.len(),
+                "edits": plan.edits
+            }))
+            .unwrap_or_default(),
+        )]))
+    }
+
+    fn get_target_folder(&self, _note_type: Option<&str>) -> PathBuf {
+        let vault_paths = self.get_vault_paths();
+        let folders = &vault_paths.config.folders;
+
+        // Always use Notes/ folder for flat structure (vault policy)
+        let folder = &folders.notes;
+        let target = self.vault_path.join(folder);
+
+        if !target.exists() {
+            let _ = std::fs::create_dir_all(&target);
+        }
+
+        target
+    }
+
+    async fn save_create(&self, params: &SaveParams) -> Result<CallToolResult, McpError> {
+        let filename = format!("{}.md", params.title);
+        let target_folder = self.get_target_folder(params.note_type.as_deref());
+        let note_path = target_folder.join(&filename);
+        let root_path = self.vault_path.join(&filename);
+
+        if note_path.exists() || root_path.exists() {
+            let existing_path = if note_path.exists() {
+                &note_path
+            } else {
+                &root_path
+            };
+            return Ok(CallToolResult::success(vec![Content::text(
                 serde_json::json!({
                     "success": false,
-                    "error": format!("Note not found: {}", params.title),
-                    "suggestion": "Use strategy='create' to create a new note"
+                    "error": format!("Note already exists: {}", existing_path.to_string_lossy()),
+                    "suggestion": "Use strategy='update' to merge into it or strategy='append' to add content"
                 })
                 .to_string(),
-            )])),
+            )]));
         }
+
+        let status = params.status.as_deref().unwrap_or("active");
+        let frontmatter = self.build_frontmatter(params, status);
+        let full_content = format!("{}{}", frontmatter, params.content);
+
+        std::fs::write(&note_path, &full_content)
+            .map_err(|e| McpError::internal_error(format!("Failed to create note: {}", e), None))?;
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::json!({
+                "success": true,
+                "action": "created",
+                "path": note_path.to_string_lossy(),
+                "folder": target_folder.file_name().unwrap_or_default().to_string_lossy(),
+                "title": params.title
+            })
+            .to_string(),
+        )]))
     }
 
-    async fn save_append(&self, params: &SaveParams) -> Result<CallToolResult, McpError> {
-        let vault_paths = self.get_vault_paths();
-        let notes = collect_all_notes(&vault_paths);
+    async fn save_update(&self, params: &SaveParams) -> Result<CallToolResult, McpError> {
+        let notes = self.get_notes();
+
+        let note = match resolve_note_strict(&notes, &self.vault_path, &params.title) {
+            Ok(note) => note,
+            Err(e) => {
+                let mut json = resolve_error_json(&e);
+                json["success"] = serde_json::json!(false);
+                if matches!(e, ResolveError::NotFound { .. }) {
+                    json["suggestion"] =
+                        serde_json::json!("Use strategy='create' to create a new note");
+                }
+                return Ok(CallToolResult::success(vec![Content::text(
+                    json.to_string(),
+                )]));
+            }
+        };
 
-        let found = notes.into_iter().find(|n| {
-            n.name == params.title
-                || n.path.file_stem().map(|s| s.to_string_lossy().to_string())
-                    == Some(params.title.clone())
-        });
+        let old_frontmatter = note.frontmatter.clone().unwrap_or_default();
+        let mut editor = FrontmatterEditor::new(&note.content);
+        let old_body = editor.body().trim_start_matches(['\r', '\n']).to_string();
+
+        let full_content = if params.replace_frontmatter {
+            // Rebuild from parameters, but never silently reset the status
+            let status = params
+                .status
+                .as_deref()
+                .or(old_frontmatter.status())
+                .unwrap_or("active");
+            let body = if params.replace_body {
+                &params.content
+            } else {
+                &old_body
+            };
+            format!("{}{}", self.build_frontmatter(params, status), body)
+        } else {
+            self.merge_frontmatter(&mut editor, params, &old_frontmatter);
+            if params.replace_body {
+                editor.set_body(&params.content);
+            }
+            editor.to_content()
+        };
+
+        let new_frontmatter = Frontmatter::parse(&full_content).unwrap_or_default();
+        let changes = old_frontmatter.diff(&new_frontmatter);
+        let body_changed = params.replace_body && params.content != old_body;
+
+        if full_content != note.content {
+            std::fs::write(&note.path, &full_content).map_err(|e| {
+                McpError::internal_error(format!("Failed to update note: {}", e), None)
+            })?;
+        }
+
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::json!({
+                "success": true,
+                "action": "updated",
+                "path": note.path.to_string_lossy(),
+                "title": params.title,
+                "mode": if params.replace_frontmatter { "replace_frontmatter" } else { "merge" },
+                "changed_fields": changes,
+                "body_changed": body_changed
+            })
+            .to_string(),
+        )]))
+    }
+
+    /// Merge SaveParams into existing frontmatter (only fields that were passed)
+    fn merge_frontmatter(
+        &self,
+        editor: &mut FrontmatterEditor,
+        params: &SaveParams,
+        existing: &Frontmatter,
+    ) {
+        let text = |s: &str| FieldValue::String(s.to_string());
+
+        if let Some(t) = &params.note_type {
+            editor.set("elysium_type", text(t));
+        }
+        if let Some(s) = &params.status {
+            editor.set("elysium_status", text(s));
+        }
+        if let Some(a) = &params.area {
+            editor.set("elysium_area", text(a));
+        }
+        if let Some(g) = &params.gist {
+            if existing.gist() != Some(g.trim()) && editor.set("elysium_gist", text(g.trim())) {
+                editor.set("elysium_gist_source", text("ai"));
+                editor.set(
+                    "elysium_gist_date",
+                    text(&chrono::Local::now().format("%Y-%m-%d").to_string()),
+                );
+            }
+        }
+
+        // Explicit tags replace the list; auto-tags only fill an empty one
+        if params.tags.is_some() || existing.tags().is_empty() {
+            let tags = self.resolve_tags(params);
+            if !tags.is_empty() {
+                editor.set(
+                    "elysium_tags",
+                    FieldValue::List(tags.iter().map(|t| text(t)).collect()),
+                );
+            }
+        }
+
+        if let Some(source) = &params.source {
+            let sources = source
+                .split(',')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .map(text)
+                .collect();
+            editor.set("elysium_source", FieldValue::List(sources));
+        }
+    }
+
+    async fn save_append(&self, params: &SaveParams) -> Result<CallToolResult, McpError> {
+        let notes = self.get_notes();
 
-        match found {
-            Some(note) => {
+        match resolve_note_strict(&notes, &self.vault_path, &params.title) {
+            Ok(note) => {
                 let existing = std::fs::read_to_string(&note.path).map_err(|e| {
                     McpError::internal_error(format!("Failed to read note: {}", e), None)
                 })?;
@@ -794,14 +1738,17 @@ impl VaultService {
                     .to_string(),
                 )]))
             }
-            None => Ok(CallToolResult::success(vec![Content::text(
-                serde_json::json!({
-                    "success": false,
-                    "error": format!("Note not found: {}", params.title),
-                    "suggestion": "Use strategy='create' to create a new note"
-                })
-                .to_string(),
-            )])),
+            Err(e) => {
+                let mut json = resolve_error_json(&e);
+                json["success"] = serde_json::json!(false);
+                if matches!(e, ResolveError::NotFound { .. }) {
+                    json["suggestion"] =
+                        serde_json::json!("Use strategy='create' to create a new note");
+                }
+                Ok(CallToolResult::success(vec![Content::text(
+                    json.to_string(),
+                )]))
+            }
         }
     }
 
@@ -877,7 +1824,7 @@ impl VaultService {
                 "action": "needs_decision",
                 "similar_notes": similar_notes,
                 "suggestion": format!(
-                    "Found {} similar note(s). Options: strategy='create' to create anyway, strategy='append' with title='{}' to add to existing, or strategy='update' to overwrite.",
+                    "Found {} similar note(s). Options: strategy='create' to create anyway, strategy='append' with title='{}' to add to existing, or strategy='update' to merge into it.",
                     high_similarity.len(),
                     high_similarity[0].title
                 )
@@ -901,7 +1848,7 @@ impl VaultService {
             )
         })?;
 
-        let suggestions = matcher
+        let suggestions = lock(&matcher)
             .suggest_tags_hybrid(&params.0.text, params.0.limit)
             .map_err(|e| {
                 McpError::internal_error(format!("Failed to suggest tags: {}", e), None)
@@ -948,6 +1895,7 @@ impl VaultService {
             )
         })?;
 
+        let matcher = lock(&matcher);
         let merge_suggestions = matcher
             .analyze_for_merges(params.0.threshold)
             .map_err(|e| {
@@ -1042,7 +1990,7 @@ impl VaultService {
         params: Parameters<SuggestTagsParams>,
     ) -> Result<CallToolResult, McpError> {
         // Check if advanced search is enabled
-        let config = crate::core::config::Config::load(&self.vault_path);
+        let config = self.cache.config();
         if !config.features.is_advanced_search_ready() {
             return Ok(CallToolResult::success(vec![Content::text(
                 serde_json::json!({
@@ -1055,26 +2003,14 @@ impl VaultService {
         }
 
         // Find the source note
-        let vault_paths = self.get_vault_paths();
-        let notes = collect_all_notes(&vault_paths);
-        let note_name = &params.0.note;
-
-        let source_note = notes.iter().find(|n| {
-            n.name == *note_name
-                || n.path.file_stem().map(|s| s.to_string_lossy().to_string())
-                    == Some(note_name.clone())
-                || n.path.to_string_lossy().contains(note_name)
-        });
-
-        let source_note = match source_note {
-            Some(n) => n,
-            None => {
+        let notes = self.get_notes();
+        let source_note = match resolve_note(&notes, &self.vault_path, &params.0.note) {
+            Ok(n) => n,
+            Err(e) => {
+                let mut json = resolve_error_json(&e);
+                json["success"] = serde_json::json!(false);
                 return Ok(CallToolResult::success(vec![Content::text(
-                    serde_json::json!({
-                        "success": false,
-                        "error": format!("Note '{}' not found", note_name)
-                    })
-                    .to_string(),
+                    json.to_string(),
                 )]));
             }
         };
@@ -1174,13 +2110,13 @@ impl VaultService {
         )]))
     }
 
-    fn build_frontmatter(&self, params: &SaveParams) -> String {
+    fn build_frontmatter(&self, params: &SaveParams, status: &str) -> String {
         let mut fm = String::from("---\n");
 
         if let Some(t) = &params.note_type {
             fm.push_str(&format!("elysium_type: {}\n", t));
         }
-        fm.push_str("elysium_status: active\n");
+        fm.push_str(&format!("elysium_status: {}\n", status));
         if let Some(a) = &params.area {
             fm.push_str(&format!("elysium_area: {}\n", a));
         }
@@ -1267,8 +2203,10 @@ pub async fn run_mcp_server(vault_path: PathBuf) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::frontmatter::Frontmatter;
-    use crate::mcp::params::{AuditParams, GetNoteParams, ListNotesParams, SearchParams};
+    use crate::mcp::params::{
+        AuditParams, GetNoteParams, ListNotesParams, ReindexParams, RenameParams, SearchParams,
+    };
+    use crate::search::chunk_index::CHUNK_INDEX_DIR;
     use crate::search::embedder::{Embedder, HtpEmbedder};
     use crate::search::plugin_index::{
         HnswIndex, IndexMeta, PluginSearchEngine, PLUGIN_INDEX_VERSION,
@@ -1276,7 +2214,7 @@ mod tests {
     use rmcp::handler::server::wrapper::Parameters;
     use rmcp::model::RawContent;
     use serde::Deserialize;
-    use std::collections::HashMap;
+    use std::collections::{BTreeMap, HashMap};
     use std::fs;
     use std::path::{Path, PathBuf};
     use tempfile::tempdir;
@@ -1303,6 +2241,20 @@ mod tests {
             .unwrap_or_default()
     }
 
+    /// Results of a vault_search response
+    fn search_results(result: &CallToolResult) -> Vec<serde_json::Value> {
+        let output: serde_json::Value =
+            serde_json::from_str(&extract_text(result)).expect("search output should be JSON");
+        serde_json::from_value(output["results"].clone())
+            .expect("search output should list results")
+    }
+
+    /// Search parameters with every option at its tool default
+    fn search_params(query: &str) -> SearchParams {
+        serde_json::from_value(serde_json::json!({ "query": query }))
+            .expect("query alone is valid SearchParams")
+    }
+
     #[derive(Deserialize)]
     struct BaselineSuite {
         baselines: Vec<SearchBaseline>,
@@ -1385,10 +2337,7 @@ mod tests {
                 .as_ref()
                 .map(|fm| fm.to_json_map())
                 .unwrap_or_default();
-            let tags = frontmatter
-                .as_ref()
-                .and_then(|fm| fm.get_list("tags"))
-                .cloned();
+            let tags = frontmatter.as_ref().and_then(|fm| fm.get_list("tags"));
 
             ids.push(file_name.clone());
             vectors.push(embedder.embed(&gist).expect("embed gist"));
@@ -1451,10 +2400,12 @@ mod tests {
     async fn smoke_vault_list_notes() {
         let service = VaultService::new(fixture_root());
         let params = ListNotesParams {
+            query: None,
             note_type: None,
             area: None,
             limit: 50,
             fields: Some("standard".to_string()),
+            include_archived: false,
         };
 
         let result = service
@@ -1471,11 +2422,68 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn smoke_vault_get_note() {
+    async fn smoke_vault_list_notes_query() {
         let service = VaultService::new(fixture_root());
-        let params = GetNoteParams {
-            note: "alpha".to_string(),
-            fields: Some("standard".to_string()),
+        let list = |query: &str| ListNotesParams {
+            query: Some(query.to_string()),
+            note_type: None,
+            area: None,
+            limit: 50,
+            fields: None,
+            include_archived: false,
+        };
+        let titles = |result: &CallToolResult| -> Vec<String> {
+            let notes: Vec<serde_json::Value> =
+                serde_json::from_str(&extract_text(result)).expect("list output should be JSON");
+            notes
+                .iter()
+                .map(|n| n["title"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        for (query, expected) in [
+            ("type:project,term sort:title desc", vec!["gamma", "beta"]),
+            ("status:!done -\"small term\"", vec!["alpha"]),
+            ("tag:demo modified:>2000-01-01", vec!["alpha"]),
+            ("\"fixture coverage\"", vec!["gamma"]),
+            ("title:beta", vec!["beta"]),
+            ("-title:alpha sort:title", vec!["beta", "gamma"]),
+            ("smoke NOT gist:tech sort:title", vec!["alpha", "gamma"]),
+            // Search text is a boolean condition: adjacent words are all
+            // required, OR takes either and NOT (...) excludes
+            ("smoke project", vec!["gamma"]),
+            ("work OR tech sort:title", vec!["alpha", "beta"]),
+            ("NOT (term) sort:title", vec!["alpha", "gamma"]),
+            // Exclusions match whole words, outside the frontmatter
+            ("-test -active sort:title", vec!["alpha", "beta", "gamma"]),
+            ("-tests sort:title", vec![]),
+            ("tags:al* OR tags:bet* sort:title", vec!["alpha", "beta"]),
+        ] {
+            let result = service
+                .vault_list_notes(Parameters(list(query)))
+                .await
+                .expect("vault_list_notes should succeed");
+            assert_eq!(titles(&result), expected, "query {}", query);
+        }
+
+        let result = service
+            .vault_list_notes(Parameters(list("modified:>yesterday")))
+            .await
+            .expect("parse errors are tool output");
+        let error: serde_json::Value = serde_json::from_str(&extract_text(&result)).unwrap();
+        assert_eq!(error["position"], 10);
+        assert!(error["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("Invalid query: Invalid date 'yesterday'"));
+    }
+
+    #[tokio::test]
+    async fn smoke_vault_get_note() {
+        let service = VaultService::new(fixture_root());
+        let params = GetNoteParams {
+            note: "alpha".to_string(),
+            fields: Some("standard".to_string()),
         };
 
         let result = service
@@ -1488,6 +2496,80 @@ mod tests {
         assert!(text.contains("# Alpha"));
     }
 
+    #[tokio::test]
+    async fn smoke_note_name_resolution() {
+        let temp = tempdir().expect("create temp dir");
+        copy_fixture_notes(temp.path());
+        let service = VaultService::new(temp.path().to_path_buf());
+        let get_note = |note: &str| GetNoteParams {
+            note: note.to_string(),
+            fields: None,
+        };
+
+        let result = service
+            .vault_get_note(Parameters(get_note("ALPHA")))
+            .await
+            .expect("vault_get_note should succeed");
+        assert!(extract_text(&result).contains("# Alpha"));
+
+        // Every fixture note contains an "a"
+        let result = service
+            .vault_get_note(Parameters(get_note("a")))
+            .await
+            .expect("vault_get_note should succeed");
+        let output: serde_json::Value = serde_json::from_str(&extract_text(&result)).unwrap();
+        assert!(output["error"].as_str().unwrap().contains("ambiguous"));
+        let candidates: Vec<&str> = output["candidates"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["path"].as_str().unwrap())
+            .collect();
+        assert_eq!(candidates, vec!["alpha.md", "beta.md", "gamma.md"]);
+
+        let params: SaveParams = serde_json::from_value(serde_json::json!({
+            "title": "alpah",
+            "content": "",
+            "strategy": "update",
+            "area": "tech",
+            "auto_tag": false
+        }))
+        .expect("save params");
+        let result = service
+            .vault_save(Parameters(params))
+            .await
+            .expect("vault_save should succeed");
+        let output: serde_json::Value = serde_json::from_str(&extract_text(&result)).unwrap();
+        assert_eq!(output["success"], false);
+        assert_eq!(
+            output["error"],
+            "Note 'alpah' not found. Did you mean 'alpha'?"
+        );
+        assert_eq!(output["did_you_mean"][0]["path"], "alpha.md");
+        assert_eq!(output["did_you_mean"][0]["distance"], 1);
+        let content = fs::read_to_string(temp.path().join("alpha.md")).expect("read note");
+        assert!(!content.contains("elysium_area: tech"));
+
+        // Writes never act on a partial match
+        let params: SaveParams = serde_json::from_value(serde_json::json!({
+            "title": "lph",
+            "content": "Appended line.",
+            "strategy": "append",
+            "auto_tag": false
+        }))
+        .expect("save params");
+        let result = service
+            .vault_save(Parameters(params))
+            .await
+            .expect("vault_save should succeed");
+        let output: serde_json::Value = serde_json::from_str(&extract_text(&result)).unwrap();
+        assert_eq!(output["success"], false);
+        assert_eq!(output["did_you_mean"][0]["path"], "alpha.md");
+        assert_eq!(output["did_you_mean"][0]["match"], "partial");
+        let content = fs::read_to_string(temp.path().join("alpha.md")).expect("read note");
+        assert!(!content.contains("Appended line."));
+    }
+
     #[tokio::test]
     async fn smoke_vault_status() {
         let service = VaultService::new(fixture_root());
@@ -1522,6 +2604,880 @@ mod tests {
         assert!(total_checks >= 2);
     }
 
+    #[tokio::test]
+    async fn smoke_vault_audit_folders() {
+        let temp = tempdir().expect("create temp dir");
+        copy_fixture_notes(temp.path());
+        let gamma = temp.path().join("gamma.md");
+        let content = fs::read_to_string(&gamma).expect("read gamma");
+        fs::write(
+            &gamma,
+            content.replace("elysium_status: done", "elysium_status: archived"),
+        )
+        .expect("write gamma");
+
+        let service = VaultService::new(temp.path().to_path_buf());
+        let result = service
+            .vault_audit(Parameters(AuditParams {
+                quick: false,
+                verbose: true,
+            }))
+            .await
+            .expect("vault_audit should succeed");
+        let audit: serde_json::Value =
+            serde_json::from_str(&extract_text(&result)).expect("audit output should be JSON");
+
+        let checks = audit["checks"].as_array().expect("checks array");
+        let folders = checks
+            .iter()
+            .find(|c| c["id"] == "folders")
+            .expect("folders check present");
+        assert_eq!(folders["status"], "fail");
+        assert_eq!(folders["errors"], 1);
+        assert_eq!(folders["error_list"][0]["note"], "gamma");
+        assert_eq!(
+            folders["error_list"][0]["suggested_path"],
+            "Archive/gamma.md"
+        );
+    }
+
+    #[tokio::test]
+    async fn smoke_vault_update_fields() {
+        let temp = tempdir().expect("create temp dir");
+        copy_fixture_notes(temp.path());
+        let service = VaultService::new(temp.path().to_path_buf());
+
+        let params = UpdateFieldsParams {
+            note: "alpha".to_string(),
+            set: Some(BTreeMap::from([(
+                "status".to_string(),
+                serde_json::json!("done"),
+            )])),
+            remove: None,
+            append: Some(BTreeMap::from([(
+                "tags".to_string(),
+                vec!["smoke".to_string()],
+            )])),
+            dry_run: false,
+        };
+
+        let result = service
+            .vault_update_fields(Parameters(params))
+            .await
+            .expect("vault_update_fields should succeed");
+        let output: serde_json::Value =
+            serde_json::from_str(&extract_text(&result)).expect("update output should be JSON");
+        assert_eq!(
+            output["changed"],
+            serde_json::json!(["elysium_status", "elysium_tags"])
+        );
+
+        let content = fs::read_to_string(temp.path().join("alpha.md")).expect("read note");
+        assert!(content.contains("elysium_status: done\n"));
+        assert!(content.contains("elysium_tags: [alpha, demo, smoke]\n"));
+        assert!(content.contains("elysium_gist: Work note gist for smoke tests.\n"));
+        assert!(content.ends_with("# Alpha\n\nLinks to [[beta]].\n"));
+    }
+
+    #[tokio::test]
+    async fn smoke_vault_save_update_merges() {
+        let temp = tempdir().expect("create temp dir");
+        copy_fixture_notes(temp.path());
+        let service = VaultService::new(temp.path().to_path_buf());
+
+        let params: SaveParams = serde_json::from_value(serde_json::json!({
+            "title": "alpha",
+            "content": "",
+            "strategy": "update",
+            "area": "tech",
+            "auto_tag": false,
+            "replace_body": false
+        }))
+        .expect("save params");
+
+        let result = service
+            .vault_save(Parameters(params))
+            .await
+            .expect("vault_save should succeed");
+        let output: serde_json::Value =
+            serde_json::from_str(&extract_text(&result)).expect("save output should be JSON");
+        assert_eq!(output["mode"], "merge");
+        assert_eq!(
+            output["changed_fields"]["updated"],
+            serde_json::json!(["area"])
+        );
+        assert_eq!(output["body_changed"], false);
+
+        let content = fs::read_to_string(temp.path().join("alpha.md")).expect("read note");
+        assert!(content.contains("elysium_area: tech\n"));
+        // Fields not passed are kept as-is
+        assert!(content.contains("elysium_status: active\n"));
+        assert!(content.contains("elysium_tags: [alpha, demo]\n"));
+        assert!(content.ends_with("# Alpha\n\nLinks to [[beta]].\n"));
+    }
+
+    #[tokio::test]
+    async fn smoke_vault_rename() {
+        let temp = tempdir().expect("create temp dir");
+        copy_fixture_notes(temp.path());
+        let service = VaultService::new(temp.path().to_path_buf());
+        let params = |dry_run| RenameParams {
+            note: "beta".to_string(),
+            new_name: Some("Beta Renamed".to_string()),
+            new_folder: Some("Notes".to_string()),
+            dry_run,
+        };
+
+        let result = service
+            .vault_rename(Parameters(params(true)))
+            .await
+            .expect("vault_rename dry run should succeed");
+        let output: serde_json::Value =
+            serde_json::from_str(&extract_text(&result)).expect("rename output should be JSON");
+        assert_eq!(output["to"], "Notes/Beta Renamed.md");
+        assert_eq!(output["edits"][0]["file"], "alpha.md");
+        assert_eq!(output["edits"][0]["line"], 10);
+        assert!(temp.path().join("beta.md").exists());
+
+        service
+            .vault_rename(Parameters(params(false)))
+            .await
+            .expect("vault_rename should succeed");
+        assert!(!temp.path().join("beta.md").exists());
+        assert!(temp.path().join("Notes/Beta Renamed.md").exists());
+        let alpha = fs::read_to_string(temp.path().join("alpha.md")).expect("read alpha");
+        assert!(alpha.contains("Links to [[Beta Renamed]]."));
+    }
+
+    #[tokio::test]
+    async fn smoke_vault_archive() {
+        let temp = tempdir().expect("create temp dir");
+        copy_fixture_notes(temp.path());
+        let service = VaultService::new(temp.path().to_path_buf());
+        let list = |include_archived| ListNotesParams {
+            query: None,
+            note_type: None,
+            area: None,
+            limit: 50,
+            fields: None,
+            include_archived,
+        };
+        let titles = |result: &CallToolResult| -> Vec<String> {
+            let notes: Vec<serde_json::Value> =
+                serde_json::from_str(&extract_text(result)).expect("list output should be JSON");
+            notes
+                .iter()
+                .map(|n| n["title"].as_str().unwrap_or_default().to_string())
+                .collect()
+        };
+
+        let result = service
+            .vault_archive(Parameters(ArchiveParams {
+                note: "beta".to_string(),
+                dry_run: false,
+            }))
+            .await
+            .expect("vault_archive should succeed");
+        let output: serde_json::Value =
+            serde_json::from_str(&extract_text(&result)).expect("archive output should be JSON");
+        assert_eq!(output["to"], "Archive/beta.md");
+
+        let beta = fs::read_to_string(temp.path().join("Archive/beta.md")).expect("read beta");
+        assert!(beta.contains("elysium_status: archived"));
+        assert!(beta.contains("elysium_archived_date: "));
+        let alpha = fs::read_to_string(temp.path().join("alpha.md")).expect("read alpha");
+        assert!(alpha.contains("Links to [[beta]]."));
+
+        let listed = service
+            .vault_list_notes(Parameters(list(false)))
+            .await
+            .expect("vault_list_notes should succeed");
+        assert!(!titles(&listed).contains(&"beta".to_string()));
+        let listed = service
+            .vault_list_notes(Parameters(list(true)))
+            .await
+            .expect("vault_list_notes should succeed");
+        assert!(titles(&listed).contains(&"beta".to_string()));
+
+        service
+            .vault_unarchive(Parameters(UnarchiveParams {
+                note: "beta".to_string(),
+                folder: None,
+                status: None,
+                dry_run: false,
+            }))
+            .await
+            .expect("vault_unarchive should succeed");
+        let beta = fs::read_to_string(temp.path().join("beta.md")).expect("read restored beta");
+        assert!(beta.contains("elysium_status: active"));
+        assert!(!beta.contains("elysium_archived"));
+    }
+
+    #[tokio::test]
+    async fn smoke_vault_link_graph() {
+        let service = VaultService::new(fixture_root());
+        let json = |result: &CallToolResult| -> serde_json::Value {
+            serde_json::from_str(&extract_text(result)).expect("graph output should be JSON")
+        };
+
+        let backlinks = service
+            .vault_backlinks(Parameters(LinksParams {
+                note: "beta".to_string(),
+            }))
+            .await
+            .expect("vault_backlinks should succeed");
+        let backlinks = json(&backlinks);
+        assert_eq!(backlinks["count"], 1);
+        assert_eq!(backlinks["backlinks"][0]["path"], "alpha.md");
+
+        let outlinks = service
+            .vault_outlinks(Parameters(LinksParams {
+                note: "alpha".to_string(),
+            }))
+            .await
+            .expect("vault_outlinks should succeed");
+        assert_eq!(json(&outlinks)["outlinks"][0]["title"], "beta");
+
+        let neighbors = service
+            .vault_neighbors(Parameters(NeighborsParams {
+                note: "beta".to_string(),
+                depth: 2,
+                direction: Some("in".to_string()),
+                limit: 10,
+            }))
+            .await
+            .expect("vault_neighbors should succeed");
+        assert_eq!(json(&neighbors)["neighbors"][0]["distance"], 1);
+
+        let stats = service
+            .vault_graph_stats(Parameters(GraphStatsParams { limit: 5 }))
+            .await
+            .expect("vault_graph_stats should succeed");
+        let stats = json(&stats);
+        assert_eq!(stats["notes"], 3);
+        assert_eq!(stats["links"], 1);
+        assert_eq!(stats["components"]["count"], 2);
+        assert_eq!(stats["dead_ends"]["notes"][0], "beta.md");
+        assert_eq!(stats["isolated"]["notes"][0], "gamma.md");
+    }
+
+    #[tokio::test]
+    async fn smoke_vault_reindex() {
+        let temp = tempdir().expect("create temp dir");
+        copy_fixture_notes(temp.path());
+        let service = VaultService::new(temp.path().to_path_buf());
+
+        let result = service
+            .vault_reindex(Parameters(ReindexParams {
+                embedding_mode: Some("htp".to_string()),
+            }))
+            .await
+            .expect("vault_reindex should succeed");
+
+        let text = extract_text(&result);
+        let stats: serde_json::Value =
+            serde_json::from_str(&text).expect("reindex output should be JSON");
+        assert_eq!(stats["note_count"].as_u64(), Some(3));
+
+        let engine = PluginSearchEngine::load(temp.path()).expect("load rebuilt index");
+        let results = engine.search("work note", 1).expect("search rebuilt index");
+        assert_eq!(results[0].path, "alpha.md");
+    }
+
+    #[tokio::test]
+    async fn smoke_vault_search_body_snippets() {
+        let temp = setup_vault_with_index();
+        let service = VaultService::new(temp.path().to_path_buf());
+
+        let result = service
+            .vault_search(Parameters(SearchParams {
+                search_mode: Some("keyword".to_string()),
+                snippets: true,
+                ..search_params("coverage")
+            }))
+            .await
+            .expect("vault_search should succeed");
+        let results = search_results(&result);
+
+        // "coverage" only appears in the body of gamma
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["path"], "gamma.md");
+        let snippet = results[0]["snippet"].as_str().expect("snippet requested");
+        assert!(snippet.contains("fixture **coverage**"), "{}", snippet);
+    }
+
+    #[tokio::test]
+    async fn smoke_vault_search_explain() {
+        let temp = setup_vault_with_index();
+        let service = VaultService::new(temp.path().to_path_buf());
+
+        let result = service
+            .vault_search(Parameters(SearchParams {
+                search_mode: Some("hybrid".to_string()),
+                explain: true,
+                ..search_params("coverage")
+            }))
+            .await
+            .expect("vault_search should succeed");
+        let results = search_results(&result);
+
+        for r in &results {
+            let explain = &r["explain"];
+            assert!(
+                explain["semantic"]["rank"].as_u64().is_some(),
+                "{}",
+                explain
+            );
+            assert_eq!(explain["fusion"]["method"], "rrf");
+            assert_eq!(explain["fusion"]["k"], 60);
+        }
+        // Only gamma mentions "coverage" (in its body)
+        let gamma = results
+            .iter()
+            .find(|r| r["path"] == "gamma.md")
+            .expect("gamma is a keyword hit");
+        let bm25 = &gamma["explain"]["bm25"];
+        assert_eq!(bm25["rank"], 1);
+        assert_eq!(bm25["matched_fields"][0]["field"], "body");
+        assert_eq!(bm25["matched_fields"][0]["boost"], 1.0);
+        let rrf = &gamma["explain"]["fusion"];
+        let total = rrf["semantic_contribution"].as_f64().unwrap()
+            + rrf["bm25_contribution"].as_f64().unwrap();
+        assert!((total - gamma["score"].as_f64().unwrap()).abs() < 1e-4);
+        for r in results.iter().filter(|r| r["path"] != "gamma.md") {
+            assert!(r["explain"]["bm25"].is_null());
+            assert_eq!(r["explain"]["fusion"]["bm25_contribution"], 0.0);
+        }
+    }
+
+    #[tokio::test]
+    async fn smoke_vault_search_fusion() {
+        let temp = setup_vault_with_index();
+        let service = VaultService::new(temp.path().to_path_buf());
+        let search = |fusion: &str| SearchParams {
+            search_mode: Some("hybrid".to_string()),
+            fusion: Some(fusion.to_string()),
+            explain: true,
+            ..search_params("gist for smoke tests")
+        };
+
+        for method in ["min_max", "z_score", "dbsf"] {
+            let result = service
+                .vault_search(Parameters(search(method)))
+                .await
+                .expect("vault_search should succeed");
+            let results = search_results(&result);
+            assert_eq!(results.len(), 3, "fusion {}", method);
+            let fusion = &results[0]["explain"]["fusion"];
+            assert_eq!(fusion["method"], method);
+            assert!(fusion["k"].is_null());
+        }
+
+        let result = service
+            .vault_search(Parameters(search("borda")))
+            .await
+            .expect("vault_search should succeed");
+        let error: serde_json::Value = serde_json::from_str(&extract_text(&result)).unwrap();
+        assert!(error["error"]
+            .as_str()
+            .unwrap()
+            .contains("Unknown fusion method 'borda'"));
+    }
+
+    #[tokio::test]
+    async fn smoke_diversity_reranking() {
+        use crate::core::config::PLUGIN_CONFIG_PATH;
+
+        let temp = tempdir().expect("create temp dir");
+        copy_fixture_notes(temp.path());
+        // A near-duplicate of alpha that plain relevance ranks right after it
+        fs::copy(
+            temp.path().join("alpha.md"),
+            temp.path().join("alpha copy.md"),
+        )
+        .expect("copy alpha");
+        let config_path = temp.path().join(PLUGIN_CONFIG_PATH);
+        fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+        fs::write(&config_path, r#"{"schema": {"gist": {"enabled": true}}}"#).unwrap();
+        let service = VaultService::new(temp.path().to_path_buf());
+        service
+            .vault_reindex(Parameters(ReindexParams {
+                embedding_mode: Some("htp".to_string()),
+            }))
+            .await
+            .expect("vault_reindex should succeed");
+
+        let search = |diversity: Option<f32>| SearchParams {
+            limit: 2,
+            search_mode: Some("semantic".to_string()),
+            diversity,
+            ..search_params("work note gist for smoke tests")
+        };
+        let mut rankings = Vec::new();
+        for diversity in [None, Some(1.0)] {
+            let result = service
+                .vault_search(Parameters(search(diversity)))
+                .await
+                .expect("vault_search should succeed");
+            let results = search_results(&result);
+            let paths: Vec<String> = results
+                .iter()
+                .map(|r| r["path"].as_str().unwrap().to_string())
+                .collect();
+            assert_eq!(paths.len(), 2);
+            rankings.push(paths);
+        }
+        let (plain, diverse) = (&rankings[0], &rankings[1]);
+        let mut duplicates = plain.clone();
+        duplicates.sort();
+        assert_eq!(duplicates, vec!["alpha copy.md", "alpha.md"]);
+        // MMR keeps the most relevant note first and pushes its duplicate
+        // below a different note
+        assert_eq!(diverse[0], plain[0]);
+        assert!(!diverse[1].starts_with("alpha"), "{:?}", diverse);
+
+        let result = service
+            .vault_related(Parameters(RelatedParams {
+                note: "alpha".to_string(),
+                limit: 5,
+                boost_type: false,
+                boost_area: false,
+                boost_links: false,
+                include_archived: false,
+                diversity: Some(0.5),
+            }))
+            .await
+            .expect("vault_related should succeed");
+        let related: Vec<serde_json::Value> =
+            serde_json::from_str(&extract_text(&result)).expect("related output should be JSON");
+        let mut paths: Vec<&str> = related
+            .iter()
+            .map(|r| r["path"].as_str().unwrap())
+            .collect();
+        paths.sort();
+        assert_eq!(paths, vec!["alpha copy.md", "beta.md", "gamma.md"]);
+    }
+
+    #[tokio::test]
+    async fn smoke_vault_related_boosts() {
+        let temp = setup_vault_with_index();
+        let service = VaultService::new(temp.path().to_path_buf());
+        let related = |boost_links: bool, boost_area: bool| RelatedParams {
+            note: "beta".to_string(),
+            limit: 5,
+            boost_type: false,
+            boost_area,
+            boost_links,
+            include_archived: false,
+            diversity: None,
+        };
+        let scores = |result: &CallToolResult| -> Vec<(String, f64)> {
+            let related: Vec<serde_json::Value> =
+                serde_json::from_str(&extract_text(result)).expect("related output should be JSON");
+            related
+                .iter()
+                .map(|r| {
+                    (
+                        r["path"].as_str().unwrap().to_string(),
+                        r["score"].as_f64().unwrap(),
+                    )
+                })
+                .collect()
+        };
+
+        let plain = service
+            .vault_related(Parameters(related(false, false)))
+            .await
+            .expect("vault_related should succeed");
+        let plain = scores(&plain);
+        let boosted = service
+            .vault_related(Parameters(related(true, false)))
+            .await
+            .expect("vault_related should succeed");
+        let boosted = scores(&boosted);
+
+        // alpha links to beta (proximity 0.5): boosted above gamma, which shares nothing
+        assert_eq!(boosted[0].0, "alpha.md");
+        let similarity = |path: &str| plain.iter().find(|(p, _)| p == path).unwrap().1;
+        assert!((boosted[0].1 - (0.7 * similarity("alpha.md") + 0.075)).abs() < 1e-4);
+        assert!((boosted[1].1 - 0.7 * similarity("gamma.md")).abs() < 1e-4);
+
+        // No other note is in beta's area: area boost only rescales
+        let by_area = service
+            .vault_related(Parameters(related(false, true)))
+            .await
+            .expect("vault_related should succeed");
+        for (path, score) in scores(&by_area) {
+            assert!((score - 0.7 * similarity(&path)).abs() < 1e-4);
+        }
+    }
+
+    #[tokio::test]
+    async fn smoke_vault_search_eval() {
+        let temp = setup_vault_with_index();
+        let service = VaultService::new(temp.path().to_path_buf());
+        let eval_set = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../tests/fixtures/golden/search_eval.json");
+
+        let result = service
+            .vault_search_eval(Parameters(SearchEvalParams {
+                path: Some(eval_set.to_string_lossy().to_string()),
+                modes: vec!["keyword".to_string(), "hybrid".to_string()],
+                fusions: vec!["rrf".to_string(), "dbsf".to_string()],
+                configs: Vec::new(),
+                k: None,
+                per_query: true,
+                include_archived: false,
+            }))
+            .await
+            .expect("vault_search_eval should succeed");
+        let report: serde_json::Value =
+            serde_json::from_str(&extract_text(&result)).expect("eval output should be JSON");
+
+        assert_eq!(report["k"], 3);
+        assert_eq!(report["queries"], 3);
+        let runs = report["runs"].as_array().unwrap();
+        let names: Vec<&str> = runs.iter().map(|r| r["name"].as_str().unwrap()).collect();
+        assert_eq!(names, vec!["keyword", "hybrid/rrf", "hybrid/dbsf"]);
+        assert!(runs[0].get("fusion").is_none());
+        assert_eq!(runs[2]["fusion"]["method"], "dbsf");
+        for run in runs {
+            for metric in ["mrr", "ndcg", "recall"] {
+                let value = run[metric].as_f64().unwrap();
+                assert!((0.0..=1.0).contains(&value), "{} {}", metric, value);
+            }
+            assert_eq!(run["queries"].as_array().unwrap().len(), 3);
+        }
+        // Each title query finds its note first by keyword
+        assert_eq!(runs[0]["mrr"], 1.0);
+
+        // One hybrid run per ranking config
+        let configs = serde_json::from_value(serde_json::json!([
+            { "name": "keyword-heavy", "bm25Weight": 0.9, "semanticWeight": 0.1, "rrfK": 10 },
+            { "fusion": "min_max" }
+        ]))
+        .unwrap();
+        let result = service
+            .vault_search_eval(Parameters(SearchEvalParams {
+                path: Some(eval_set.to_string_lossy().to_string()),
+                modes: vec!["hybrid".to_string()],
+                fusions: Vec::new(),
+                configs,
+                k: None,
+                per_query: false,
+                include_archived: false,
+            }))
+            .await
+            .expect("vault_search_eval should succeed");
+        let report: serde_json::Value = serde_json::from_str(&extract_text(&result)).unwrap();
+        let runs = report["runs"].as_array().unwrap();
+        let names: Vec<&str> = runs.iter().map(|r| r["name"].as_str().unwrap()).collect();
+        assert_eq!(
+            names,
+            vec!["hybrid/rrf/keyword-heavy", "hybrid/min_max/config2"]
+        );
+        assert_eq!(runs[0]["fusion"]["bm25_weight"], 0.9);
+        assert_eq!(runs[0]["fusion"]["rrf_k"], 10);
+        assert_eq!(runs[1]["fusion"]["rrf_k"], 60);
+
+        let result = service
+            .vault_search_eval(Parameters(SearchEvalParams {
+                path: Some(eval_set.to_string_lossy().to_string()),
+                modes: vec!["fuzzy".to_string()],
+                fusions: Vec::new(),
+                configs: Vec::new(),
+                k: None,
+                per_query: false,
+                include_archived: false,
+            }))
+            .await
+            .expect("vault_search_eval should succeed");
+        assert!(extract_text(&result).contains("Unknown search mode 'fuzzy'"));
+
+        let result = service
+            .vault_search_eval(Parameters(SearchEvalParams {
+                path: None,
+                modes: Vec::new(),
+                fusions: Vec::new(),
+                configs: Vec::new(),
+                k: None,
+                per_query: false,
+                include_archived: false,
+            }))
+            .await
+            .expect("vault_search_eval should succeed");
+        assert!(extract_text(&result).contains("Failed to read judged query set"));
+    }
+
+    #[tokio::test]
+    async fn smoke_vault_search_expansion() {
+        use crate::core::config::{PLUGIN_DATA_DIR, TAG_DB_FILE};
+
+        let temp = setup_vault_with_index();
+        let data_dir = temp.path().join(PLUGIN_DATA_DIR);
+        let db = TagDatabase::open(&data_dir.join(TAG_DB_FILE)).expect("open tag DB");
+        db.add_tag_with_embedding("beta", "Beta tag", &[0.0; 4])
+            .expect("add tag");
+        db.add_alias("beta", "bravo").expect("add alias");
+        drop(db);
+        fs::write(
+            data_dir.join(expansion::SYNONYMS_FILE),
+            "# test\ndelta => alpha\n",
+        )
+        .unwrap();
+        let service = VaultService::new(temp.path().to_path_buf());
+
+        let search = |expand: Option<bool>| SearchParams {
+            search_mode: Some("keyword".to_string()),
+            expand,
+            ..search_params("bravo delta")
+        };
+
+        let result = service
+            .vault_search(Parameters(search(None)))
+            .await
+            .expect("vault_search should succeed");
+        let output: serde_json::Value = serde_json::from_str(&extract_text(&result)).unwrap();
+        assert_eq!(output, serde_json::json!({ "results": [] }));
+        assert_eq!(result.content.len(), 1);
+
+        let result = service
+            .vault_search(Parameters(search(Some(true))))
+            .await
+            .expect("vault_search should succeed");
+        let report: serde_json::Value =
+            serde_json::from_str(&extract_text(&result)).expect("search output should be JSON");
+        let mut paths: Vec<&str> = report["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["path"].as_str().unwrap())
+            .collect();
+        paths.sort();
+        assert_eq!(paths, vec!["alpha.md", "beta.md"]);
+
+        assert!(report.get("warning").is_none());
+        assert_eq!(report["expansion"]["weight"], 0.5);
+        let terms = report["expansion"]["terms"].as_array().unwrap();
+        assert_eq!(terms.len(), 2);
+        assert_eq!(terms[0]["term"], "bravo");
+        assert_eq!(terms[0]["expansions"], serde_json::json!(["beta"]));
+        assert_eq!(terms[0]["source"], "tag_alias");
+        assert_eq!(terms[1]["term"], "delta");
+        assert_eq!(terms[1]["source"], "synonym");
+    }
+
+    #[tokio::test]
+    async fn smoke_vault_search_keyword_syntax() {
+        let temp = setup_vault_with_index();
+        let service = VaultService::new(temp.path().to_path_buf());
+
+        let search = |query: &str, lenient: Option<bool>| SearchParams {
+            search_mode: Some("keyword".to_string()),
+            lenient,
+            ..search_params(query)
+        };
+        let paths = |results: &serde_json::Value| -> Vec<String> {
+            let mut paths: Vec<String> = results
+                .as_array()
+                .expect("results should be a list")
+                .iter()
+                .map(|r| r["path"].as_str().unwrap().to_string())
+                .collect();
+            paths.sort();
+            paths
+        };
+        let output = |result: &CallToolResult| -> serde_json::Value {
+            serde_json::from_str(&extract_text(result)).expect("search output should be JSON")
+        };
+
+        let result = service
+            .vault_search(Parameters(search(
+                r#""smoke tests" AND (work OR tech) -title:beta"#,
+                None,
+            )))
+            .await
+            .expect("vault_search should succeed");
+        assert_eq!(paths(&output(&result)["results"]), vec!["alpha.md"]);
+
+        let result = service
+            .vault_search(Parameters(search("proj* status:done", None)))
+            .await
+            .expect("vault_search should succeed");
+        assert_eq!(paths(&output(&result)["results"]), vec!["gamma.md"]);
+
+        // Wildcard tags search tag text instead of filtering
+        let result = service
+            .vault_search(Parameters(search("tags:dem*", None)))
+            .await
+            .expect("vault_search should succeed");
+        assert_eq!(paths(&output(&result)["results"]), vec!["alpha.md"]);
+
+        // A phrase in an OR group is optional
+        let result = service
+            .vault_search(Parameters(search(
+                r#"(learning OR "no such phrase")"#,
+                None,
+            )))
+            .await
+            .expect("vault_search should succeed");
+        assert_eq!(paths(&output(&result)["results"]), vec!["gamma.md"]);
+
+        // Positions refer to the query as written, filters included
+        let result = service
+            .vault_search(Parameters(search("type:note gist (smoke", None)))
+            .await
+            .expect("vault_search should succeed");
+        let error: serde_json::Value = serde_json::from_str(&extract_text(&result)).unwrap();
+        assert_eq!(error["position"], 15);
+        assert!(error["error"].as_str().unwrap().contains("Unclosed ("));
+
+        // Lenient: searched as plain words, the error becomes a warning
+        let result = service
+            .vault_search(Parameters(search("type:note gist (smoke", Some(true))))
+            .await
+            .expect("vault_search should succeed");
+        let output = output(&result);
+        assert_eq!(paths(&output["results"]), vec!["alpha.md"]);
+        assert_eq!(output["warning"]["position"], 15);
+        assert!(output["warning"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("Unclosed ("));
+    }
+
+    #[tokio::test]
+    async fn smoke_vault_search_filters() {
+        let temp = setup_vault_with_index();
+        let service = VaultService::new(temp.path().to_path_buf());
+
+        type Configure = fn(&mut SearchParams);
+        let search = |mode: &str, configure: Configure| {
+            let mut params = SearchParams {
+                search_mode: Some(mode.to_string()),
+                ..search_params("gist for smoke tests")
+            };
+            configure(&mut params);
+            params
+        };
+
+        for mode in ["semantic", "keyword", "hybrid"] {
+            let cases: [(Configure, Vec<&str>); 4] = [
+                (|p| p.tags = vec!["demo".to_string()], vec!["alpha.md"]),
+                (|p| p.status = Some("DONE".to_string()), vec!["gamma.md"]),
+                (
+                    |p| {
+                        p.where_fields
+                            .insert("elysium_area".to_string(), "tech".to_string());
+                    },
+                    vec!["beta.md"],
+                ),
+                (|p| p.folder = Some("Projects".to_string()), vec![]),
+            ];
+            for (configure, expected) in cases {
+                let result = service
+                    .vault_search(Parameters(search(mode, configure)))
+                    .await
+                    .expect("vault_search should succeed");
+                let results = search_results(&result);
+                let paths: Vec<&str> = results
+                    .iter()
+                    .map(|r| r["path"].as_str().unwrap())
+                    .collect();
+                assert_eq!(paths, expected, "mode {}", mode);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn smoke_vault_search_query_syntax() {
+        let temp = setup_vault_with_index();
+        let service = VaultService::new(temp.path().to_path_buf());
+        let search = |query: &str| SearchParams {
+            search_mode: Some("hybrid".to_string()),
+            ..search_params(query)
+        };
+        let paths = |result: &CallToolResult| -> Vec<String> {
+            let results = search_results(result);
+            results
+                .iter()
+                .map(|r| r["path"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        for (query, expected) in [
+            (
+                "smoke tests type:note,term sort:path desc",
+                vec!["beta.md", "alpha.md"],
+            ),
+            (
+                "smoke tests -\"small term\" area:!learning",
+                vec!["alpha.md"],
+            ),
+            ("smoke tests created:<2000-01-01", vec![]),
+        ] {
+            let result = service
+                .vault_search(Parameters(search(query)))
+                .await
+                .expect("vault_search should succeed");
+            assert_eq!(paths(&result), expected, "query {}", query);
+        }
+
+        for query in ["gist \"unterminated", "type:project"] {
+            let result = service
+                .vault_search(Parameters(search(query)))
+                .await
+                .expect("query errors are tool output");
+            let error: serde_json::Value = serde_json::from_str(&extract_text(&result)).unwrap();
+            assert!(error["error"].is_string(), "query {}", query);
+        }
+    }
+
+    #[tokio::test]
+    async fn smoke_vault_search_chunk_heading() {
+        let temp = setup_vault_with_index();
+        let service = VaultService::new(temp.path().to_path_buf());
+
+        let result = service
+            .vault_search(Parameters(SearchParams {
+                limit: 1,
+                search_mode: Some("semantic".to_string()),
+                ..search_params("project note for fixture coverage")
+            }))
+            .await
+            .expect("vault_search should succeed");
+        let results = search_results(&result);
+
+        // Matched through the body passage under "# Gamma"
+        assert_eq!(results[0]["path"], "gamma.md");
+        assert_eq!(results[0]["heading"], "Gamma");
+        assert!(temp
+            .path()
+            .join(CHUNK_INDEX_DIR)
+            .join("chunks.bin")
+            .exists());
+    }
+
+    #[tokio::test]
+    async fn smoke_vault_search_gist_match_has_no_heading() {
+        let temp = setup_vault_with_index();
+        let service = VaultService::new(temp.path().to_path_buf());
+
+        let result = service
+            .vault_search(Parameters(SearchParams {
+                limit: 3,
+                search_mode: Some("semantic".to_string()),
+                ..search_params("work note gist for smoke tests alpha")
+            }))
+            .await
+            .expect("vault_search should succeed");
+        let results = search_results(&result);
+
+        // The gist outscores the "# Alpha" passage, so no heading is reported
+        assert_eq!(results[0]["path"], "alpha.md");
+        assert!(results[0].get("heading").is_none());
+    }
+
     #[tokio::test]
     async fn smoke_vault_search_golden() {
         let temp = setup_vault_with_index();
@@ -1549,21 +3505,16 @@ mod tests {
 
             for query in baseline_case.queries {
                 let params = SearchParams {
-                    query: query.query.clone(),
                     limit,
-                    note_type: None,
-                    area: None,
-                    fields: Some("default".to_string()),
                     search_mode: Some(baseline_case.mode.clone()),
+                    ..search_params(&query.query)
                 };
 
                 let result = service
                     .vault_search(Parameters(params))
                     .await
                     .expect("vault_search should succeed");
-                let text = extract_text(&result);
-                let results: Vec<serde_json::Value> =
-                    serde_json::from_str(&text).expect("search output should be JSON");
+                let results = search_results(&result);
 
                 let top_slice = results.iter().take(max_rank);
                 let top_paths: Vec<String> = top_slice
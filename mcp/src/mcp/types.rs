@@ -22,6 +22,9 @@ pub struct AuditCheckJson {
 pub struct AuditErrorJson {
     pub note: String,
     pub message: String,
+    /// Vault-relative path the note should be moved to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_path: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
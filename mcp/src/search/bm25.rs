@@ -4,13 +4,34 @@
 //! Complements semantic search for hybrid search functionality.
 
 use anyhow::{Context, Result};
-use std::path::Path;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
 use tantivy::collector::TopDocs;
-use tantivy::query::QueryParser;
-use tantivy::schema::{Schema, Value, STORED, STRING, TEXT};
-use tantivy::{doc, Index, IndexReader, IndexWriter, ReloadPolicy};
-
+use tantivy::query::{
+    BooleanQuery, BoostQuery, ConstScoreQuery, EmptyQuery, Occur, PhrasePrefixQuery, PhraseQuery,
+    Query, RegexQuery, TermQuery, TermSetQuery,
+};
+use tantivy::schema::{
+    Field, IndexRecordOption, Schema, TextFieldIndexing, TextOptions, Value, STORED, STRING,
+};
+use tantivy::{
+    doc, Index, IndexReader, IndexWriter, ReloadPolicy, Snippet, SnippetGenerator, Term,
+};
+
+use super::expansion::Expansion;
+use super::filter::{
+    field_term, folder_ancestors, split_list, tag_ancestors, Condition, SearchFilter,
+};
+use super::keyword::{KeywordField, KeywordNode, KeywordQuery};
 use super::plugin_index::{NoteRecord, PluginIndexReader};
+use super::tokenizer::{self, TokenizerKind};
+use crate::core::config::RankingConfig;
+use crate::core::frontmatter::FrontmatterEditor;
+
+/// Maximum snippet length in characters
+const SNIPPET_MAX_CHARS: usize = 160;
 
 // ============================================================================
 // Configuration
@@ -25,6 +46,10 @@ pub struct Bm25Config {
     pub gist_boost: f32,
     /// Boost weight for tags field (default: 1.5)
     pub tags_boost: f32,
+    /// Boost weight for note body (default: 1.0)
+    pub body_boost: f32,
+    /// Text analyzer for title, gist, tags and body (default: tantivy's)
+    pub tokenizer: TokenizerKind,
 }
 
 impl Default for Bm25Config {
@@ -33,10 +58,114 @@ impl Default for Bm25Config {
             title_boost: 3.0,
             gist_boost: 2.0,
             tags_boost: 1.5,
+            body_boost: 1.0,
+            tokenizer: TokenizerKind::Default,
+        }
+    }
+}
+
+impl Bm25Config {
+    /// Defaults overridden by the vault config `search.boosts` and
+    /// `search.tokenizer`
+    ///
+    /// An unknown tokenizer is reported and the default is used.
+    pub fn from_ranking(ranking: &RankingConfig) -> Self {
+        let defaults = Self::default();
+        let boosts = &ranking.boosts;
+        let tokenizer = match ranking.tokenizer.as_deref() {
+            None => defaults.tokenizer,
+            Some(name) => TokenizerKind::parse(name).unwrap_or_else(|| {
+                eprintln!(
+                    "Warning: Unknown search.tokenizer '{}' in config (expected {}). Using default.",
+                    name,
+                    TokenizerKind::NAMES
+                );
+                TokenizerKind::Default
+            }),
+        };
+        Self {
+            title_boost: boosts.title.unwrap_or(defaults.title_boost),
+            gist_boost: boosts.gist.unwrap_or(defaults.gist_boost),
+            tags_boost: boosts.tags.unwrap_or(defaults.tags_boost),
+            body_boost: boosts.body.unwrap_or(defaults.body_boost),
+            tokenizer,
         }
     }
 }
 
+/// A field that matched a query (see `Bm25Index::matched_fields`)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldMatch {
+    pub field: String,
+    /// Configured boost for the field
+    pub boost: f32,
+    /// Boosted BM25 score of the query on this field alone
+    pub score: f32,
+}
+
+// ============================================================================
+// Persistent State
+// ============================================================================
+
+/// Version of the tantivy schema and tokenization
+///
+/// Bump whenever indexed fields or analyzers change: a persisted index with
+/// a different version is rebuilt from scratch.
+pub const BM25_SCHEMA_VERSION: u32 = 3;
+
+/// Sync state stored next to the tantivy files
+const STATE_FILE: &str = "elysium_state.json";
+
+/// What was indexed: plugin export timestamp and per-note mtimes
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct IndexState {
+    schema_version: u32,
+    /// Text analyzer (`TokenizerKind::name`)
+    #[serde(default = "default_tokenizer")]
+    tokenizer: String,
+    exported_at: u64,
+    notes: HashMap<String, u64>,
+}
+
+impl IndexState {
+    fn read(index_dir: &Path) -> Option<Self> {
+        let content = fs::read_to_string(index_dir.join(STATE_FILE)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    fn write(
+        index_dir: &Path,
+        notes: &[NoteRecord],
+        exported_at: u64,
+        tokenizer: TokenizerKind,
+    ) -> Result<()> {
+        let state = Self {
+            schema_version: BM25_SCHEMA_VERSION,
+            tokenizer: tokenizer.name().to_string(),
+            exported_at,
+            notes: notes.iter().map(|n| (n.path.clone(), n.mtime)).collect(),
+        };
+        let tmp = index_dir.join(format!("{}.tmp", STATE_FILE));
+        fs::write(&tmp, serde_json::to_string(&state)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, index_dir.join(STATE_FILE)).context("Failed to save BM25 index state")
+    }
+}
+
+fn default_tokenizer() -> String {
+    TokenizerKind::Default.name().to_string()
+}
+
+/// Result of opening a persisted index
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Bm25SyncStats {
+    /// Index was rebuilt from scratch
+    pub rebuilt: bool,
+    pub added: usize,
+    pub updated: usize,
+    pub removed: usize,
+}
+
 // ============================================================================
 // BM25 Index
 // ============================================================================
@@ -49,7 +178,14 @@ pub struct Bm25Index {
     title_field: tantivy::schema::Field,
     gist_field: tantivy::schema::Field,
     tags_field: tantivy::schema::Field,
+    body_field: tantivy::schema::Field,
     path_field: tantivy::schema::Field,
+    // Filter fields (see `search::filter`)
+    field_field: tantivy::schema::Field,
+    tag_field: tantivy::schema::Field,
+    folder_field: tantivy::schema::Field,
+    /// Vault root for reading note bodies (None: bodies are not indexed)
+    vault_root: Option<PathBuf>,
     // Configuration
     config: Bm25Config,
 }
@@ -58,14 +194,20 @@ impl Bm25Index {
     /// Build index from note records
     ///
     /// Creates a new index at the specified directory from the given notes.
+    /// Only metadata (title, gist, tags) is indexed; see
+    /// `build_from_notes_with_config` to include note bodies.
     pub fn build_from_notes(notes: &[NoteRecord], index_dir: &Path) -> Result<Self> {
-        Self::build_from_notes_with_config(notes, index_dir, Bm25Config::default())
+        Self::build_from_notes_with_config(notes, index_dir, None, Bm25Config::default())
     }
 
     /// Build index from note records with custom configuration
+    ///
+    /// With `vault_root`, each note's Markdown body (without frontmatter) is
+    /// read from disk and indexed as the `body` field.
     pub fn build_from_notes_with_config(
         notes: &[NoteRecord],
         index_dir: &Path,
+        vault_root: Option<&Path>,
         config: Bm25Config,
     ) -> Result<Self> {
         // Create index directory
@@ -74,10 +216,10 @@ impl Bm25Index {
         })?;
 
         // Build schema
-        let (schema, title_field, gist_field, tags_field, path_field) = Self::build_schema();
+        let schema = Self::build_schema(config.tokenizer);
 
         // Create or open index
-        let index = Index::create_in_dir(index_dir, schema.clone())
+        let index = Index::create_in_dir(index_dir, schema)
             .or_else(|_| {
                 // If index exists, open and clear it
                 let index = Index::open_in_dir(index_dir)?;
@@ -85,44 +227,67 @@ impl Bm25Index {
             })
             .with_context(|| format!("Failed to create index at {}", index_dir.display()))?;
 
-        // Index all notes
-        let mut writer: IndexWriter = index
-            .writer(50_000_000) // 50MB heap
-            .context("Failed to create index writer")?;
+        Self::fill(index, notes, vault_root, config)
+    }
 
-        // Clear existing documents
-        writer.delete_all_documents()?;
+    /// Build an in-memory index (used when the on-disk index is locked)
+    pub fn build_in_memory(
+        notes: &[NoteRecord],
+        vault_root: Option<&Path>,
+        config: Bm25Config,
+    ) -> Result<Self> {
+        let schema = Self::build_schema(config.tokenizer);
+        Self::fill(Index::create_in_ram(schema), notes, vault_root, config)
+    }
 
-        for note in notes {
-            let title = Self::extract_title(&note.path);
-            let tags_text = note.tags.as_ref().map(|t| t.join(" ")).unwrap_or_default();
-
-            writer.add_document(doc!(
-                title_field => title,
-                gist_field => note.gist.as_str(),
-                tags_field => tags_text,
-                path_field => note.path.as_str(),
-            ))?;
+    /// Open the persisted index and bring it up to date with `notes`
+    ///
+    /// - Same `exported_at` as the last sync: the index is reopened as is.
+    /// - Different `exported_at`: only notes whose `mtime` changed (or that
+    ///   were added/removed) are re-indexed.
+    /// - Missing state, different `BM25_SCHEMA_VERSION` or a different
+    ///   tokenizer: full rebuild.
+    pub fn open_or_update(
+        notes: &[NoteRecord],
+        index_dir: &Path,
+        vault_root: Option<&Path>,
+        exported_at: u64,
+        config: Bm25Config,
+    ) -> Result<(Self, Bm25SyncStats)> {
+        if let Some(state) = IndexState::read(index_dir) {
+            if state.schema_version == BM25_SCHEMA_VERSION
+                && state.tokenizer == config.tokenizer.name()
+            {
+                if let Ok(mut index) = Self::load_with_config(index_dir, config.clone()) {
+                    index.vault_root = vault_root.map(Path::to_path_buf);
+                    if state.exported_at == exported_at {
+                        return Ok((index, Bm25SyncStats::default()));
+                    }
+                    let stats = index.apply_changes(notes, &state.notes)?;
+                    IndexState::write(index_dir, notes, exported_at, config.tokenizer)?;
+                    return Ok((index, stats));
+                }
+            }
         }
 
-        writer.commit().context("Failed to commit index")?;
-
-        // Create reader
-        let reader = index
-            .reader_builder()
-            .reload_policy(ReloadPolicy::OnCommitWithDelay)
-            .try_into()
-            .context("Failed to create index reader")?;
+        // Stale schema or unreadable index: start over
+        if index_dir.exists() {
+            fs::remove_dir_all(index_dir).with_context(|| {
+                format!("Failed to remove stale index: {}", index_dir.display())
+            })?;
+        }
+        let tokenizer = config.tokenizer;
+        let index = Self::build_from_notes_with_config(notes, index_dir, vault_root, config)?;
+        IndexState::write(index_dir, notes, exported_at, tokenizer)?;
 
-        Ok(Self {
+        Ok((
             index,
-            reader,
-            title_field,
-            gist_field,
-            tags_field,
-            path_field,
-            config,
-        })
+            Bm25SyncStats {
+                rebuilt: true,
+                added: notes.len(),
+                ..Default::default()
+            },
+        ))
     }
 
     /// Load existing index from directory
@@ -134,7 +299,11 @@ impl Bm25Index {
     pub fn load_with_config(index_dir: &Path, config: Bm25Config) -> Result<Self> {
         let index = Index::open_in_dir(index_dir)
             .with_context(|| format!("Failed to open index at {}", index_dir.display()))?;
+        Self::from_index(index, config)
+    }
 
+    fn from_index(index: Index, config: Bm25Config) -> Result<Self> {
+        tokenizer::register(&index);
         let schema = index.schema();
 
         let title_field = schema
@@ -146,9 +315,21 @@ impl Bm25Index {
         let tags_field = schema
             .get_field("tags")
             .context("Schema missing 'tags' field")?;
+        let body_field = schema
+            .get_field("body")
+            .context("Schema missing 'body' field")?;
         let path_field = schema
             .get_field("path")
             .context("Schema missing 'path' field")?;
+        let field_field = schema
+            .get_field("field")
+            .context("Schema missing 'field' field")?;
+        let tag_field = schema
+            .get_field("tag")
+            .context("Schema missing 'tag' field")?;
+        let folder_field = schema
+            .get_field("folder")
+            .context("Schema missing 'folder' field")?;
 
         let reader = index
             .reader_builder()
@@ -162,11 +343,130 @@ impl Bm25Index {
             title_field,
             gist_field,
             tags_field,
+            body_field,
             path_field,
+            field_field,
+            tag_field,
+            folder_field,
+            vault_root: None,
             config,
         })
     }
 
+    /// Replace all documents of `index` with `notes`
+    fn fill(
+        index: Index,
+        notes: &[NoteRecord],
+        vault_root: Option<&Path>,
+        config: Bm25Config,
+    ) -> Result<Self> {
+        let mut bm25 = Self::from_index(index, config)?;
+        bm25.vault_root = vault_root.map(Path::to_path_buf);
+
+        let mut writer: IndexWriter = bm25
+            .index
+            .writer(50_000_000) // 50MB heap
+            .context("Failed to create index writer")?;
+
+        // Clear existing documents
+        writer.delete_all_documents()?;
+
+        for note in notes {
+            bm25.add_note(&writer, note)?;
+        }
+
+        writer.commit().context("Failed to commit index")?;
+        bm25.reader
+            .reload()
+            .context("Failed to reload index reader")?;
+
+        Ok(bm25)
+    }
+
+    /// Re-index notes that were added, changed (mtime) or removed
+    fn apply_changes(
+        &self,
+        notes: &[NoteRecord],
+        previous: &HashMap<String, u64>,
+    ) -> Result<Bm25SyncStats> {
+        let mut stats = Bm25SyncStats::default();
+        let current: HashSet<&str> = notes.iter().map(|n| n.path.as_str()).collect();
+
+        let mut writer: IndexWriter = self
+            .index
+            .writer_with_num_threads(1, 15_000_000)
+            .context("Failed to create index writer")?;
+
+        for path in previous.keys() {
+            if !current.contains(path.as_str()) {
+                writer.delete_term(Term::from_field_text(self.path_field, path));
+                stats.removed += 1;
+            }
+        }
+
+        for note in notes {
+            match previous.get(&note.path) {
+                None => stats.added += 1,
+                Some(&mtime) if mtime != note.mtime => {
+                    writer.delete_term(Term::from_field_text(self.path_field, &note.path));
+                    stats.updated += 1;
+                }
+                Some(_) => continue,
+            }
+            self.add_note(&writer, note)?;
+        }
+
+        if stats != Bm25SyncStats::default() {
+            writer.commit().context("Failed to commit index")?;
+            self.reader
+                .reload()
+                .context("Failed to reload index reader")?;
+        }
+
+        Ok(stats)
+    }
+
+    fn add_note(&self, writer: &IndexWriter, note: &NoteRecord) -> Result<()> {
+        let title = Self::extract_title(&note.path);
+        let tags_text = note.tags.as_ref().map(|t| t.join(" ")).unwrap_or_default();
+        let body = self
+            .vault_root
+            .as_deref()
+            .map(|root| Self::read_body(root, &note.path))
+            .unwrap_or_default();
+
+        let mut document = doc!(
+            self.title_field => title,
+            self.gist_field => note.gist.as_str(),
+            self.tags_field => tags_text,
+            self.body_field => body,
+            self.path_field => note.path.as_str(),
+        );
+        for (name, value) in &note.fields {
+            for item in split_list(value) {
+                document.add_text(self.field_field, field_term(name, item));
+            }
+        }
+        for tag in note.tags.iter().flatten() {
+            for ancestor in tag_ancestors(tag) {
+                document.add_text(self.tag_field, ancestor);
+            }
+        }
+        for folder in folder_ancestors(&note.path) {
+            document.add_text(self.folder_field, folder);
+        }
+
+        writer.add_document(document)?;
+        Ok(())
+    }
+
+    /// Markdown body of a note without frontmatter (empty if unreadable)
+    fn read_body(vault_root: &Path, path: &str) -> String {
+        fs::read_to_string(vault_root.join(path))
+            .map(|content| FrontmatterEditor::new(&content).body().trim().to_string())
+            .unwrap_or_default()
+    }
+
     /// Build BM25 index from Obsidian vault
     ///
     /// Reads notes from the plugin index and builds a BM25 index
@@ -194,30 +494,72 @@ impl Bm25Index {
         // Build index in plugin's index directory
         let index_dir = vault_path.join(".obsidian/plugins/elysium/index/bm25");
 
-        Self::build_from_notes_with_config(&notes, &index_dir, config)
+        Self::build_from_notes_with_config(&notes, &index_dir, Some(vault_path), config)
     }
 
-    /// Search the index with query string
+    /// Search the index with query string (keyword syntax, see
+    /// `super::keyword`)
     ///
-    /// Returns vector of (path, score) tuples sorted by relevance.
+    /// Returns vector of (path, score) tuples sorted by relevance. A malformed
+    /// query fails with a `QueryError` giving its position.
     pub fn search(&self, query: &str, limit: usize) -> Result<Vec<(String, f32)>> {
-        let searcher = self.reader.searcher();
-
-        // Build query parser with field boosts
-        let mut query_parser = QueryParser::for_index(
-            &self.index,
-            vec![self.title_field, self.gist_field, self.tags_field],
-        );
+        self.search_filtered(query, limit, &SearchFilter::default())
+    }
 
-        // Set field boosts
-        query_parser.set_field_boost(self.title_field, self.config.title_boost);
-        query_parser.set_field_boost(self.gist_field, self.config.gist_boost);
-        query_parser.set_field_boost(self.tags_field, self.config.tags_boost);
+    /// Search only notes matching `filter`
+    ///
+    /// Filter conditions become term clauses on the `field`/`tag`/`folder`
+    /// fields; they restrict matches without changing scores.
+    pub fn search_filtered(
+        &self,
+        query: &str,
+        limit: usize,
+        filter: &SearchFilter,
+    ) -> Result<Vec<(String, f32)>> {
+        let query = KeywordQuery::parse(query)?;
+        self.search_expanded(&query, None, limit, filter)
+    }
 
-        // Parse query (lenient mode to handle special characters)
-        let parsed_query = query_parser
-            .parse_query(query)
-            .with_context(|| format!("Failed to parse query: {}", query))?;
+    /// Search like `search_filtered`, also matching expanded terms
+    ///
+    /// Each expansion is an optional phrase clause scored at the expansion
+    /// weight, so it adds matches without outranking the original terms.
+    pub fn search_expanded(
+        &self,
+        query: &KeywordQuery,
+        expansion: Option<&Expansion>,
+        limit: usize,
+        filter: &SearchFilter,
+    ) -> Result<Vec<(String, f32)>> {
+        let searcher = self.reader.searcher();
+        let mut parsed_query = self.build_query(query, None);
+        if let Some(expansion) = expansion.filter(|e| !e.is_empty()) {
+            let mut clauses = vec![(Occur::Should, parsed_query)];
+            for term in expansion.queries() {
+                let phrase = KeywordNode::Phrase {
+                    text: term.to_string(),
+                    field: None,
+                };
+                if let Some(query) = self.node_query(&phrase, None) {
+                    let expanded = BoostQuery::new(query, expansion.weight);
+                    clauses.push((Occur::Should, Box::new(expanded)));
+                }
+            }
+            parsed_query = Box::new(BooleanQuery::new(clauses));
+        }
+        let parsed_query: Box<dyn Query> = if filter.is_empty() {
+            parsed_query
+        } else {
+            let mut clauses = vec![(Occur::Must, parsed_query)];
+            for condition in &filter.include {
+                let filter_query = ConstScoreQuery::new(self.condition_query(condition), 0.0);
+                clauses.push((Occur::Must, Box::new(filter_query)));
+            }
+            for condition in &filter.exclude {
+                clauses.push((Occur::MustNot, self.condition_query(condition)));
+            }
+            Box::new(BooleanQuery::new(clauses))
+        };
 
         // Execute search
         let top_docs = searcher
@@ -242,6 +584,79 @@ impl Bm25Index {
         Ok(results)
     }
 
+    /// Highlighted body excerpts for `paths` matching `query`
+    ///
+    /// Matched terms are wrapped in `**`. Notes without a body match are
+    /// left out of the map. A malformed query is searched escaped.
+    pub fn snippets(&self, query: &str, paths: &[String]) -> Result<HashMap<String, String>> {
+        let searcher = self.reader.searcher();
+        let parsed_query = self.build_query(&KeywordQuery::parse_lenient(query).0, None);
+        let mut generator = SnippetGenerator::create(&searcher, &*parsed_query, self.body_field)
+            .context("Failed to create snippet generator")?;
+        generator.set_max_num_chars(SNIPPET_MAX_CHARS);
+
+        let mut snippets = HashMap::new();
+        for path in paths {
+            let Some(address) = self.doc_address(&searcher, path)? else {
+                continue;
+            };
+            let doc: tantivy::TantivyDocument = searcher
+                .doc(address)
+                .context("Failed to retrieve document")?;
+
+            let snippet = generator.snippet_from_doc(&doc);
+            if !snippet.is_empty() {
+                snippets.insert(path.clone(), Self::highlight(&snippet));
+            }
+        }
+
+        Ok(snippets)
+    }
+
+    /// Fields of each note that match `query`, with boost and boosted score
+    ///
+    /// Notes matching in no field are omitted. Field scores are computed per
+    /// field, so they add up to the note score only approximately. A
+    /// malformed query is matched escaped.
+    pub fn matched_fields(
+        &self,
+        query: &str,
+        paths: &[String],
+    ) -> Result<HashMap<String, Vec<FieldMatch>>> {
+        let searcher = self.reader.searcher();
+        let query = KeywordQuery::parse_lenient(query).0;
+        let field_queries: Vec<(&str, f32, Box<dyn Query>)> = KeywordField::ALL
+            .into_iter()
+            .map(|field| {
+                let (_, boost) = self.text_field(field);
+                (field.name(), boost, self.build_query(&query, Some(field)))
+            })
+            .collect();
+
+        let mut matched = HashMap::new();
+        for path in paths {
+            let Some(address) = self.doc_address(&searcher, path)? else {
+                continue;
+            };
+            let matches: Vec<FieldMatch> = field_queries
+                .iter()
+                .filter_map(|(name, boost, parsed)| {
+                    let explanation = parsed.explain(&searcher, address).ok()?;
+                    Some(FieldMatch {
+                        field: name.to_string(),
+                        boost: *boost,
+                        score: explanation.value(),
+                    })
+                })
+                .collect();
+            if !matches.is_empty() {
+                matched.insert(path.clone(), matches);
+            }
+        }
+
+        Ok(matched)
+    }
+
     /// Get the number of documents in the index
     pub fn num_docs(&self) -> u64 {
         self.reader.searcher().num_docs()
@@ -256,27 +671,230 @@ impl Bm25Index {
     // Private helpers
     // ------------------------------------------------------------------------
 
+    /// Address of the document for a note path
+    fn doc_address(
+        &self,
+        searcher: &tantivy::Searcher,
+        path: &str,
+    ) -> Result<Option<tantivy::DocAddress>> {
+        let path_query = TermQuery::new(
+            Term::from_field_text(self.path_field, path),
+            IndexRecordOption::Basic,
+        );
+        Ok(searcher
+            .search(&path_query, &TopDocs::with_limit(1))?
+            .into_iter()
+            .next()
+            .map(|(_, address)| address))
+    }
+
+    /// Render a snippet as plain text with `**` around matched terms
+    fn highlight(snippet: &Snippet) -> String {
+        let fragment = snippet.fragment();
+        let mut text = String::with_capacity(fragment.len() + 16);
+        let mut last = 0;
+        for range in snippet.highlighted() {
+            text.push_str(&fragment[last..range.start]);
+            text.push_str("**");
+            text.push_str(&fragment[range.clone()]);
+            text.push_str("**");
+            last = range.end;
+        }
+        text.push_str(&fragment[last..]);
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    /// Query matching notes that satisfy a filter condition
+    ///
+    /// One term set, however many values (a `Paths` set can hold the vault).
+    fn condition_query(&self, condition: &Condition) -> Box<dyn Query> {
+        let field = match condition {
+            Condition::Field { .. } => self.field_field,
+            Condition::Tag(_) => self.tag_field,
+            Condition::Folder(_) => self.folder_field,
+            Condition::Paths(_) => self.path_field,
+        };
+        let terms = condition
+            .terms()
+            .into_iter()
+            .map(|term| Term::from_field_text(field, &term));
+        Box::new(TermSetQuery::new(terms))
+    }
+
+    /// Index field and boost of a keyword field
+    fn text_field(&self, field: KeywordField) -> (Field, f32) {
+        match field {
+            KeywordField::Title => (self.title_field, self.config.title_boost),
+            KeywordField::Gist => (self.gist_field, self.config.gist_boost),
+            KeywordField::Tags => (self.tags_field, self.config.tags_boost),
+            KeywordField::Body => (self.body_field, self.config.body_boost),
+        }
+    }
+
+    /// Tantivy query for a keyword query over all text fields (or `only`)
+    ///
+    /// A query without searchable words matches nothing.
+    fn build_query(&self, query: &KeywordQuery, only: Option<KeywordField>) -> Box<dyn Query> {
+        self.node_query(&query.root, only)
+            .unwrap_or_else(|| Box::new(EmptyQuery))
+    }
+
+    /// None if the node has no words left after tokenization (`-`, `?`)
+    fn node_query(&self, node: &KeywordNode, only: Option<KeywordField>) -> Option<Box<dyn Query>> {
+        match node {
+            KeywordNode::Term {
+                text,
+                field,
+                prefix,
+            } => self.field_queries(*field, only, |f| self.term_query(f, text, *prefix)),
+            KeywordNode::Phrase { text, field } => {
+                self.field_queries(*field, only, |f| self.phrase_query(f, text))
+            }
+            KeywordNode::Or(nodes) => self.group_query(nodes, Occur::Should, only),
+            KeywordNode::And(nodes) => self.group_query(nodes, Occur::Must, only),
+            // Exclusions only apply inside a group (the parser ensures one)
+            KeywordNode::Not(_) => None,
+        }
+    }
+
+    fn group_query(
+        &self,
+        nodes: &[KeywordNode],
+        occur: Occur,
+        only: Option<KeywordField>,
+    ) -> Option<Box<dyn Query>> {
+        let mut clauses = Vec::new();
+        for node in nodes {
+            match node {
+                KeywordNode::Not(inner) => {
+                    if let Some(query) = self.node_query(inner, only) {
+                        clauses.push((Occur::MustNot, query));
+                    }
+                }
+                node => {
+                    if let Some(query) = self.node_query(node, only) {
+                        clauses.push((occur, query));
+                    }
+                }
+            }
+        }
+        if clauses.iter().all(|(o, _)| *o == Occur::MustNot) {
+            return None;
+        }
+        Some(Box::new(BooleanQuery::new(clauses)))
+    }
+
+    /// Boosted query per field (the node's field, `only` or all), OR-ed
+    fn field_queries(
+        &self,
+        scope: Option<KeywordField>,
+        only: Option<KeywordField>,
+        build: impl Fn(Field) -> Option<Box<dyn Query>>,
+    ) -> Option<Box<dyn Query>> {
+        let mut clauses: Vec<(Occur, Box<dyn Query>)> = KeywordField::ALL
+            .into_iter()
+            .filter(|f| scope.is_none_or(|s| s == *f) && only.is_none_or(|o| o == *f))
+            .filter_map(|f| {
+                let (field, boost) = self.text_field(f);
+                let query: Box<dyn Query> = Box::new(BoostQuery::new(build(field)?, boost));
+                Some((Occur::Should, query))
+            })
+            .collect();
+        match clauses.len() {
+            0 => None,
+            1 => clauses.pop().map(|(_, query)| query),
+            _ => Some(Box::new(BooleanQuery::new(clauses))),
+        }
+    }
+
+    /// A word: one term, or a phrase of the terms it tokenizes to (`e-mail`)
+    ///
+    /// With the CJK tokenizer, CJK runs become separate bigram terms that are
+    /// OR-ed. A prefix matches the last term as a prefix.
+    fn term_query(&self, field: Field, text: &str, prefix: bool) -> Option<Box<dyn Query>> {
+        if prefix {
+            let mut terms = self.analyze(field, text);
+            let (_, last) = terms.pop()?;
+            let query: Box<dyn Query> = if terms.is_empty() {
+                let pattern = format!("{}.*", regex::escape(last.value().as_str()?));
+                Box::new(RegexQuery::from_pattern(&pattern, field).ok()?)
+            } else {
+                terms.push((terms.len(), last));
+                Box::new(PhrasePrefixQuery::new_with_offset(terms))
+            };
+            return Some(query);
+        }
+
+        let words: Vec<String> = match self.config.tokenizer {
+            TokenizerKind::Default => text.split_whitespace().map(str::to_string).collect(),
+            TokenizerKind::Cjk => text
+                .split_whitespace()
+                .flat_map(tokenizer::expand_word)
+                .collect(),
+        };
+        let mut clauses: Vec<(Occur, Box<dyn Query>)> = words
+            .iter()
+            .filter_map(|word| Some((Occur::Should, self.phrase_query(field, word)?)))
+            .collect();
+        match clauses.len() {
+            0 => None,
+            1 => clauses.pop().map(|(_, query)| query),
+            _ => Some(Box::new(BooleanQuery::new(clauses))),
+        }
+    }
+
+    /// Terms of `text` in order (a single term is a term query)
+    fn phrase_query(&self, field: Field, text: &str) -> Option<Box<dyn Query>> {
+        let mut terms = self.analyze(field, text);
+        match terms.len() {
+            0 => None,
+            1 => {
+                let (_, term) = terms.pop()?;
+                Some(Box::new(TermQuery::new(term, IndexRecordOption::WithFreqs)))
+            }
+            _ => Some(Box::new(PhraseQuery::new_with_offset(terms))),
+        }
+    }
+
+    /// Terms of `text` with their positions, tokenized like the field
+    fn analyze(&self, field: Field, text: &str) -> Vec<(usize, Term)> {
+        let Ok(mut analyzer) = self.index.tokenizer_for_field(field) else {
+            return Vec::new();
+        };
+        let mut terms = Vec::new();
+        let mut stream = analyzer.token_stream(text);
+        while let Some(token) = stream.next() {
+            terms.push((token.position, Term::from_field_text(field, &token.text)));
+        }
+        terms
+    }
+
     /// Build the tantivy schema
-    fn build_schema() -> (
-        Schema,
-        tantivy::schema::Field,
-        tantivy::schema::Field,
-        tantivy::schema::Field,
-        tantivy::schema::Field,
-    ) {
+    fn build_schema(tokenizer: TokenizerKind) -> Schema {
         let mut schema_builder = Schema::builder();
 
-        // TEXT fields: tokenized and indexed for full-text search, not stored
-        let title_field = schema_builder.add_text_field("title", TEXT);
-        let gist_field = schema_builder.add_text_field("gist", TEXT);
-        let tags_field = schema_builder.add_text_field("tags", TEXT);
+        // Text fields: tokenized (with positions) for full-text search, not stored
+        let text = TextOptions::default().set_indexing_options(
+            TextFieldIndexing::default()
+                .set_tokenizer(tokenizer.name())
+                .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+        );
+        schema_builder.add_text_field("title", text.clone());
+        schema_builder.add_text_field("gist", text.clone());
+        schema_builder.add_text_field("tags", text.clone());
+        // Body is stored so snippets can be generated from it
+        schema_builder.add_text_field("body", text.set_stored());
 
         // STRING | STORED: stored for retrieval, indexed as single token
-        let path_field = schema_builder.add_text_field("path", STRING | STORED);
+        schema_builder.add_text_field("path", STRING | STORED);
 
-        let schema = schema_builder.build();
+        // STRING: filter terms, one token per value (`type=project`,
+        // tag and folder ancestors)
+        schema_builder.add_text_field("field", STRING);
+        schema_builder.add_text_field("tag", STRING);
+        schema_builder.add_text_field("folder", STRING);
 
-        (schema, title_field, gist_field, tags_field, path_field)
+        schema_builder.build()
     }
 
     /// Extract title from file path
@@ -296,6 +914,7 @@ impl Bm25Index {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::search::query::QueryError;
     use std::collections::HashMap;
     use tempfile::TempDir;
 
@@ -371,6 +990,118 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_open_or_update_incremental() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut notes = create_test_notes();
+
+        let (_, stats) =
+            Bm25Index::open_or_update(&notes, temp_dir.path(), None, 1, Bm25Config::default())?;
+        assert!(stats.rebuilt);
+        assert_eq!(stats.added, 3);
+
+        // Same export: reopened without changes
+        let (index, stats) =
+            Bm25Index::open_or_update(&notes, temp_dir.path(), None, 1, Bm25Config::default())?;
+        assert_eq!(stats, Bm25SyncStats::default());
+        assert_eq!(index.num_docs(), 3);
+        drop(index);
+
+        // New export: one changed, one removed, one added
+        notes[0].gist = "Rust ownership and borrowing rules".to_string();
+        notes[0].mtime += 1;
+        notes.remove(1);
+        notes.push(NoteRecord {
+            path: "Notes/Go Concurrency.md".to_string(),
+            gist: "Goroutines and channels".to_string(),
+            mtime: 1704067500,
+            indexed: true,
+            fields: HashMap::new(),
+            tags: None,
+        });
+
+        let (index, stats) =
+            Bm25Index::open_or_update(&notes, temp_dir.path(), None, 2, Bm25Config::default())?;
+        assert_eq!(
+            stats,
+            Bm25SyncStats {
+                rebuilt: false,
+                added: 1,
+                updated: 1,
+                removed: 1,
+            }
+        );
+        assert_eq!(index.num_docs(), 3);
+        assert_eq!(
+            index.search("borrowing", 5)?[0].0,
+            "Notes/Rust Programming.md"
+        );
+        assert!(index.search("python", 5)?.is_empty());
+        assert_eq!(index.search("goroutines", 5)?.len(), 1);
+        // Updated note is not duplicated
+        assert_eq!(index.search("rust", 5)?.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_or_update_rebuilds_on_schema_change() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let notes = create_test_notes();
+        Bm25Index::open_or_update(&notes, temp_dir.path(), None, 1, Bm25Config::default())?;
+
+        let state_path = temp_dir.path().join(STATE_FILE);
+        let state = fs::read_to_string(&state_path)?.replace(
+            &format!("\"schema_version\":{}", BM25_SCHEMA_VERSION),
+            "\"schema_version\":0",
+        );
+        fs::write(&state_path, state)?;
+
+        let (index, stats) =
+            Bm25Index::open_or_update(&notes, temp_dir.path(), None, 1, Bm25Config::default())?;
+        assert!(stats.rebuilt);
+        assert_eq!(index.num_docs(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cjk_tokenizer() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let notes = vec![NoteRecord {
+            path: "Notes/GPU 공유.md".to_string(),
+            gist: "여러 작업이 GPU를 메모리에서 나눠 쓰는 방법".to_string(),
+            mtime: 1704067200,
+            indexed: true,
+            fields: HashMap::new(),
+            tags: None,
+        }];
+        let cjk = Bm25Config {
+            tokenizer: TokenizerKind::Cjk,
+            ..Default::default()
+        };
+
+        // Particles stay attached with the default tokenizer
+        let index = Bm25Index::build_in_memory(&notes, None, Bm25Config::default())?;
+        assert!(index.search("메모리", 10)?.is_empty());
+
+        let (index, _) = Bm25Index::open_or_update(&notes, temp_dir.path(), None, 1, cjk)?;
+        assert_eq!(index.search("GPU", 10)?.len(), 1);
+        assert_eq!(index.search("메모리", 10)?.len(), 1);
+        assert_eq!(index.search("gist:나눠", 10)?.len(), 1);
+        assert!(index.search("커널", 10)?.is_empty());
+        // Field scopes and exclusions cover every bigram of a word
+        assert!(index.search("title:메모리", 10)?.is_empty());
+        assert!(index.search("GPU -메모리", 10)?.is_empty());
+
+        // Switching tokenizer rebuilds the persisted index
+        let (_, stats) =
+            Bm25Index::open_or_update(&notes, temp_dir.path(), None, 1, Bm25Config::default())?;
+        assert!(stats.rebuilt);
+
+        Ok(())
+    }
+
     #[test]
     fn test_custom_config() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -380,9 +1111,11 @@ mod tests {
             title_boost: 5.0,
             gist_boost: 1.0,
             tags_boost: 2.0,
+            body_boost: 0.5,
+            ..Default::default()
         };
 
-        let index = Bm25Index::build_from_notes_with_config(&notes, temp_dir.path(), config)?;
+        let index = Bm25Index::build_from_notes_with_config(&notes, temp_dir.path(), None, config)?;
 
         assert_eq!(index.config().title_boost, 5.0);
         assert_eq!(index.config().gist_boost, 1.0);
@@ -391,6 +1124,164 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_body_search_and_snippets() -> Result<()> {
+        let vault = TempDir::new()?;
+        let index_dir = TempDir::new()?;
+        fs::create_dir_all(vault.path().join("Notes"))?;
+        fs::write(
+            vault.path().join("Notes/Rust Programming.md"),
+            "---\nelysium_gist: frontmatter-only\n---\n# Rust\n\nAsync code runs on the tokio runtime.\n",
+        )?;
+        let notes = create_test_notes();
+
+        let index = Bm25Index::build_from_notes_with_config(
+            &notes,
+            index_dir.path(),
+            Some(vault.path()),
+            Bm25Config::default(),
+        )?;
+
+        let results = index.search("tokio", 10)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "Notes/Rust Programming.md");
+        // Frontmatter is not part of the body
+        assert!(index.search("frontmatter", 10)?.is_empty());
+
+        let paths = vec![
+            "Notes/Rust Programming.md".to_string(),
+            "Notes/Python Basics.md".to_string(),
+        ];
+        let snippets = index.snippets("tokio", &paths)?;
+        assert_eq!(snippets.len(), 1);
+        assert!(snippets["Notes/Rust Programming.md"].contains("**tokio**"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_filtered() -> Result<()> {
+        let mut notes = create_test_notes();
+        notes[0]
+            .fields
+            .insert("type".to_string(), "Project".to_string());
+        notes[0]
+            .fields
+            .insert("status".to_string(), "archived".to_string());
+        notes[1]
+            .fields
+            .insert("type".to_string(), "project".to_string());
+        notes[1].path = "Archive/Python Basics.md".to_string();
+        notes[2].tags = Some(vec!["ai/ml".to_string()]);
+
+        let index = Bm25Index::build_in_memory(&notes, None, Bm25Config::default())?;
+        let paths = |filter: &SearchFilter| -> Result<Vec<String>> {
+            Ok(index
+                .search_filtered("programming systems", 10, filter)?
+                .into_iter()
+                .map(|(path, _)| path)
+                .collect())
+        };
+
+        let projects = paths(&SearchFilter::default().field("type", "PROJECT"))?;
+        assert_eq!(projects.len(), 2);
+        assert!(!projects.contains(&"Notes/Machine Learning.md".to_string()));
+
+        let active = paths(&SearchFilter::default().exclude_archived(&Default::default()))?;
+        assert_eq!(active, vec!["Notes/Machine Learning.md"]);
+
+        assert_eq!(
+            paths(&SearchFilter::default().tag("ai"))?,
+            vec!["Notes/Machine Learning.md"]
+        );
+        assert_eq!(
+            paths(&SearchFilter::default().folder("archive"))?,
+            vec!["Archive/Python Basics.md"]
+        );
+
+        // A vault-sized path set is a single term set
+        let mut allowed = SearchFilter::default();
+        allowed.include.push(Condition::Paths(
+            (0..5000)
+                .map(|i| format!("Other/{}.md", i))
+                .chain(["Archive/Python Basics.md".to_string()])
+                .collect(),
+        ));
+        assert_eq!(paths(&allowed)?, vec!["Archive/Python Basics.md"]);
+
+        // Filters do not change scores
+        let filtered =
+            index.search_filtered("rust", 10, &SearchFilter::default().folder("Notes"))?;
+        assert_eq!(filtered, index.search("rust", 10)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_matched_fields() -> Result<()> {
+        let notes = create_test_notes();
+        let index = Bm25Index::build_in_memory(&notes, None, Bm25Config::default())?;
+        let paths: Vec<String> = notes.iter().map(|n| n.path.clone()).collect();
+
+        let matched = index.matched_fields("rust", &paths)?;
+        assert_eq!(matched.len(), 1);
+        let fields = &matched["Notes/Rust Programming.md"];
+        let names: Vec<&str> = fields.iter().map(|f| f.field.as_str()).collect();
+        assert_eq!(names, vec!["title", "gist", "tags"]);
+        assert_eq!(fields[0].boost, 3.0);
+        assert!(fields.iter().all(|f| f.score > 0.0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_keyword_syntax() -> Result<()> {
+        let notes = create_test_notes();
+        let index = Bm25Index::build_in_memory(&notes, None, Bm25Config::default())?;
+        let paths = |query: &str| -> Result<Vec<String>> {
+            let mut paths: Vec<String> = index
+                .search(query, 10)?
+                .into_iter()
+                .map(|(path, _)| path)
+                .collect();
+            paths.sort();
+            Ok(paths)
+        };
+        let rust = "Notes/Rust Programming.md".to_string();
+        let python = "Notes/Python Basics.md".to_string();
+        let ml = "Notes/Machine Learning.md".to_string();
+
+        assert_eq!(
+            paths("\"programming language\"")?,
+            vec![python.clone(), rust.clone()]
+        );
+        assert!(paths("\"language programming\"")?.is_empty());
+        assert_eq!(paths("programming AND systems")?, vec![rust.clone()]);
+        assert_eq!(paths("programming NOT python")?, vec![rust.clone()]);
+        assert_eq!(paths("programming -tags:python")?, vec![rust.clone()]);
+        assert!(paths("(rust OR python) AND NOT programming")?.is_empty());
+
+        // Field scopes
+        assert_eq!(paths("title:programming")?, vec![rust.clone()]);
+        assert_eq!(
+            paths("gist:(subset OR simplicity)")?,
+            vec![ml.clone(), python.clone()]
+        );
+        assert_eq!(paths("tags:ml")?, vec![ml.clone()]);
+        assert!(paths("title:ml")?.is_empty());
+
+        // Prefixes, also within a tokenized word
+        assert_eq!(paths("pyth*")?, vec![python.clone()]);
+        assert_eq!(paths("prog*")?, vec![python.clone(), rust]);
+        assert_eq!(paths("high-lev*")?, vec![python]);
+
+        let error = index.search("rust (safety", 10).unwrap_err();
+        let error = error.downcast_ref::<QueryError>().unwrap();
+        assert_eq!(error.position, 5);
+
+        Ok(())
+    }
+
     #[test]
     fn test_extract_title() {
         assert_eq!(Bm25Index::extract_title("Notes/Test Note.md"), "Test Note");
@@ -0,0 +1,449 @@
+//! Chunk Index - passage-level embeddings for long notes
+//!
+//! Note bodies are split into heading/paragraph chunks (see `chunker`) and
+//! each chunk is embedded with the same `Embedder` as the plugin index. A
+//! query is compared with every chunk and hits are aggregated per note (the
+//! best chunk wins), so a topic buried in a long note, or a note without a
+//! gist, can still be found.
+//!
+//! Vectors persist in `chunk_index/chunks.bin`; only notes whose mtime changed
+//! are re-embedded. Search is exact (linear scan): a personal vault has few
+//! enough chunks that this takes milliseconds and needs no graph upkeep.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+use walkdir::WalkDir;
+
+use super::chunker::chunk_body;
+use super::embedder::Embedder;
+use super::embedding::cosine_similarity;
+use super::filter::{field_text, Filterable, SearchFilter};
+use crate::core::frontmatter::FrontmatterEditor;
+use crate::core::note::{should_exclude_path, Note};
+use crate::core::paths::relative_path;
+
+/// Chunk index directory (relative to vault root)
+pub const CHUNK_INDEX_DIR: &str = ".obsidian/plugins/elysium/chunk_index";
+
+/// Bump when chunking rules or the store layout change (forces re-embedding)
+const CHUNK_INDEX_VERSION: u32 = 2;
+
+const STORE_FILE: &str = "chunks.bin";
+
+/// Number of chunks embedded per batch
+const EMBED_BATCH_SIZE: usize = 64;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredChunk {
+    heading: Option<String>,
+    vector: Vec<f32>,
+}
+
+/// Chunks of one note plus the metadata needed to report and filter it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkedNote {
+    mtime: u64,
+    /// Frontmatter fields without `elysium_` prefix (lists joined with ", ")
+    pub fields: HashMap<String, String>,
+    pub tags: Vec<String>,
+    chunks: Vec<StoredChunk>,
+}
+
+impl Filterable for ChunkedNote {
+    fn field(&self, name: &str) -> Option<Cow<'_, str>> {
+        self.fields.get(name).map(|v| Cow::Borrowed(v.as_str()))
+    }
+
+    fn tags(&self) -> Cow<'_, [String]> {
+        Cow::Borrowed(&self.tags)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ChunkStore {
+    version: u32,
+    embedding_mode: String,
+    notes: HashMap<String, ChunkedNote>,
+}
+
+/// Best-matching chunk of a note
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChunkHit {
+    pub path: String,
+    /// Heading of the matching chunk (None before the first heading)
+    pub heading: Option<String>,
+    pub score: f32,
+}
+
+/// Result of syncing the chunk index
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChunkSyncStats {
+    /// Stored chunks were discarded (version or embedding mode changed)
+    pub rebuilt: bool,
+    /// Notes (re-)embedded
+    pub embedded: usize,
+    pub removed: usize,
+    /// Changed notes left for a later sync (over the sync limit)
+    pub pending: usize,
+}
+
+pub struct ChunkIndex {
+    store: ChunkStore,
+}
+
+impl ChunkIndex {
+    /// Load the stored chunks and re-embed every note changed since the
+    /// last sync
+    pub fn open_or_update(
+        vault_root: &Path,
+        index_dir: &Path,
+        embedder: &dyn Embedder,
+        embedding_mode: &str,
+    ) -> Result<(Self, ChunkSyncStats)> {
+        let (mut index, rebuilt) = Self::open(index_dir, embedding_mode);
+        let mut stats = index.sync(vault_root, index_dir, embedder, None)?;
+        stats.rebuilt = rebuilt;
+        Ok((index, stats))
+    }
+
+    /// Load the stored chunks without syncing; true if they were discarded
+    /// (missing, or version or embedding mode changed)
+    pub fn open(index_dir: &Path, embedding_mode: &str) -> (Self, bool) {
+        match Self::read_store(index_dir) {
+            Some(store)
+                if store.version == CHUNK_INDEX_VERSION
+                    && store.embedding_mode == embedding_mode =>
+            {
+                (Self { store }, false)
+            }
+            _ => {
+                let store = ChunkStore {
+                    version: CHUNK_INDEX_VERSION,
+                    embedding_mode: embedding_mode.to_string(),
+                    notes: HashMap::new(),
+                };
+                (Self { store }, true)
+            }
+        }
+    }
+
+    /// Drop deleted notes and re-embed changed ones, at most `limit` (in
+    /// path order); the rest are counted as pending
+    pub fn sync(
+        &mut self,
+        vault_root: &Path,
+        index_dir: &Path,
+        embedder: &dyn Embedder,
+        limit: Option<usize>,
+    ) -> Result<ChunkSyncStats> {
+        let mut stats = ChunkSyncStats::default();
+        let store = &mut self.store;
+        let current = Self::scan(vault_root);
+
+        let before = store.notes.len();
+        store.notes.retain(|path, _| current.contains_key(path));
+        stats.removed = before - store.notes.len();
+
+        let mut changed: Vec<(&String, u64)> = current
+            .iter()
+            .filter(|(path, mtime)| store.notes.get(*path).map(|n| n.mtime) != Some(**mtime))
+            .map(|(path, mtime)| (path, *mtime))
+            .collect();
+        changed.sort();
+        let limit = limit.unwrap_or(usize::MAX);
+        stats.pending = changed.len().saturating_sub(limit);
+
+        for (path, mtime) in changed.into_iter().take(limit) {
+            let note = match Note::load(&vault_root.join(path)) {
+                Ok(note) => note,
+                Err(e) => {
+                    eprintln!("Warning: Skipping unreadable note {} ({})", path, e);
+                    continue;
+                }
+            };
+            store
+                .notes
+                .insert(path.clone(), Self::embed_note(&note, mtime, embedder)?);
+            stats.embedded += 1;
+        }
+
+        if stats.embedded > 0 || stats.removed > 0 {
+            Self::write_store(index_dir, store)?;
+        }
+
+        Ok(stats)
+    }
+
+    /// Notes matching `filter` whose best chunk is most similar to `query`,
+    /// best first
+    pub fn search(&self, query: &[f32], limit: usize, filter: &SearchFilter) -> Vec<ChunkHit> {
+        let mut hits: Vec<ChunkHit> = self
+            .store
+            .notes
+            .iter()
+            .filter(|(path, note)| filter.matches(path, *note))
+            .filter_map(|(path, note)| {
+                let best = note
+                    .chunks
+                    .iter()
+                    .map(|c| (c, cosine_similarity(query, &c.vector)))
+                    .max_by(|a, b| a.1.total_cmp(&b.1))?;
+                Some(ChunkHit {
+                    path: path.clone(),
+                    heading: best.0.heading.clone(),
+                    score: best.1,
+                })
+            })
+            .collect();
+
+        hits.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        hits.truncate(limit);
+        hits
+    }
+
+    /// Metadata of an indexed note
+    pub fn note(&self, path: &str) -> Option<&ChunkedNote> {
+        self.store.notes.get(path)
+    }
+
+    fn embed_note(note: &Note, mtime: u64, embedder: &dyn Embedder) -> Result<ChunkedNote> {
+        let body = FrontmatterEditor::new(&note.content).body().to_string();
+        let chunks = chunk_body(&body);
+
+        let mut stored = Vec::with_capacity(chunks.len());
+        for batch in chunks.chunks(EMBED_BATCH_SIZE) {
+            let texts: Vec<String> = batch.iter().map(|c| c.embedding_text(&note.name)).collect();
+            let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+            let vectors = embedder
+                .embed_batch(&texts)
+                .context("Failed to embed note chunks")?;
+            stored.extend(
+                batch
+                    .iter()
+                    .zip(vectors)
+                    .map(|(chunk, vector)| StoredChunk {
+                        heading: chunk.heading.clone(),
+                        vector,
+                    }),
+            );
+        }
+
+        // Same string form as the plugin export's fields
+        let fields = note
+            .frontmatter
+            .as_ref()
+            .map(|fm| {
+                fm.fields
+                    .iter()
+                    .filter_map(|(k, v)| Some((k.clone(), field_text(v)?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(ChunkedNote {
+            mtime,
+            fields,
+            tags: note.tags(),
+            chunks: stored,
+        })
+    }
+
+    /// Vault-relative note paths with mtimes (ms)
+    fn scan(vault_root: &Path) -> HashMap<String, u64> {
+        WalkDir::new(vault_root)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| {
+                e.file_type().is_file()
+                    && e.path().extension().map(|ext| ext == "md").unwrap_or(false)
+                    && !should_exclude_path(vault_root, e.path())
+            })
+            .map(|e| {
+                let mtime = e
+                    .metadata()
+                    .ok()
+                    .and_then(|m| m.modified().ok())
+                    .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                    .map(|d| d.as_millis() as u64)
+                    .unwrap_or(0);
+                (relative_path(vault_root, e.path()), mtime)
+            })
+            .collect()
+    }
+
+    fn read_store(index_dir: &Path) -> Option<ChunkStore> {
+        let data = fs::read(index_dir.join(STORE_FILE)).ok()?;
+        bincode::deserialize(&data).ok()
+    }
+
+    fn write_store(index_dir: &Path, store: &ChunkStore) -> Result<()> {
+        fs::create_dir_all(index_dir).with_context(|| {
+            format!(
+                "Failed to create chunk index directory: {}",
+                index_dir.display()
+            )
+        })?;
+        let data = bincode::serialize(store).context("Failed to serialize chunk index")?;
+        let tmp = index_dir.join(format!("{}.tmp", STORE_FILE));
+        fs::write(&tmp, data).with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, index_dir.join(STORE_FILE)).context("Failed to save chunk index")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::embedder::HtpEmbedder;
+    use crate::test_support::write_note;
+    use tempfile::TempDir;
+
+    fn setup_vault() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        write_note(
+            temp.path(),
+            "Notes/Homelab.md",
+            "---\nelysium_type: project\nelysium_gist: Home server setup\n---\n# Homelab\n\n## Networking\n\nVLANs and firewall rules for the router.\n\n## Backups\n\nNightly restic snapshots to object storage with retention policy.\n",
+        );
+        write_note(
+            temp.path(),
+            "Notes/Recipes.md",
+            "Pasta with tomato sauce and basil.\n",
+        );
+        temp
+    }
+
+    fn open(temp: &TempDir) -> Result<(ChunkIndex, ChunkSyncStats)> {
+        ChunkIndex::open_or_update(
+            temp.path(),
+            &temp.path().join(CHUNK_INDEX_DIR),
+            &HtpEmbedder::new(),
+            "htp",
+        )
+    }
+
+    #[test]
+    fn test_search_returns_best_heading() -> Result<()> {
+        let temp = setup_vault();
+        let (index, stats) = open(&temp)?;
+        assert!(stats.rebuilt);
+        assert_eq!(stats.embedded, 2);
+
+        let embedder = HtpEmbedder::new();
+        let hits = index.search(
+            &embedder.embed("restic snapshots retention")?,
+            2,
+            &SearchFilter::default(),
+        );
+        assert_eq!(hits[0].path, "Notes/Homelab.md");
+        assert_eq!(hits[0].heading.as_deref(), Some("Backups"));
+        // One hit per note
+        assert_eq!(hits.len(), 2);
+
+        // Gistless note is indexed from its body
+        let query = embedder.embed("pasta tomato basil")?;
+        let hits = index.search(&query, 1, &SearchFilter::default());
+        assert_eq!(hits[0].path, "Notes/Recipes.md");
+        assert_eq!(hits[0].heading, None);
+        assert_eq!(
+            index
+                .note("Notes/Homelab.md")
+                .unwrap()
+                .fields
+                .get("type")
+                .map(String::as_str),
+            Some("project")
+        );
+
+        // Filtered scan skips non-matching notes
+        let hits = index.search(&query, 2, &SearchFilter::default().field("type", "project"));
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].path, "Notes/Homelab.md");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_only_changed_notes_are_embedded() -> Result<()> {
+        let temp = setup_vault();
+        open(&temp)?;
+
+        let (_, stats) = open(&temp)?;
+        assert_eq!(stats, ChunkSyncStats::default());
+
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        write_note(temp.path(), "Notes/Recipes.md", "Risotto with mushrooms.\n");
+        fs::remove_file(temp.path().join("Notes/Homelab.md"))?;
+        write_note(temp.path(), "New.md", "# New\n\nFresh note.\n");
+
+        let (index, stats) = open(&temp)?;
+        assert_eq!(
+            stats,
+            ChunkSyncStats {
+                rebuilt: false,
+                embedded: 2,
+                removed: 1,
+                pending: 0,
+            }
+        );
+        assert!(index.note("Notes/Homelab.md").is_none());
+
+        // Different embedding mode discards stored vectors
+        let (_, stats) = ChunkIndex::open_or_update(
+            temp.path(),
+            &temp.path().join(CHUNK_INDEX_DIR),
+            &HtpEmbedder::new(),
+            "model2vec",
+        )?;
+        assert!(stats.rebuilt);
+        assert_eq!(stats.embedded, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sync_limit_leaves_notes_pending() -> Result<()> {
+        let temp = setup_vault();
+        let index_dir = temp.path().join(CHUNK_INDEX_DIR);
+        let embedder = HtpEmbedder::new();
+
+        let (mut index, rebuilt) = ChunkIndex::open(&index_dir, "htp");
+        assert!(rebuilt);
+        let stats = index.sync(temp.path(), &index_dir, &embedder, Some(1))?;
+        assert_eq!((stats.embedded, stats.pending), (1, 1));
+        assert!(index.note("Notes/Homelab.md").is_some());
+        assert!(index.note("Notes/Recipes.md").is_none());
+
+        let stats = index.sync(temp.path(), &index_dir, &embedder, Some(1))?;
+        assert_eq!((stats.embedded, stats.pending), (1, 0));
+
+        // Progress is stored
+        let (index, rebuilt) = ChunkIndex::open(&index_dir, "htp");
+        assert!(!rebuilt);
+        assert!(index.note("Notes/Recipes.md").is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unreadable_note_is_skipped() -> Result<()> {
+        let temp = setup_vault();
+        // Not UTF-8
+        fs::write(temp.path().join("Notes/Binary.md"), [0xff, 0xfe, 0x00])?;
+
+        let (index, stats) = open(&temp)?;
+        assert_eq!(stats.embedded, 2);
+        assert!(index.note("Notes/Binary.md").is_none());
+        assert!(index.note("Notes/Recipes.md").is_some());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,198 @@
+//! Note chunking for passage-level embeddings
+//!
+//! A note body is split into sections at headings, and each section into
+//! chunks of whole paragraphs up to `CHUNK_MAX_CHARS`. Fenced code blocks stay
+//! in one paragraph and `#` lines inside them are not headings. Each chunk
+//! keeps the heading it falls under so search results can link to
+//! `[[Note#Heading]]`.
+
+/// Upper bound for chunk text length (characters)
+pub const CHUNK_MAX_CHARS: usize = 1000;
+
+/// A passage of a note body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+    /// Nearest heading above the chunk (None before the first heading)
+    pub heading: Option<String>,
+    pub text: String,
+}
+
+impl Chunk {
+    /// Text to embed: note title and heading give the passage its context
+    pub fn embedding_text(&self, title: &str) -> String {
+        match &self.heading {
+            Some(heading) => format!("{} > {}: {}", title, heading, self.text),
+            None => format!("{}: {}", title, self.text),
+        }
+    }
+}
+
+/// Split a note body (without frontmatter) into chunks
+pub fn chunk_body(body: &str) -> Vec<Chunk> {
+    let mut chunks = Vec::new();
+    for (heading, paragraphs) in sections(body) {
+        let mut text = String::new();
+        for paragraph in paragraphs {
+            for piece in split_long(&paragraph) {
+                let len = text.chars().count();
+                if len > 0 && len + 2 + piece.chars().count() > CHUNK_MAX_CHARS {
+                    push_chunk(&mut chunks, &heading, std::mem::take(&mut text));
+                }
+                if !text.is_empty() {
+                    text.push_str("\n\n");
+                }
+                text.push_str(&piece);
+            }
+        }
+        push_chunk(&mut chunks, &heading, text);
+    }
+    chunks
+}
+
+fn push_chunk(chunks: &mut Vec<Chunk>, heading: &Option<String>, text: String) {
+    if text.chars().any(char::is_alphanumeric) {
+        chunks.push(Chunk {
+            heading: heading.clone(),
+            text,
+        });
+    }
+}
+
+/// Sections as (heading, paragraphs)
+fn sections(body: &str) -> Vec<(Option<String>, Vec<String>)> {
+    let mut sections = vec![(None, Vec::new())];
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut fence: Option<&str> = None;
+
+    let flush = |paragraph: &mut Vec<&str>, sections: &mut Vec<(Option<String>, Vec<String>)>| {
+        if !paragraph.is_empty() {
+            let text = paragraph.join("\n").trim().to_string();
+            if let Some((_, paragraphs)) = sections.last_mut() {
+                paragraphs.push(text);
+            }
+            paragraph.clear();
+        }
+    };
+
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+
+        if let Some(marker) = fence {
+            paragraph.push(line);
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fence = Some(&trimmed[..3]);
+            paragraph.push(line);
+            continue;
+        }
+
+        if let Some(heading) = heading_text(trimmed) {
+            flush(&mut paragraph, &mut sections);
+            sections.push((Some(heading), Vec::new()));
+        } else if trimmed.is_empty() {
+            flush(&mut paragraph, &mut sections);
+        } else {
+            paragraph.push(line);
+        }
+    }
+    flush(&mut paragraph, &mut sections);
+
+    sections
+}
+
+/// Text of an ATX heading line (`## Title ##`), None for other lines
+fn heading_text(line: &str) -> Option<String> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let rest = &line[level..];
+    if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+        return None;
+    }
+    let text = rest.trim().trim_end_matches('#').trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Split text longer than `CHUNK_MAX_CHARS` at whitespace
+fn split_long(text: &str) -> Vec<String> {
+    if text.chars().count() <= CHUNK_MAX_CHARS {
+        return vec![text.to_string()];
+    }
+
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    let mut current_len = 0;
+    for word in text.split_whitespace() {
+        let word_len = word.chars().count();
+        if current_len > 0 && current_len + 1 + word_len > CHUNK_MAX_CHARS {
+            pieces.push(std::mem::take(&mut current));
+            current_len = 0;
+        }
+        if current_len > 0 {
+            current.push(' ');
+            current_len += 1;
+        }
+        current.push_str(word);
+        current_len += word_len;
+    }
+    if !current.is_empty() {
+        pieces.push(current);
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_by_heading() {
+        let body = "Intro paragraph.\n\n# Setup\n\nInstall it.\n\nThen run it.\n\n## Usage ##\n\n```sh\n# not a heading\n\nrun --fast\n```\n";
+        let chunks = chunk_body(body);
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].heading, None);
+        assert_eq!(chunks[0].text, "Intro paragraph.");
+        assert_eq!(chunks[1].heading.as_deref(), Some("Setup"));
+        assert_eq!(chunks[1].text, "Install it.\n\nThen run it.");
+        assert_eq!(chunks[2].heading.as_deref(), Some("Usage"));
+        assert!(chunks[2].text.contains("# not a heading\n\nrun --fast"));
+        assert_eq!(
+            chunks[1].embedding_text("Tool"),
+            "Tool > Setup: Install it.\n\nThen run it."
+        );
+    }
+
+    #[test]
+    fn test_long_sections_are_split() {
+        let paragraph = "word ".repeat(150);
+        let body = format!(
+            "# Long\n\n{}\n\n{}\n\n{}",
+            paragraph,
+            paragraph,
+            "x".repeat(10)
+        );
+        let chunks = chunk_body(&body);
+
+        assert!(chunks.len() >= 2);
+        assert!(chunks
+            .iter()
+            .all(|c| c.text.chars().count() <= CHUNK_MAX_CHARS));
+        assert!(chunks.iter().all(|c| c.heading.as_deref() == Some("Long")));
+        // Short trailing paragraph is packed into the last chunk
+        assert!(chunks.last().unwrap().text.ends_with("xxxxxxxxxx"));
+    }
+
+    #[test]
+    fn test_heading_text() {
+        assert_eq!(heading_text("## Title ##").as_deref(), Some("Title"));
+        assert_eq!(heading_text("#tag"), None);
+        assert_eq!(heading_text("####### Too deep"), None);
+        assert_eq!(heading_text("#"), None);
+    }
+}
@@ -21,6 +21,8 @@ pub struct SearchResult {
     pub note_type: Option<String>,
     pub area: Option<String>,
     pub score: f32,
+    /// Heading of the best-matching passage (chunk search)
+    pub heading: Option<String>,
 }
 
 #[allow(dead_code)]
@@ -63,6 +65,7 @@ impl From<(NoteRecord, f32)> for SearchResult {
             note_type: record.note_type,
             area: record.area,
             score,
+            heading: None,
         }
     }
 }
@@ -163,6 +166,7 @@ impl SearchEngine {
                     note_type: record.note_type,
                     area: record.area,
                     score: boosted_score,
+                    heading: None,
                 }
             })
             .collect();
@@ -313,6 +317,7 @@ pub fn simple_search(vault_paths: &VaultPaths, query: &str, limit: usize) -> Vec
                 note_type: note.note_type().map(String::from),
                 area: note.area().map(String::from),
                 score,
+                heading: None,
             })
         })
         .collect();
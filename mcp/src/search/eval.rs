@@ -0,0 +1,376 @@
+//! Search evaluation over a judged query set
+//!
+//! A judged set lists queries with the notes that should be found for each,
+//! optionally graded (higher is more relevant):
+//!
+//! ```json
+//! {
+//!   "k": 10,
+//!   "queries": [
+//!     { "query": "gpu sharing", "relevant": ["Projects/GPU.md", { "path": "Terms/MIG.md", "grade": 2 }] }
+//!   ]
+//! }
+//! ```
+//!
+//! Each run (search mode plus hybrid configuration) searches every query for
+//! the top k notes and reports, per query and averaged:
+//! - MRR: reciprocal rank of the first relevant note (0 if none in the top k)
+//! - nDCG@k: discounted gain `(2^grade - 1) / log2(rank + 1)`, divided by the
+//!   gain of the ideal ordering of the judged notes
+//! - recall@k: share of the relevant notes found in the top k
+//!
+//! Queries are plain search text (filters and sort keys of the query syntax
+//! are not applied).
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::filter::SearchFilter;
+use super::hybrid::{HybridConfig, HybridSearchEngine, SearchMode, SearchOptions};
+
+/// Default judged set location (vault-relative, in the plugin data folder)
+pub const EVAL_SET_FILE: &str = ".obsidian/plugins/elysium/search_eval.json";
+
+/// Default cutoff when neither the caller nor the set chooses one
+pub const DEFAULT_K: usize = 10;
+
+/// Judged query set
+#[derive(Debug, Clone, Deserialize)]
+pub struct EvalSet {
+    /// Cutoff for the metrics (default: 10)
+    #[serde(default)]
+    pub k: Option<usize>,
+    pub queries: Vec<JudgedQuery>,
+}
+
+/// A query and the notes relevant to it
+#[derive(Debug, Clone, Deserialize)]
+pub struct JudgedQuery {
+    pub query: String,
+    pub relevant: Vec<Judgment>,
+}
+
+/// A relevant note: a vault-relative path (grade 1) or a path with a grade
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Judgment {
+    Path(String),
+    Graded { path: String, grade: u32 },
+}
+
+impl Judgment {
+    fn path(&self) -> String {
+        let path = match self {
+            Judgment::Path(path) | Judgment::Graded { path, .. } => path,
+        };
+        let path = path.trim().trim_start_matches('/');
+        if path.ends_with(".md") {
+            path.to_string()
+        } else {
+            format!("{}.md", path)
+        }
+    }
+
+    fn grade(&self) -> u32 {
+        match self {
+            Judgment::Path(_) => 1,
+            Judgment::Graded { grade, .. } => *grade,
+        }
+    }
+}
+
+impl EvalSet {
+    /// Parse and validate a judged set
+    pub fn from_json(json: &str) -> Result<Self> {
+        let set: Self = serde_json::from_str(json).context("Invalid judged query set")?;
+        if set.queries.is_empty() {
+            bail!("Judged query set has no queries");
+        }
+        for judged in &set.queries {
+            if judged.query.trim().is_empty() {
+                bail!("Judged query set has an empty query");
+            }
+            if !judged.relevant.iter().any(|j| j.grade() > 0) {
+                bail!("Query '{}' has no relevant notes", judged.query);
+            }
+        }
+        Ok(set)
+    }
+
+    /// Load a judged set from a JSON file
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read judged query set: {}", path.display()))?;
+        Self::from_json(&json)
+    }
+}
+
+/// One configuration to evaluate
+#[derive(Debug, Clone)]
+pub struct EvalRun {
+    pub mode: SearchMode,
+    /// Fusion settings (used in hybrid mode)
+    pub config: HybridConfig,
+    /// Name of the ranking settings, when several are compared
+    pub label: Option<String>,
+}
+
+impl EvalRun {
+    /// Label: mode name, plus the fusion method (and settings name) for
+    /// hybrid runs
+    pub fn name(&self) -> String {
+        match (self.mode, &self.label) {
+            (SearchMode::Hybrid, Some(label)) => {
+                format!("hybrid/{}/{}", self.config.fusion.name(), label)
+            }
+            (SearchMode::Hybrid, None) => format!("hybrid/{}", self.config.fusion.name()),
+            (mode, _) => mode.name().to_string(),
+        }
+    }
+}
+
+/// Ranking metrics (averaged over queries in a run report)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Metrics {
+    pub mrr: f32,
+    pub ndcg: f32,
+    pub recall: f32,
+}
+
+/// Metrics of one query in one run
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryReport {
+    pub query: String,
+    #[serde(flatten)]
+    pub metrics: Metrics,
+    /// Top k paths as ranked
+    pub results: Vec<String>,
+}
+
+/// Metrics of one run
+#[derive(Debug, Clone, Serialize)]
+pub struct RunReport {
+    pub name: String,
+    pub mode: &'static str,
+    /// Fusion settings (hybrid runs only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fusion: Option<FusionSettings>,
+    #[serde(flatten)]
+    pub metrics: Metrics,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub queries: Vec<QueryReport>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FusionSettings {
+    pub method: &'static str,
+    pub bm25_weight: f32,
+    pub semantic_weight: f32,
+    pub rrf_k: usize,
+}
+
+/// Evaluation results of all runs
+#[derive(Debug, Clone, Serialize)]
+pub struct EvalReport {
+    pub k: usize,
+    pub queries: usize,
+    pub runs: Vec<RunReport>,
+}
+
+/// Run every query of `set` for each run and report the metrics
+///
+/// `k` overrides the set's cutoff. Per-query reports are included when
+/// `per_query` is set. The engine's configuration is restored afterwards.
+pub fn evaluate(
+    engine: &mut HybridSearchEngine,
+    set: &EvalSet,
+    runs: &[EvalRun],
+    k: Option<usize>,
+    filter: &SearchFilter,
+    per_query: bool,
+) -> Result<EvalReport> {
+    let k = k.or(set.k).unwrap_or(DEFAULT_K).max(1);
+    let original = engine.config().clone();
+
+    let reports: Result<Vec<RunReport>> = runs
+        .iter()
+        .map(|run| {
+            engine.set_config(run.config.clone());
+            evaluate_run(engine, set, run, k, filter, per_query)
+        })
+        .collect();
+    engine.set_config(original);
+
+    Ok(EvalReport {
+        k,
+        queries: set.queries.len(),
+        runs: reports?,
+    })
+}
+
+fn evaluate_run(
+    engine: &mut HybridSearchEngine,
+    set: &EvalSet,
+    run: &EvalRun,
+    k: usize,
+    filter: &SearchFilter,
+    per_query: bool,
+) -> Result<RunReport> {
+    let mut options = SearchOptions::new(run.mode);
+    options.fusion = Some(run.config.fusion);
+    // Malformed keyword syntax is searched as plain words, like vault_search
+    // in hybrid mode
+    options.lenient = true;
+
+    let mut total = Metrics::default();
+    let mut queries = Vec::new();
+    for judged in &set.queries {
+        let results: Vec<String> = engine
+            .search(&judged.query, k, options, filter)
+            .with_context(|| format!("Search failed for '{}'", judged.query))?
+            .into_iter()
+            .map(|r| r.path)
+            .collect();
+        let grades: HashMap<String, u32> = judged
+            .relevant
+            .iter()
+            .map(|j| (j.path(), j.grade()))
+            .collect();
+        let metrics = Metrics {
+            mrr: reciprocal_rank(&results, &grades),
+            ndcg: ndcg_at_k(&results, &grades, k),
+            recall: recall_at_k(&results, &grades, k),
+        };
+        total.mrr += metrics.mrr;
+        total.ndcg += metrics.ndcg;
+        total.recall += metrics.recall;
+        if per_query {
+            queries.push(QueryReport {
+                query: judged.query.clone(),
+                metrics,
+                results,
+            });
+        }
+    }
+
+    let n = set.queries.len().max(1) as f32;
+    Ok(RunReport {
+        name: run.name(),
+        mode: run.mode.name(),
+        fusion: (run.mode == SearchMode::Hybrid).then(|| FusionSettings {
+            method: run.config.fusion.name(),
+            bm25_weight: run.config.bm25_weight,
+            semantic_weight: run.config.semantic_weight,
+            rrf_k: run.config.rrf_k,
+        }),
+        metrics: Metrics {
+            mrr: total.mrr / n,
+            ndcg: total.ndcg / n,
+            recall: total.recall / n,
+        },
+        queries,
+    })
+}
+
+/// 1 / rank of the first relevant result (0 if none)
+pub fn reciprocal_rank(results: &[String], grades: &HashMap<String, u32>) -> f32 {
+    results
+        .iter()
+        .position(|path| grades.get(path).is_some_and(|&g| g > 0))
+        .map_or(0.0, |i| 1.0 / (i + 1) as f32)
+}
+
+/// Normalized discounted cumulative gain of the top k results
+pub fn ndcg_at_k(results: &[String], grades: &HashMap<String, u32>, k: usize) -> f32 {
+    let gain =
+        |grade: u32, i: usize| ((1u64 << grade.min(31)) - 1) as f32 / ((i + 2) as f32).log2();
+
+    let dcg: f32 = results
+        .iter()
+        .take(k)
+        .enumerate()
+        .map(|(i, path)| gain(grades.get(path).copied().unwrap_or(0), i))
+        .sum();
+
+    let mut ideal: Vec<u32> = grades.values().copied().filter(|&g| g > 0).collect();
+    ideal.sort_unstable_by(|a, b| b.cmp(a));
+    let idcg: f32 = ideal
+        .into_iter()
+        .take(k)
+        .enumerate()
+        .map(|(i, g)| gain(g, i))
+        .sum();
+
+    if idcg > 0.0 {
+        dcg / idcg
+    } else {
+        0.0
+    }
+}
+
+/// Share of relevant notes among the top k results
+pub fn recall_at_k(results: &[String], grades: &HashMap<String, u32>, k: usize) -> f32 {
+    let relevant = grades.values().filter(|&&g| g > 0).count();
+    if relevant == 0 {
+        return 0.0;
+    }
+    let found = results
+        .iter()
+        .take(k)
+        .filter(|path| grades.get(*path).is_some_and(|&g| g > 0))
+        .count();
+    found as f32 / relevant as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_metrics() {
+        let grades: HashMap<String, u32> =
+            HashMap::from([("a.md".to_string(), 2), ("b.md".to_string(), 1)]);
+
+        let ideal = paths(&["a.md", "b.md", "c.md"]);
+        assert_eq!(reciprocal_rank(&ideal, &grades), 1.0);
+        assert!((ndcg_at_k(&ideal, &grades, 3) - 1.0).abs() < 1e-6);
+        assert_eq!(recall_at_k(&ideal, &grades, 3), 1.0);
+
+        let swapped = paths(&["c.md", "b.md", "a.md"]);
+        assert_eq!(reciprocal_rank(&swapped, &grades), 0.5);
+        // (1/log2(3) + 3/log2(4)) / (3 + 1/log2(3))
+        let expected = (1.0 / 3f32.log2() + 1.5) / (3.0 + 1.0 / 3f32.log2());
+        assert!((ndcg_at_k(&swapped, &grades, 3) - expected).abs() < 1e-6);
+        assert_eq!(recall_at_k(&swapped, &grades, 2), 0.5);
+
+        let missed = paths(&["c.md"]);
+        assert_eq!(reciprocal_rank(&missed, &grades), 0.0);
+        assert_eq!(ndcg_at_k(&missed, &grades, 3), 0.0);
+    }
+
+    #[test]
+    fn test_parse_eval_set() {
+        let set = EvalSet::from_json(
+            r#"{"k": 5, "queries": [{"query": "gpu", "relevant": ["Notes/a", {"path": "b.md", "grade": 3}]}]}"#,
+        )
+        .unwrap();
+        assert_eq!(set.k, Some(5));
+        let relevant = &set.queries[0].relevant;
+        assert_eq!(relevant[0].path(), "Notes/a.md");
+        assert_eq!(
+            (relevant[1].path(), relevant[1].grade()),
+            ("b.md".to_string(), 3)
+        );
+
+        assert!(EvalSet::from_json(r#"{"queries": []}"#).is_err());
+        assert!(EvalSet::from_json(r#"{"queries": [{"query": "x", "relevant": []}]}"#).is_err());
+    }
+}
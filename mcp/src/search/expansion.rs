@@ -0,0 +1,237 @@
+//! Query expansion from tag aliases and a synonym file
+//!
+//! Tag aliases (`vram` -> `gpu`) come from the tag database; the synonym file
+//! (`synonyms.txt` in the plugin data folder) is maintained by the user:
+//!
+//! ```text
+//! # Equivalent terms, one group per line
+//! k8s, kubernetes
+//! # One-way: the left side also searches the right side
+//! vram => gpu memory, gpu
+//! ```
+//!
+//! Query words (and phrases of up to `MAX_PHRASE_WORDS` words) found in
+//! either source add their expansions to the BM25 query as optional clauses
+//! scored at `weight` (default `DEFAULT_WEIGHT`), so notes matching the
+//! original words still rank first. Semantic search uses the original query.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+/// Synonym file name in the plugin data folder
+pub const SYNONYMS_FILE: &str = "synonyms.txt";
+
+/// Score weight of expanded terms relative to the original query
+pub const DEFAULT_WEIGHT: f32 = 0.5;
+
+/// Longest synonym phrase matched in a query
+const MAX_PHRASE_WORDS: usize = 3;
+
+/// Where an expansion comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExpansionSource {
+    TagAlias,
+    Synonym,
+}
+
+/// Expansion dictionary: lowercase term -> expansions
+#[derive(Debug, Clone, Default)]
+pub struct QueryExpander {
+    entries: BTreeMap<String, Vec<(String, ExpansionSource)>>,
+}
+
+/// Expansions applied to one query
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Expansion {
+    pub weight: f32,
+    pub terms: Vec<ExpandedTerm>,
+}
+
+/// A query term and what it expanded to
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExpandedTerm {
+    pub term: String,
+    pub expansions: Vec<String>,
+    pub source: ExpansionSource,
+}
+
+impl Expansion {
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// All expanded terms (without duplicates)
+    pub fn queries(&self) -> Vec<&str> {
+        let mut seen = HashSet::new();
+        self.terms
+            .iter()
+            .flat_map(|t| t.expansions.iter())
+            .filter(|e| seen.insert(e.as_str()))
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+impl QueryExpander {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expand a tag alias to its tag (`vram` -> `gpu`)
+    pub fn add_tag_alias(&mut self, alias: &str, tag: &str) {
+        // Nested tags search by their last segment (`gpu/cuda` -> `cuda`)
+        let tag = tag.rsplit('/').next().unwrap_or(tag);
+        self.add(alias, tag, ExpansionSource::TagAlias);
+    }
+
+    /// Load synonym groups and one-way rules (a missing file adds nothing)
+    pub fn load_synonyms(&mut self, path: &Path) -> Result<()> {
+        if !path.exists() {
+            return Ok(());
+        }
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read synonyms: {}", path.display()))?;
+        self.add_synonyms(&text);
+        Ok(())
+    }
+
+    /// Add synonyms in the file format
+    pub fn add_synonyms(&mut self, text: &str) {
+        let split = |s: &str| -> Vec<String> {
+            s.split(',')
+                .map(normalize)
+                .filter(|t| !t.is_empty())
+                .collect()
+        };
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some((from, to)) = line.split_once("=>") {
+                for term in split(from) {
+                    for target in split(to) {
+                        self.add(&term, &target, ExpansionSource::Synonym);
+                    }
+                }
+            } else {
+                let group = split(line);
+                for term in &group {
+                    for target in group.iter().filter(|t| *t != term) {
+                        self.add(term, target, ExpansionSource::Synonym);
+                    }
+                }
+            }
+        }
+    }
+
+    fn add(&mut self, term: &str, expansion: &str, source: ExpansionSource) {
+        let (term, expansion) = (normalize(term), normalize(expansion));
+        if term.is_empty() || expansion.is_empty() || term == expansion {
+            return;
+        }
+        let entry = self.entries.entry(term).or_default();
+        if !entry.iter().any(|(e, _)| *e == expansion) {
+            entry.push((expansion, source));
+        }
+    }
+
+    /// Expansions for the words and phrases of `query`
+    ///
+    /// Terms already in the query are not added again.
+    pub fn expand(&self, query: &str, weight: f32) -> Expansion {
+        let words: Vec<String> = query
+            .split(|c: char| !c.is_alphanumeric() && c != '-' && c != '_')
+            .filter(|w| !w.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+        let present: HashSet<&str> = words.iter().map(String::as_str).collect();
+
+        let mut terms: Vec<ExpandedTerm> = Vec::new();
+        for n in (1..=MAX_PHRASE_WORDS).rev() {
+            for window in words.windows(n) {
+                let term = window.join(" ");
+                let Some(entries) = self.entries.get(&term) else {
+                    continue;
+                };
+                if terms.iter().any(|t| t.term == term) {
+                    continue;
+                }
+                // One entry per source, keeping the dictionary order
+                for source in [ExpansionSource::TagAlias, ExpansionSource::Synonym] {
+                    let expansions: Vec<String> = entries
+                        .iter()
+                        .filter(|(e, s)| *s == source && !present.contains(e.as_str()))
+                        .map(|(e, _)| e.clone())
+                        .collect();
+                    if !expansions.is_empty() {
+                        terms.push(ExpandedTerm {
+                            term: term.clone(),
+                            expansions,
+                            source,
+                        });
+                    }
+                }
+            }
+        }
+
+        Expansion { weight, terms }
+    }
+}
+
+/// Lowercase with single spaces
+fn normalize(term: &str) -> String {
+    term.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand() {
+        let mut expander = QueryExpander::new();
+        expander.add_tag_alias("VRAM", "gpu");
+        expander.add_tag_alias("metal", "hardware/mps");
+        expander.add_synonyms("# comment\nk8s, Kubernetes\nvram => gpu memory, gpu\n");
+
+        let expansion = expander.expand("VRAM usage on k8s", 0.5);
+        assert_eq!(expansion.weight, 0.5);
+        assert_eq!(
+            expansion.terms,
+            vec![
+                ExpandedTerm {
+                    term: "vram".to_string(),
+                    expansions: vec!["gpu".to_string()],
+                    source: ExpansionSource::TagAlias,
+                },
+                ExpandedTerm {
+                    term: "vram".to_string(),
+                    expansions: vec!["gpu memory".to_string()],
+                    source: ExpansionSource::Synonym,
+                },
+                ExpandedTerm {
+                    term: "k8s".to_string(),
+                    expansions: vec!["kubernetes".to_string()],
+                    source: ExpansionSource::Synonym,
+                },
+            ]
+        );
+        assert_eq!(expansion.queries(), vec!["gpu", "gpu memory", "kubernetes"]);
+
+        // Groups work both ways; one-way rules do not
+        assert_eq!(expander.expand("kubernetes", 0.5).queries(), vec!["k8s"]);
+        assert!(expander.expand("gpu memory", 0.5).is_empty());
+        assert_eq!(expander.expand("metal", 0.5).queries(), vec!["mps"]);
+        // Already in the query
+        assert!(expander.expand("k8s kubernetes", 0.5).is_empty());
+    }
+}
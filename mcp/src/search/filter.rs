@@ -0,0 +1,281 @@
+//! Search filters pushed down into the search engines
+//!
+//! A `SearchFilter` is a conjunction of conditions on frontmatter fields,
+//! tags and folders. Each engine applies it while searching instead of
+//! post-filtering a fixed over-fetch, so a selective filter still returns
+//! `limit` results when enough matching notes exist:
+//! - semantic: filtered HNSW traversal, or a linear scan over the matching
+//!   notes when they are few (see `PluginSearchEngine::search_vector`)
+//! - passages: the chunk scan skips non-matching notes
+//! - BM25: term filters on indexed `field`/`tag`/`folder` tokens (and `path`)
+//!
+//! Matching is case-insensitive. List fields match if any item matches;
+//! tags and folders also match their descendants (`gpu` matches `gpu/cuda`).
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use crate::core::config::FoldersConfig;
+use crate::core::frontmatter::FieldValue;
+use crate::core::note::Note;
+
+/// One filter condition
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    /// Frontmatter field (without `elysium_` prefix) equals one of `values`
+    Field { name: String, values: Vec<String> },
+    /// Note has the tag (or a nested tag below it)
+    Tag(String),
+    /// Note is in the folder (or a subfolder)
+    Folder(String),
+    /// Note path (vault-relative) is one of these
+    Paths(HashSet<String>),
+}
+
+impl Condition {
+    /// Lowercased terms as indexed by BM25 (`field`/`tag`/`folder` fields)
+    pub fn terms(&self) -> Vec<String> {
+        match self {
+            Condition::Field { name, values } => {
+                values.iter().map(|v| field_term(name, v)).collect()
+            }
+            Condition::Tag(tag) => vec![normalize_tag(tag)],
+            Condition::Folder(folder) => vec![normalize_folder(folder)],
+            Condition::Paths(paths) => paths.iter().cloned().collect(),
+        }
+    }
+
+    fn matches(&self, path: &str, note: &dyn Filterable) -> bool {
+        match self {
+            Condition::Field { name, values } => note.field(name).is_some_and(|field| {
+                split_list(&field).any(|item| values.iter().any(|v| v.eq_ignore_ascii_case(item)))
+            }),
+            Condition::Tag(tag) => {
+                let tag = normalize_tag(tag);
+                note.tags()
+                    .iter()
+                    .any(|t| tag_ancestors(t).any(|a| a == tag))
+            }
+            Condition::Folder(folder) => {
+                let folder = normalize_folder(folder);
+                folder_ancestors(path).any(|f| f.eq_ignore_ascii_case(&folder))
+            }
+            Condition::Paths(paths) => paths.contains(path),
+        }
+    }
+}
+
+/// Note metadata a filter can test
+pub trait Filterable {
+    /// Field value; list fields are joined with ", "
+    fn field(&self, name: &str) -> Option<Cow<'_, str>>;
+    fn tags(&self) -> Cow<'_, [String]>;
+}
+
+impl Filterable for Note {
+    fn field(&self, name: &str) -> Option<Cow<'_, str>> {
+        field_text(self.get_field(name)?).map(Cow::Owned)
+    }
+
+    fn tags(&self) -> Cow<'_, [String]> {
+        Cow::Owned(Note::tags(self))
+    }
+}
+
+/// Field value as filtered and exported: text, or list items joined with ", "
+/// (nested maps have no text form)
+pub fn field_text(value: &FieldValue) -> Option<String> {
+    match value {
+        FieldValue::List(_) => Some(value.as_string_list()?.join(", ")),
+        _ => value.to_string_value(),
+    }
+}
+
+/// Conjunction of required and excluded conditions
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchFilter {
+    /// All must match
+    pub include: Vec<Condition>,
+    /// None may match
+    pub exclude: Vec<Condition>,
+}
+
+impl SearchFilter {
+    /// Require a field value
+    pub fn field(mut self, name: &str, value: &str) -> Self {
+        self.include.push(Condition::Field {
+            name: name.to_string(),
+            values: vec![value.to_string()],
+        });
+        self
+    }
+
+    /// Require a tag
+    pub fn tag(mut self, tag: &str) -> Self {
+        self.include.push(Condition::Tag(tag.to_string()));
+        self
+    }
+
+    /// Require a folder prefix
+    pub fn folder(mut self, folder: &str) -> Self {
+        self.include.push(Condition::Folder(folder.to_string()));
+        self
+    }
+
+    /// Exclude archived notes (`status: archived` or in the archive folder)
+    pub fn exclude_archived(mut self, folders: &FoldersConfig) -> Self {
+        self.exclude.push(Condition::Field {
+            name: "status".to_string(),
+            values: vec!["archived".to_string()],
+        });
+        if !folders.archive.trim_matches('/').is_empty() {
+            self.exclude
+                .push(Condition::Folder(folders.archive.clone()));
+        }
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Whether a required condition tests the field
+    pub fn requires_field(&self, field: &str) -> bool {
+        self.include
+            .iter()
+            .any(|c| matches!(c, Condition::Field { name, .. } if name == field))
+    }
+
+    /// Whether a note passes the filter
+    pub fn matches(&self, path: &str, note: &dyn Filterable) -> bool {
+        self.include.iter().all(|c| c.matches(path, note))
+            && !self.exclude.iter().any(|c| c.matches(path, note))
+    }
+}
+
+/// BM25 term for a field value
+pub fn field_term(name: &str, value: &str) -> String {
+    format!("{}={}", name.to_lowercase(), value.trim().to_lowercase())
+}
+
+/// Items of a field value (list fields are joined with ", ")
+pub fn split_list(value: &str) -> impl Iterator<Item = &str> {
+    value.split(", ").map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// A tag and its parents, lowercased: `a/b/c` -> `a/b/c`, `a/b`, `a`
+pub fn tag_ancestors(tag: &str) -> impl Iterator<Item = String> {
+    let tag = normalize_tag(tag);
+    let ends: Vec<usize> = tag
+        .match_indices('/')
+        .map(|(i, _)| i)
+        .chain(std::iter::once(tag.len()))
+        .collect();
+    ends.into_iter()
+        .rev()
+        .map(move |end| tag[..end].to_string())
+}
+
+/// Folders containing a note path: `A/B/n.md` -> `a/b`, `a`
+pub fn folder_ancestors(path: &str) -> impl Iterator<Item = String> + '_ {
+    path.match_indices('/')
+        .map(move |(i, _)| path[..i].to_lowercase())
+        .rev()
+}
+
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().trim_start_matches('#').to_lowercase()
+}
+
+fn normalize_folder(folder: &str) -> String {
+    folder.trim().trim_matches('/').to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    struct TestNote {
+        fields: HashMap<String, String>,
+        tags: Vec<String>,
+    }
+
+    impl Filterable for TestNote {
+        fn field(&self, name: &str) -> Option<Cow<'_, str>> {
+            self.fields.get(name).map(|v| Cow::Borrowed(v.as_str()))
+        }
+        fn tags(&self) -> Cow<'_, [String]> {
+            Cow::Borrowed(&self.tags)
+        }
+    }
+
+    fn note(fields: &[(&str, &str)], tags: &[&str]) -> TestNote {
+        TestNote {
+            fields: fields
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_matches_fields_tags_and_folders() {
+        let n = note(
+            &[("type", "Project"), ("source", "paper, blog")],
+            &["gpu/cuda"],
+        );
+        let path = "Projects/GPU/Sharing.md";
+
+        assert!(SearchFilter::default().matches(path, &n));
+        assert!(SearchFilter::default()
+            .field("type", "project")
+            .matches(path, &n));
+        assert!(SearchFilter::default()
+            .field("source", "blog")
+            .matches(path, &n));
+        assert!(!SearchFilter::default()
+            .field("area", "work")
+            .matches(path, &n));
+        assert!(SearchFilter::default().tag("#GPU").matches(path, &n));
+        assert!(!SearchFilter::default().tag("cuda").matches(path, &n));
+        assert!(SearchFilter::default()
+            .folder("projects/")
+            .matches(path, &n));
+        assert!(SearchFilter::default()
+            .folder("Projects/GPU")
+            .matches(path, &n));
+        assert!(!SearchFilter::default().folder("Proj").matches(path, &n));
+    }
+
+    #[test]
+    fn test_exclude_archived() {
+        let folders = FoldersConfig::default();
+        let filter = SearchFilter::default().exclude_archived(&folders);
+        let active = note(&[("status", "active")], &[]);
+        let archived = note(&[("status", "Archived")], &[]);
+
+        assert!(filter.matches("Notes/a.md", &active));
+        assert!(!filter.matches("Notes/a.md", &archived));
+        assert!(!filter.matches("Archive/a.md", &active));
+    }
+
+    #[test]
+    fn test_terms() {
+        assert_eq!(tag_ancestors("#A/B").collect::<Vec<_>>(), vec!["a/b", "a"]);
+        assert_eq!(
+            folder_ancestors("A/B/n.md").collect::<Vec<_>>(),
+            vec!["a/b", "a"]
+        );
+        assert!(folder_ancestors("n.md").next().is_none());
+        assert_eq!(
+            Condition::Field {
+                name: "Type".to_string(),
+                values: vec!["Project".to_string()],
+            }
+            .terms(),
+            vec!["type=project"]
+        );
+    }
+}
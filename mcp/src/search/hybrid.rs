@@ -2,16 +2,31 @@
 //!
 //! Supports three search modes:
 //! - Hybrid: RRF fusion of BM25 + Semantic results (default)
-//! - Semantic: HNSW vector search only (existing behavior)
+//! - Semantic: gist (HNSW) and passage (chunk) vector search
 //! - Keyword: BM25 text search only
 
 use anyhow::{Context, Result};
+use serde::Serialize;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
-use super::bm25::Bm25Index;
+use super::bm25::{Bm25Config, Bm25Index, FieldMatch};
+use super::chunk_index::{ChunkHit, ChunkIndex, CHUNK_INDEX_DIR};
 use super::engine::SearchResult;
-use super::plugin_index::{NoteRecord, PluginSearchEngine};
+use super::expansion::Expansion;
+use super::filter::SearchFilter;
+use super::keyword::KeywordQuery;
+use super::mmr;
+use super::plugin_index::{self, NoteRecord, PluginSearchEngine};
+use crate::core::config::RankingConfig;
+
+/// Persistent BM25 index directory (vault-relative)
+pub const BM25_INDEX_DIR: &str = ".obsidian/plugins/elysium/bm25_index";
+
+/// Notes embedded into the chunk index per search; the rest follow on later
+/// searches (`VaultIndexer::build` embeds them all at once)
+const CHUNK_SYNC_LIMIT: usize = 50;
 
 // ============================================================================
 // Search Mode
@@ -42,10 +57,88 @@ impl SearchMode {
     /// assert_eq!(SearchMode::from_str("unknown"), SearchMode::Hybrid); // default
     /// ```
     pub fn from_str(s: &str) -> Self {
+        Self::parse(s).unwrap_or_default()
+    }
+
+    pub const NAMES: &'static str = "hybrid, semantic, keyword";
+
+    /// Parse a search mode name (None if unknown)
+    pub fn parse(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
-            "semantic" => SearchMode::Semantic,
-            "keyword" | "bm25" => SearchMode::Keyword,
-            _ => SearchMode::Hybrid, // default
+            "hybrid" => Some(SearchMode::Hybrid),
+            "semantic" => Some(SearchMode::Semantic),
+            "keyword" | "bm25" => Some(SearchMode::Keyword),
+            _ => None,
+        }
+    }
+    pub fn name(&self) -> &'static str {
+        match self {
+            SearchMode::Hybrid => "hybrid",
+            SearchMode::Semantic => "semantic",
+            SearchMode::Keyword => "keyword",
+        }
+    }
+}
+
+/// How hybrid search combines semantic and BM25 results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FusionMethod {
+    /// Reciprocal rank fusion: weight / (k + rank), ignores raw scores (default)
+    #[default]
+    Rrf,
+    /// Scores scaled to [0, 1] by each list's min and max, then weighted sum
+    MinMax,
+    /// Scores standardized by each list's mean and standard deviation, then
+    /// weighted sum; a note missing from a list gets that list's lowest z-score
+    ZScore,
+    /// Distribution-based score fusion: scores scaled to [0, 1] between
+    /// mean - 3σ and mean + 3σ of each list, then weighted sum
+    Dbsf,
+}
+
+impl FusionMethod {
+    pub const NAMES: &'static str = "rrf, min_max, z_score, dbsf";
+
+    /// Parse a fusion method name (None if unknown)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "rrf" => Some(FusionMethod::Rrf),
+            "min_max" | "minmax" => Some(FusionMethod::MinMax),
+            "z_score" | "zscore" => Some(FusionMethod::ZScore),
+            "dbsf" => Some(FusionMethod::Dbsf),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FusionMethod::Rrf => "rrf",
+            FusionMethod::MinMax => "min_max",
+            FusionMethod::ZScore => "z_score",
+            FusionMethod::Dbsf => "dbsf",
+        }
+    }
+}
+
+/// Per-query search options
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SearchOptions<'a> {
+    pub mode: SearchMode,
+    /// Fusion method for hybrid mode (None: configured default)
+    pub fusion: Option<FusionMethod>,
+    /// MMR diversity in [0, 1] (None or 0: rank by relevance only)
+    pub diversity: Option<f32>,
+    /// Down-weighted extra BM25 terms (keyword and hybrid modes)
+    pub expansion: Option<&'a Expansion>,
+    /// Search a malformed keyword query escaped instead of failing
+    pub lenient: bool,
+}
+
+impl SearchOptions<'_> {
+    pub fn new(mode: SearchMode) -> Self {
+        Self {
+            mode,
+            ..Default::default()
         }
     }
 }
@@ -63,6 +156,8 @@ pub struct HybridConfig {
     pub semantic_weight: f32,
     /// RRF k parameter - controls rank contribution decay (default: 60)
     pub rrf_k: usize,
+    /// Fusion method when a query does not choose one (default: RRF)
+    pub fusion: FusionMethod,
 }
 
 impl Default for HybridConfig {
@@ -71,6 +166,7 @@ impl Default for HybridConfig {
             bm25_weight: 0.3,
             semantic_weight: 0.7,
             rrf_k: 60,
+            fusion: FusionMethod::Rrf,
         }
     }
 }
@@ -84,6 +180,30 @@ impl HybridConfig {
             ..Default::default()
         }
     }
+
+    /// Defaults overridden by the vault config `search` section
+    ///
+    /// An unknown fusion method is reported and RRF is used.
+    pub fn from_ranking(ranking: &RankingConfig) -> Self {
+        let defaults = Self::default();
+        let fusion = match ranking.fusion.as_deref() {
+            None => defaults.fusion,
+            Some(name) => FusionMethod::parse(name).unwrap_or_else(|| {
+                eprintln!(
+                    "Warning: Unknown search.fusion '{}' in config (expected {}). Using rrf.",
+                    name,
+                    FusionMethod::NAMES
+                );
+                FusionMethod::Rrf
+            }),
+        };
+        Self {
+            bm25_weight: ranking.bm25_weight.unwrap_or(defaults.bm25_weight),
+            semantic_weight: ranking.semantic_weight.unwrap_or(defaults.semantic_weight),
+            rrf_k: ranking.rrf_k.unwrap_or(defaults.rrf_k),
+            fusion,
+        }
+    }
 }
 
 // ============================================================================
@@ -105,33 +225,215 @@ impl HybridConfig {
 ///
 /// # Returns
 /// Fused results sorted by combined RRF score in descending order
+#[allow(dead_code)]
 pub fn fuse_rrf(
     semantic_results: Vec<(String, f32)>,
     bm25_results: Vec<(String, f32)>,
     config: &HybridConfig,
 ) -> Vec<(String, f32)> {
-    let mut scores: HashMap<String, f32> = HashMap::new();
-    let k = config.rrf_k as f32;
+    fuse(semantic_results, bm25_results, config, FusionMethod::Rrf)
+        .into_iter()
+        .map(|f| (f.path, f.score))
+        .collect()
+}
 
-    // Add semantic search contributions
-    for (rank, (path, _score)) in semantic_results.into_iter().enumerate() {
-        let rrf_score = config.semantic_weight / (k + (rank + 1) as f32);
-        *scores.entry(path).or_insert(0.0) += rrf_score;
-    }
+/// Fused score of a document with what each source contributed
+#[derive(Debug, Clone, PartialEq)]
+pub struct FusedScore {
+    pub path: String,
+    pub score: f32,
+    /// 1-based rank and score in the semantic results
+    pub semantic: Option<(usize, f32)>,
+    /// 1-based rank and score in the BM25 results
+    pub bm25: Option<(usize, f32)>,
+    pub semantic_contribution: f32,
+    pub bm25_contribution: f32,
+}
 
-    // Add BM25 search contributions
-    for (rank, (path, _score)) in bm25_results.into_iter().enumerate() {
-        let rrf_score = config.bm25_weight / (k + (rank + 1) as f32);
-        *scores.entry(path).or_insert(0.0) += rrf_score;
+/// Fuse semantic and BM25 results with the given method
+///
+/// Each source contributes its weight times a rank term (RRF) or a
+/// normalized score (other methods). Sorted by fused score, ties by path.
+pub fn fuse(
+    semantic_results: Vec<(String, f32)>,
+    bm25_results: Vec<(String, f32)>,
+    config: &HybridConfig,
+    method: FusionMethod,
+) -> Vec<FusedScore> {
+    let (semantic_parts, semantic_missing) = contributions(
+        &semantic_results,
+        config.semantic_weight,
+        config.rrf_k,
+        method,
+    );
+    let (bm25_parts, bm25_missing) =
+        contributions(&bm25_results, config.bm25_weight, config.rrf_k, method);
+
+    let mut fused: HashMap<String, FusedScore> = HashMap::new();
+    let blank = |path: String| FusedScore {
+        path,
+        score: 0.0,
+        semantic: None,
+        bm25: None,
+        semantic_contribution: semantic_missing,
+        bm25_contribution: bm25_missing,
+    };
+
+    for (rank, ((path, score), part)) in
+        semantic_results.into_iter().zip(semantic_parts).enumerate()
+    {
+        let f = fused.entry(path.clone()).or_insert_with(|| blank(path));
+        f.semantic = Some((rank + 1, score));
+        f.semantic_contribution = part;
+    }
+    for (rank, ((path, score), part)) in bm25_results.into_iter().zip(bm25_parts).enumerate() {
+        let f = fused.entry(path.clone()).or_insert_with(|| blank(path));
+        f.bm25 = Some((rank + 1, score));
+        f.bm25_contribution = part;
     }
 
-    // Sort by fused score descending
-    let mut results: Vec<(String, f32)> = scores.into_iter().collect();
-    results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    let mut results: Vec<FusedScore> = fused
+        .into_values()
+        .map(|mut f| {
+            f.score = f.semantic_contribution + f.bm25_contribution;
+            f
+        })
+        .collect();
+    results.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.path.cmp(&b.path))
+    });
 
     results
 }
 
+/// Weighted contribution of each ranked result, and of a missing result
+fn contributions(
+    results: &[(String, f32)],
+    weight: f32,
+    rrf_k: usize,
+    method: FusionMethod,
+) -> (Vec<f32>, f32) {
+    let scores: Vec<f32> = results.iter().map(|(_, score)| *score).collect();
+    let n = scores.len().max(1) as f32;
+    let mean = scores.iter().sum::<f32>() / n;
+    let std = (scores.iter().map(|s| (s - mean).powi(2)).sum::<f32>() / n).sqrt();
+
+    let (normalized, missing): (Vec<f32>, f32) = match method {
+        FusionMethod::Rrf => {
+            let parts = (0..scores.len())
+                .map(|rank| 1.0 / (rrf_k as f32 + (rank + 1) as f32))
+                .collect();
+            (parts, 0.0)
+        }
+        FusionMethod::MinMax => {
+            let min = scores.iter().copied().fold(f32::INFINITY, f32::min);
+            let max = scores.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+            (scale(&scores, min, max), 0.0)
+        }
+        FusionMethod::ZScore => {
+            let z: Vec<f32> = scores
+                .iter()
+                .map(|s| if std > 0.0 { (s - mean) / std } else { 0.0 })
+                .collect();
+            let lowest = z.iter().copied().fold(f32::INFINITY, f32::min);
+            let missing = if lowest.is_finite() { lowest } else { 0.0 };
+            (z, missing)
+        }
+        FusionMethod::Dbsf => (scale(&scores, mean - 3.0 * std, mean + 3.0 * std), 0.0),
+    };
+
+    (
+        normalized.into_iter().map(|v| weight * v).collect(),
+        weight * missing,
+    )
+}
+
+/// Scale scores to [0, 1] between `low` and `high` (1.0 if they are equal)
+fn scale(scores: &[f32], low: f32, high: f32) -> Vec<f32> {
+    scores
+        .iter()
+        .map(|s| {
+            if high > low {
+                ((s - low) / (high - low)).clamp(0.0, 1.0)
+            } else {
+                1.0
+            }
+        })
+        .collect()
+}
+
+/// Keyword syntax of a BM25 query; a syntax error fails unless `lenient`
+fn keyword_query(query: &str, lenient: bool) -> Result<KeywordQuery> {
+    match KeywordQuery::parse(query) {
+        Ok(parsed) => Ok(parsed),
+        Err(_) if lenient => Ok(KeywordQuery::escaped(query)),
+        Err(e) => Err(e.into()),
+    }
+}
+
+// ============================================================================
+// Score Explanation
+// ============================================================================
+
+/// Score explanations by result path
+type Explanations = HashMap<String, ScoreExplanation>;
+
+/// Why a result scored as it did (`explain` option of `vault_search`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ScoreExplanation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub semantic: Option<SemanticExplanation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bm25: Option<Bm25Explanation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fusion: Option<FusionExplanation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SemanticExplanation {
+    pub rank: usize,
+    /// Cosine similarity of the best match (gist or passage)
+    pub score: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gist_score: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passage_score: Option<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Bm25Explanation {
+    pub rank: usize,
+    pub score: f32,
+    /// Fields containing query terms, with boost and score contribution
+    pub matched_fields: Vec<FieldMatch>,
+}
+
+impl Bm25Explanation {
+    fn new(rank: usize, score: f32) -> Self {
+        Self {
+            rank,
+            score,
+            matched_fields: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FusionExplanation {
+    pub method: &'static str,
+    /// RRF k (rrf only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub k: Option<usize>,
+    pub semantic_weight: f32,
+    pub bm25_weight: f32,
+    /// Semantic part of the fused score (rrf: semantic_weight / (k + rank))
+    pub semantic_contribution: f32,
+    /// BM25 part of the fused score (rrf: bm25_weight / (k + rank))
+    pub bm25_contribution: f32,
+}
+
 // ============================================================================
 // Hybrid Search Engine
 // ============================================================================
@@ -145,8 +447,16 @@ pub struct HybridSearchEngine {
     semantic: PluginSearchEngine,
     /// BM25 keyword search index (lazy loaded)
     bm25: Option<Bm25Index>,
+    /// Passage embeddings (lazy loaded, possibly partly synced)
+    chunks: Option<ChunkIndex>,
+    /// Plugin index stamp the chunks were loaded for (None before the first)
+    chunks_stamp: Option<Option<SystemTime>>,
+    /// Changed notes remain to be embedded into the chunks
+    chunks_pending: bool,
     /// Hybrid search configuration
     config: HybridConfig,
+    /// BM25 field boosts
+    bm25_config: Bm25Config,
     /// Vault path for BM25 index building
     vault_path: PathBuf,
 }
@@ -169,7 +479,11 @@ impl HybridSearchEngine {
         Ok(Self {
             semantic,
             bm25: None,
+            chunks: None,
+            chunks_stamp: None,
+            chunks_pending: false,
             config: HybridConfig::default(),
+            bm25_config: Bm25Config::default(),
             vault_path: vault_path.to_path_buf(),
         })
     }
@@ -182,14 +496,19 @@ impl HybridSearchEngine {
         Ok(engine)
     }
 
+    /// Create with fusion settings and BM25 boosts from the vault config
+    pub fn with_ranking(vault_path: &Path, ranking: &RankingConfig) -> Result<Self> {
+        let mut engine = Self::with_config(vault_path, HybridConfig::from_ranking(ranking))?;
+        engine.bm25_config = Bm25Config::from_ranking(ranking);
+        Ok(engine)
+    }
+
     /// Get current configuration
-    #[allow(dead_code)]
     pub fn config(&self) -> &HybridConfig {
         &self.config
     }
 
     /// Update configuration
-    #[allow(dead_code)]
     pub fn set_config(&mut self, config: HybridConfig) {
         self.config = config;
     }
@@ -197,9 +516,12 @@ impl HybridSearchEngine {
     /// Search with specified mode
     ///
     /// # Arguments
-    /// * `query` - Search query string
+    /// * `query` - Query in keyword syntax (semantic search embeds its words)
     /// * `limit` - Maximum number of results
-    /// * `mode` - Search mode (Hybrid, Semantic, or Keyword)
+    /// * `options` - Search mode (Hybrid, Semantic, or Keyword), fusion method
+    ///   and MMR diversity
+    /// * `filter` - Notes to search; applied inside each engine, so up to
+    ///   `limit` matching notes are returned however selective it is
     ///
     /// # Returns
     /// Vector of search results sorted by relevance
@@ -207,97 +529,393 @@ impl HybridSearchEngine {
         &mut self,
         query: &str,
         limit: usize,
-        mode: SearchMode,
+        options: SearchOptions,
+        filter: &SearchFilter,
     ) -> Result<Vec<SearchResult>> {
-        match mode {
-            SearchMode::Semantic => self.search_semantic(query, limit),
-            SearchMode::Keyword => self.search_keyword(query, limit),
-            SearchMode::Hybrid => self.search_hybrid(query, limit),
+        Ok(self.run_search(query, limit, options, filter)?.0)
+    }
+
+    /// Search like `search`, with a score explanation per result
+    ///
+    /// Explanations hold each source's rank and score, the BM25 fields that
+    /// matched (with their boosts) and, in hybrid mode, the fusion contribution
+    /// of each side.
+    pub fn search_explained(
+        &mut self,
+        query: &str,
+        limit: usize,
+        options: SearchOptions,
+        filter: &SearchFilter,
+    ) -> Result<Vec<(SearchResult, ScoreExplanation)>> {
+        let (results, mut explanations) = self.run_search(query, limit, options, filter)?;
+
+        if options.mode != SearchMode::Semantic {
+            let paths: Vec<String> = results.iter().map(|r| r.path.clone()).collect();
+            let mut matched = self.bm25.as_ref().unwrap().matched_fields(query, &paths)?;
+            for (path, explanation) in &mut explanations {
+                if let (Some(bm25), Some(fields)) = (&mut explanation.bm25, matched.remove(path)) {
+                    bm25.matched_fields = fields;
+                }
+            }
         }
+
+        Ok(results
+            .into_iter()
+            .map(|r| {
+                let explanation = explanations.remove(&r.path).unwrap_or_default();
+                (r, explanation)
+            })
+            .collect())
     }
 
-    /// Semantic search only (HNSW)
-    fn search_semantic(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
-        self.semantic.search(query, limit)
+    fn run_search(
+        &mut self,
+        query: &str,
+        limit: usize,
+        options: SearchOptions,
+        filter: &SearchFilter,
+    ) -> Result<(Vec<SearchResult>, Explanations)> {
+        let Some(diversity) = options.diversity.filter(|d| *d > 0.0) else {
+            return self.run_mode(query, limit, options, filter);
+        };
+
+        // Re-rank a larger candidate pool for diversity
+        let (candidates, mut explanations) =
+            self.run_mode(query, mmr::candidate_pool(limit), options, filter)?;
+        let semantic = &self.semantic;
+        let results = mmr::rerank(
+            candidates,
+            limit,
+            diversity,
+            |r| r.score,
+            |r| semantic.vector(&r.path),
+        );
+        explanations.retain(|path, _| results.iter().any(|r| &r.path == path));
+        Ok((results, explanations))
+    }
+
+    fn run_mode(
+        &mut self,
+        query: &str,
+        limit: usize,
+        options: SearchOptions,
+        filter: &SearchFilter,
+    ) -> Result<(Vec<SearchResult>, Explanations)> {
+        match options.mode {
+            SearchMode::Semantic => {
+                let text = keyword_query(query, true)?.text();
+                self.search_semantic(&text, limit, filter)
+            }
+            SearchMode::Keyword => {
+                let keyword = keyword_query(query, options.lenient)?;
+                self.search_keyword(&keyword, limit, options.expansion, filter)
+            }
+            SearchMode::Hybrid => {
+                let keyword = keyword_query(query, options.lenient)?;
+                let fusion = options.fusion.unwrap_or(self.config.fusion);
+                self.search_hybrid(&keyword, limit, fusion, options.expansion, filter)
+            }
+        }
+    }
+
+    /// Semantic search: gist embeddings plus passage embeddings
+    ///
+    /// A note scores as its best match (gist or chunk); results carry the
+    /// chunk's heading only when the chunk is that best match.
+    fn search_semantic(
+        &mut self,
+        query: &str,
+        limit: usize,
+        filter: &SearchFilter,
+    ) -> Result<(Vec<SearchResult>, Explanations)> {
+        self.ensure_chunk_index();
+
+        let query_embedding = self.semantic.embed_query(query)?;
+        let results = self.semantic.search_vector(&query_embedding, limit, filter);
+        let hits = match &self.chunks {
+            Some(chunks) => chunks.search(&query_embedding, limit, filter),
+            None => Vec::new(),
+        };
+
+        Ok(self.merge_chunk_hits(results, hits, limit))
+    }
+
+    fn merge_chunk_hits(
+        &self,
+        results: Vec<SearchResult>,
+        hits: Vec<ChunkHit>,
+        limit: usize,
+    ) -> (Vec<SearchResult>, Explanations) {
+        let mut passage_scores: HashMap<String, f32> = HashMap::new();
+        let gist_scores: HashMap<String, f32> =
+            results.iter().map(|r| (r.path.clone(), r.score)).collect();
+        let mut by_path: HashMap<String, SearchResult> =
+            results.into_iter().map(|r| (r.path.clone(), r)).collect();
+
+        for hit in hits {
+            passage_scores.insert(hit.path.clone(), hit.score);
+            if let Some(result) = by_path.get_mut(&hit.path) {
+                if hit.score > result.score {
+                    result.score = hit.score;
+                    result.heading = hit.heading;
+                }
+            } else if let Some(mut result) = self.result_for(&hit.path, hit.score) {
+                result.heading = hit.heading;
+                by_path.insert(hit.path, result);
+            }
+        }
+
+        let mut merged: Vec<SearchResult> = by_path.into_values().collect();
+        merged.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        merged.truncate(limit);
+
+        let explanations = merged
+            .iter()
+            .enumerate()
+            .map(|(i, r)| {
+                let semantic = SemanticExplanation {
+                    rank: i + 1,
+                    score: r.score,
+                    gist_score: gist_scores.get(&r.path).copied(),
+                    passage_score: passage_scores.get(&r.path).copied(),
+                };
+                let explanation = ScoreExplanation {
+                    semantic: Some(semantic),
+                    ..Default::default()
+                };
+                (r.path.clone(), explanation)
+            })
+            .collect();
+
+        (merged, explanations)
     }
 
     /// Keyword search only (BM25)
-    fn search_keyword(&mut self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
+    fn search_keyword(
+        &mut self,
+        query: &KeywordQuery,
+        limit: usize,
+        expansion: Option<&Expansion>,
+        filter: &SearchFilter,
+    ) -> Result<(Vec<SearchResult>, Explanations)> {
         self.ensure_bm25_index()?;
 
         let bm25 = self.bm25.as_ref().unwrap();
-        let bm25_results = bm25.search(query, limit)?;
+        let bm25_results = bm25.search_expanded(query, expansion, limit, filter)?;
+
+        let explanations = bm25_results
+            .iter()
+            .enumerate()
+            .map(|(i, (path, score))| {
+                let explanation = ScoreExplanation {
+                    bm25: Some(Bm25Explanation::new(i + 1, *score)),
+                    ..Default::default()
+                };
+                (path.clone(), explanation)
+            })
+            .collect();
 
         // Convert BM25 results to SearchResult
-        self.convert_bm25_results(bm25_results)
+        Ok((self.convert_bm25_results(bm25_results)?, explanations))
     }
 
-    /// Hybrid search (RRF fusion of semantic + BM25)
-    fn search_hybrid(&mut self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
+    /// Hybrid search (fusion of semantic + BM25)
+    ///
+    /// The semantic side embeds the searched words without keyword syntax.
+    fn search_hybrid(
+        &mut self,
+        query: &KeywordQuery,
+        limit: usize,
+        fusion: FusionMethod,
+        expansion: Option<&Expansion>,
+        filter: &SearchFilter,
+    ) -> Result<(Vec<SearchResult>, Explanations)> {
         self.ensure_bm25_index()?;
 
         // Get more results from each source for better fusion
         let fetch_limit = limit * 3;
 
         // Get semantic results
-        let semantic_results = self.semantic.search(query, fetch_limit)?;
+        let (semantic_results, mut semantic_explanations) =
+            self.search_semantic(&query.text(), fetch_limit, filter)?;
         let semantic_pairs: Vec<(String, f32)> = semantic_results
             .iter()
             .map(|r| (r.path.clone(), r.score))
             .collect();
+        let headings: HashMap<String, String> = semantic_results
+            .into_iter()
+            .filter_map(|r| Some((r.path, r.heading?)))
+            .collect();
 
         // Get BM25 results
         let bm25 = self.bm25.as_ref().unwrap();
-        let bm25_pairs = bm25.search(query, fetch_limit)?;
-
-        // Fuse results with RRF
-        let fused = fuse_rrf(semantic_pairs, bm25_pairs, &self.config);
+        let bm25_pairs = bm25.search_expanded(query, expansion, fetch_limit, filter)?;
+
+        // Fuse results (RRF by default)
+        let fused = fuse(semantic_pairs, bm25_pairs, &self.config, fusion);
+
+        let mut explanations = Explanations::new();
+        for f in &fused {
+            let semantic = semantic_explanations
+                .remove(&f.path)
+                .and_then(|e| e.semantic);
+            let explanation = ScoreExplanation {
+                semantic,
+                bm25: f
+                    .bm25
+                    .map(|(rank, score)| Bm25Explanation::new(rank, score)),
+                fusion: Some(FusionExplanation {
+                    method: fusion.name(),
+                    k: (fusion == FusionMethod::Rrf).then_some(self.config.rrf_k),
+                    semantic_weight: self.config.semantic_weight,
+                    bm25_weight: self.config.bm25_weight,
+                    semantic_contribution: f.semantic_contribution,
+                    bm25_contribution: f.bm25_contribution,
+                }),
+            };
+            explanations.insert(f.path.clone(), explanation);
+        }
 
         // Convert fused results to SearchResult, limited to requested count
-        self.convert_fused_results(fused, limit)
+        let fused = fused.into_iter().map(|f| (f.path, f.score)).collect();
+        let mut results = self.convert_fused_results(fused, limit)?;
+        for result in &mut results {
+            result.heading = headings.get(&result.path).cloned();
+        }
+        explanations.retain(|path, _| results.iter().any(|r| &r.path == path));
+        Ok((results, explanations))
     }
 
-    /// Ensure BM25 index is built (lazy loading)
+    /// Highlighted body excerpts matching `query` for the given result paths
+    ///
+    /// Works for any search mode; notes whose body does not contain a query
+    /// term get no snippet.
+    pub fn snippets(&mut self, query: &str, paths: &[String]) -> Result<HashMap<String, String>> {
+        self.ensure_bm25_index()?;
+        self.bm25.as_ref().unwrap().snippets(query, paths)
+    }
+
+    /// Ensure BM25 index is loaded (lazy loading)
+    ///
+    /// The index persists in `bm25_index/` and is only updated for notes
+    /// that changed since the last plugin export. If another process holds
+    /// the index lock, an in-memory index is built instead.
     fn ensure_bm25_index(&mut self) -> Result<()> {
         if self.bm25.is_none() {
             // Collect notes from semantic engine for BM25 indexing
             let notes: Vec<NoteRecord> = self.semantic.iter_notes().cloned().collect();
 
-            // Build BM25 index in a subdirectory of the vault
-            let bm25_index_dir = self.vault_path.join(".obsidian/plugins/elysium/bm25_index");
-
-            let bm25 = Bm25Index::build_from_notes(&notes, &bm25_index_dir)
-                .context("Failed to build BM25 index")?;
+            let bm25_index_dir = self.vault_path.join(BM25_INDEX_DIR);
+
+            let bm25 = match Bm25Index::open_or_update(
+                &notes,
+                &bm25_index_dir,
+                Some(&self.vault_path),
+                self.semantic.exported_at(),
+                self.bm25_config.clone(),
+            ) {
+                Ok((bm25, _stats)) => bm25,
+                Err(e) => {
+                    eprintln!(
+                        "Warning: BM25 index at {} unavailable ({}). Using in-memory index.",
+                        bm25_index_dir.display(),
+                        e
+                    );
+                    Bm25Index::build_in_memory(
+                        &notes,
+                        Some(&self.vault_path),
+                        self.bm25_config.clone(),
+                    )
+                    .context("Failed to build BM25 index")?
+                }
+            };
             self.bm25 = Some(bm25);
         }
         Ok(())
     }
 
-    /// Convert BM25 results to SearchResult
-    fn convert_bm25_results(&self, results: Vec<(String, f32)>) -> Result<Vec<SearchResult>> {
-        let mut search_results = Vec::with_capacity(results.len());
-
-        for (path, score) in results {
-            if let Some(note) = self.semantic.get_note(&path) {
-                search_results.push(SearchResult {
-                    id: path.clone(),
-                    path,
-                    title: note
-                        .path
-                        .rsplit('/')
-                        .next()
-                        .unwrap_or(&note.path)
-                        .trim_end_matches(".md")
-                        .to_string(),
-                    gist: Some(note.gist.clone()),
-                    note_type: note.fields.get("type").cloned(),
-                    area: note.fields.get("area").cloned(),
-                    score,
-                });
+    /// Ensure the chunk index is loaded and syncing with the vault
+    ///
+    /// Chunks persist in `chunk_index/` and are reloaded whenever the plugin
+    /// index is re-exported. Each search embeds at most `CHUNK_SYNC_LIMIT`
+    /// changed notes; notes not embedded yet are found by their gist only.
+    fn ensure_chunk_index(&mut self) {
+        let index_dir = self.vault_path.join(CHUNK_INDEX_DIR);
+        let stamp = plugin_index::index_stamp(&self.vault_path);
+        if self.chunks_stamp != Some(stamp) {
+            let (chunks, _rebuilt) = ChunkIndex::open(&index_dir, self.semantic.embedding_mode());
+            self.chunks = Some(chunks);
+            self.chunks_stamp = Some(stamp);
+            self.chunks_pending = true;
+        }
+        let Some(chunks) = self.chunks.as_mut().filter(|_| self.chunks_pending) else {
+            return;
+        };
+
+        match chunks.sync(
+            &self.vault_path,
+            &index_dir,
+            self.semantic.embedder(),
+            Some(CHUNK_SYNC_LIMIT),
+        ) {
+            Ok(stats) => self.chunks_pending = stats.pending > 0,
+            Err(e) => {
+                eprintln!(
+                    "Warning: Chunk index at {} could not be synced ({}). Searching stored passages and gists only.",
+                    index_dir.display(),
+                    e
+                );
+                self.chunks_pending = false;
             }
         }
+    }
+
+    /// Search result for a note from the plugin index, or the chunk index
+    /// for notes the plugin did not export
+    fn result_for(&self, path: &str, score: f32) -> Option<SearchResult> {
+        let title = path
+            .rsplit('/')
+            .next()
+            .unwrap_or(path)
+            .trim_end_matches(".md")
+            .to_string();
+
+        if let Some(note) = self.semantic.get_note(path) {
+            return Some(SearchResult {
+                id: path.to_string(),
+                path: path.to_string(),
+                title,
+                gist: Some(note.gist.clone()),
+                note_type: note.fields.get("type").cloned(),
+                area: note.fields.get("area").cloned(),
+                score,
+                heading: None,
+            });
+        }
+
+        let note = self.chunks.as_ref()?.note(path)?;
+        Some(SearchResult {
+            id: path.to_string(),
+            path: path.to_string(),
+            title,
+            gist: None,
+            note_type: note.fields.get("type").cloned(),
+            area: note.fields.get("area").cloned(),
+            score,
+            heading: None,
+        })
+    }
 
-        Ok(search_results)
+    /// Convert BM25 results to SearchResult
+    fn convert_bm25_results(&self, results: Vec<(String, f32)>) -> Result<Vec<SearchResult>> {
+        Ok(results
+            .into_iter()
+            .filter_map(|(path, score)| self.result_for(&path, score))
+            .collect())
     }
 
     /// Convert fused RRF results to SearchResult
@@ -306,29 +924,11 @@ impl HybridSearchEngine {
         results: Vec<(String, f32)>,
         limit: usize,
     ) -> Result<Vec<SearchResult>> {
-        let mut search_results = Vec::with_capacity(limit.min(results.len()));
-
-        for (path, score) in results.into_iter().take(limit) {
-            if let Some(note) = self.semantic.get_note(&path) {
-                search_results.push(SearchResult {
-                    id: path.clone(),
-                    path,
-                    title: note
-                        .path
-                        .rsplit('/')
-                        .next()
-                        .unwrap_or(&note.path)
-                        .trim_end_matches(".md")
-                        .to_string(),
-                    gist: Some(note.gist.clone()),
-                    note_type: note.fields.get("type").cloned(),
-                    area: note.fields.get("area").cloned(),
-                    score,
-                });
-            }
-        }
-
-        Ok(search_results)
+        Ok(results
+            .into_iter()
+            .filter_map(|(path, score)| self.result_for(&path, score))
+            .take(limit)
+            .collect())
     }
 
     /// Get semantic engine reference
@@ -446,6 +1046,7 @@ mod tests {
             bm25_weight: 0.5,
             semantic_weight: 0.5,
             rrf_k: 60,
+            ..Default::default()
         };
 
         // Single doc in both lists at rank 1
@@ -458,4 +1059,92 @@ mod tests {
         let expected_score = 1.0 / 61.0;
         assert!((result[0].1 - expected_score).abs() < 0.0001);
     }
+
+    #[test]
+    fn test_fuse_explained() {
+        let config = HybridConfig::default();
+        let semantic = vec![("doc1".to_string(), 0.9), ("doc2".to_string(), 0.7)];
+        let bm25 = vec![("doc2".to_string(), 4.0)];
+
+        let result = fuse(semantic, bm25, &config, FusionMethod::Rrf);
+        let k = config.rrf_k as f32;
+
+        assert_eq!(result[0].path, "doc2");
+        assert_eq!(result[0].semantic, Some((2, 0.7)));
+        assert_eq!(result[0].bm25, Some((1, 4.0)));
+        assert!(
+            (result[0].semantic_contribution - config.semantic_weight / (k + 2.0)).abs() < 1e-6
+        );
+        assert!((result[0].bm25_contribution - config.bm25_weight / (k + 1.0)).abs() < 1e-6);
+        assert_eq!(
+            result[0].score,
+            result[0].semantic_contribution + result[0].bm25_contribution
+        );
+        assert_eq!(result[1].bm25, None);
+        assert_eq!(result[1].bm25_contribution, 0.0);
+    }
+
+    #[test]
+    fn test_fuse_normalized_methods() {
+        let config = HybridConfig::with_weights(0.6, 0.4);
+        // BM25 strongly prefers doc2; semantic slightly prefers doc1
+        let semantic = vec![("doc1".to_string(), 0.80), ("doc2".to_string(), 0.78)];
+        let bm25 = vec![
+            ("doc2".to_string(), 12.0),
+            ("doc1".to_string(), 3.0),
+            ("doc3".to_string(), 2.0),
+        ];
+
+        let min_max = fuse(
+            semantic.clone(),
+            bm25.clone(),
+            &config,
+            FusionMethod::MinMax,
+        );
+        assert_eq!(min_max[0].path, "doc2");
+        assert!((min_max[0].bm25_contribution - 0.6).abs() < 1e-6);
+        assert!(min_max[0].semantic_contribution.abs() < 1e-6);
+        // doc3 is missing from semantic results: contributes nothing there
+        let doc3 = min_max.iter().find(|f| f.path == "doc3").unwrap();
+        assert_eq!(doc3.semantic, None);
+        assert_eq!(doc3.semantic_contribution, 0.0);
+
+        let z_score = fuse(
+            semantic.clone(),
+            bm25.clone(),
+            &config,
+            FusionMethod::ZScore,
+        );
+        assert_eq!(z_score[0].path, "doc2");
+        // Missing from semantic: the lowest semantic z-score (doc2's, -1)
+        let doc3 = z_score.iter().find(|f| f.path == "doc3").unwrap();
+        assert!((doc3.semantic_contribution + 0.4).abs() < 1e-4);
+
+        let dbsf = fuse(semantic, bm25, &config, FusionMethod::Dbsf);
+        assert_eq!(dbsf[0].path, "doc2");
+        assert!(dbsf
+            .iter()
+            .all(|f| (0.0..=1.0).contains(&f.score) && f.score > 0.0));
+
+        assert_eq!(FusionMethod::parse("Min-Max"), Some(FusionMethod::MinMax));
+        assert_eq!(FusionMethod::parse("zscore"), Some(FusionMethod::ZScore));
+        assert_eq!(FusionMethod::parse("borda"), None);
+    }
+
+    #[test]
+    fn test_config_from_ranking() {
+        let ranking: RankingConfig =
+            serde_json::from_str(r#"{"fusion": "dbsf", "semanticWeight": 0.4}"#).unwrap();
+        let config = HybridConfig::from_ranking(&ranking);
+        assert_eq!(config.fusion, FusionMethod::Dbsf);
+        assert_eq!(config.semantic_weight, 0.4);
+        assert_eq!(config.bm25_weight, 0.3);
+        assert_eq!(config.rrf_k, 60);
+
+        let ranking: RankingConfig = serde_json::from_str(r#"{"fusion": "nope"}"#).unwrap();
+        assert_eq!(
+            HybridConfig::from_ranking(&ranking).fusion,
+            FusionMethod::Rrf
+        );
+    }
 }
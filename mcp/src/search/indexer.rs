@@ -0,0 +1,309 @@
+//! Headless Indexer - build the plugin index without Obsidian
+//!
+//! Scans the vault, embeds each note's gist and writes the same files the
+//! Obsidian plugin exports (plugin index contract v1, see `docs/contracts/`):
+//! - hnsw.bin: HNSW vector index (bincode serialized)
+//! - notes.json: Note metadata (path, gist, fields, tags)
+//! - meta.json: Index metadata (embedding mode, dimension, timestamp)
+//!
+//! It also syncs the chunk index (passage embeddings), so searches after a
+//! reindex need not embed passages themselves.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use super::chunk_index::{ChunkIndex, CHUNK_INDEX_DIR};
+use super::embedder::Embedder;
+use super::plugin_index::{
+    create_embedder_for_mode, HnswIndex, IndexMeta, NoteExport, PluginIndexWriter,
+    PLUGIN_INDEX_VERSION,
+};
+use crate::core::config::Config;
+use crate::core::frontmatter::FieldValue;
+use crate::core::note::{collect_all_notes, Note};
+use crate::core::paths::{relative_path, VaultPaths};
+
+/// Number of texts embedded per batch
+const EMBED_BATCH_SIZE: usize = 64;
+
+/// Index build statistics
+#[derive(Debug, Clone)]
+pub struct IndexBuildStats {
+    pub note_count: usize,
+    pub embedding_mode: String,
+    pub dimension: usize,
+    pub index_size: usize,
+    pub exported_at: u64,
+    /// Notes (re-)embedded into the chunk index
+    pub chunked_notes: usize,
+    pub duration_ms: u128,
+}
+
+/// Builds and exports the plugin-compatible index from the vault on disk
+pub struct VaultIndexer {
+    vault_path: PathBuf,
+    embedding_mode: String,
+    embedder: Box<dyn Embedder>,
+}
+
+impl VaultIndexer {
+    /// Create indexer using the embedding mode selected in vault config
+    ///
+    /// Model2Vec is used when advanced semantic search is enabled and the
+    /// model has been downloaded, HTP otherwise (same rule as the plugin).
+    pub fn new(vault_path: &Path) -> Result<Self> {
+        let config = Config::load(vault_path);
+        let mode = if config.features.is_advanced_search_ready() {
+            "model2vec"
+        } else {
+            "htp"
+        };
+        Self::with_mode(vault_path, mode)
+    }
+
+    /// Create indexer with an explicit embedding mode ("htp" or "model2vec")
+    pub fn with_mode(vault_path: &Path, embedding_mode: &str) -> Result<Self> {
+        let embedding_mode = embedding_mode.to_lowercase();
+        if embedding_mode != "htp" && embedding_mode != "model2vec" {
+            anyhow::bail!(
+                "Unknown embedding mode '{}'. Use: htp, model2vec",
+                embedding_mode
+            );
+        }
+
+        let embedder = create_embedder_for_mode(vault_path, &embedding_mode)
+            .with_context(|| format!("Failed to create {} embedder", embedding_mode))?;
+
+        Ok(Self {
+            vault_path: vault_path.to_path_buf(),
+            embedding_mode,
+            embedder,
+        })
+    }
+
+    /// Scan the vault, rebuild the HNSW index and write all index files
+    pub fn build(&self) -> Result<IndexBuildStats> {
+        let start = Instant::now();
+
+        let vault_paths = VaultPaths::from_root(self.vault_path.clone());
+        let notes = collect_all_notes(&vault_paths);
+
+        let gist_enabled = vault_paths.config.schema.gist.enabled;
+        let mut records: Vec<NoteExport> = notes
+            .iter()
+            .map(|note| self.note_record(note, gist_enabled))
+            .collect();
+        records.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let mut hnsw = HnswIndex::new();
+        for batch in records.chunks(EMBED_BATCH_SIZE) {
+            let texts: Vec<&str> = batch.iter().map(|r| r.gist.as_str()).collect();
+            let vectors = self
+                .embedder
+                .embed_batch(&texts)
+                .context("Failed to embed note gists")?;
+            for (record, vector) in batch.iter().zip(vectors) {
+                hnsw.insert(record.path.clone(), vector);
+            }
+        }
+
+        let hnsw_data = hnsw.serialize()?;
+        let exported_at = chrono::Utc::now().timestamp_millis().max(0) as u64;
+
+        let meta = IndexMeta {
+            embedding_mode: self.embedding_mode.clone(),
+            dimension: self.embedder.dimension(),
+            note_count: records.len(),
+            index_size: hnsw_data.len(),
+            exported_at,
+            version: PLUGIN_INDEX_VERSION,
+        };
+
+        PluginIndexWriter::new(&self.vault_path).write(&meta, &records, &hnsw_data)?;
+
+        let (_, chunk_stats) = ChunkIndex::open_or_update(
+            &self.vault_path,
+            &self.vault_path.join(CHUNK_INDEX_DIR),
+            self.embedder.as_ref(),
+            &self.embedding_mode,
+        )
+        .context("Failed to sync chunk index")?;
+
+        Ok(IndexBuildStats {
+            note_count: meta.note_count,
+            embedding_mode: meta.embedding_mode,
+            dimension: meta.dimension,
+            index_size: meta.index_size,
+            exported_at,
+            chunked_notes: chunk_stats.embedded,
+            duration_ms: start.elapsed().as_millis(),
+        })
+    }
+
+    /// Build the notes.json record for a note
+    ///
+    /// Matches the plugin: the gist is the embedded text when gists are
+    /// enabled in the schema config (falling back to the filename), fields
+    /// hold every elysium_* key except gist.
+    fn note_record(&self, note: &Note, gist_enabled: bool) -> NoteExport {
+        let path = relative_path(&self.vault_path, &note.path);
+
+        let gist = match note.gist() {
+            Some(g) if gist_enabled && !g.trim().is_empty() => g.to_string(),
+            _ => note.name.replace(['-', '_'], " "),
+        };
+
+        // Contract fields are strings or string arrays; typed YAML values
+        // (numbers, booleans) are exported as text, nested maps are skipped
+        let fields = note
+            .frontmatter
+            .as_ref()
+            .map(|fm| {
+                fm.fields
+                    .iter()
+                    .filter(|(k, _)| k.as_str() != "gist")
+                    .filter_map(|(k, v)| {
+                        let value = match v {
+                            FieldValue::List(_) => serde_json::json!(v.as_string_list()?),
+                            _ => serde_json::Value::String(v.to_string_value()?),
+                        };
+                        Some((k.clone(), value))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let tags = note.frontmatter.as_ref().and_then(|fm| fm.get_list("tags"));
+
+        NoteExport {
+            path,
+            gist,
+            mtime: note.modified.timestamp_millis().max(0) as u64,
+            indexed: true,
+            fields,
+            tags,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::PLUGIN_CONFIG_PATH;
+    use crate::search::plugin_index::{PluginIndexReader, PluginSearchEngine, PLUGIN_INDEX_DIR};
+    use crate::test_support::write_note;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn setup_vault() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        write_note(
+            temp.path(),
+            "Notes/GPU Memory.md",
+            "---\nelysium_type: note\nelysium_area: tech\nelysium_gist: GPU memory sharing between processes\nelysium_tags: [gpu, cuda]\n---\n# GPU\n",
+        );
+        write_note(
+            temp.path(),
+            "cooking-recipes.md",
+            "---\nelysium_type: note\nelysium_area: life\n---\nPasta.\n",
+        );
+        write_note(temp.path(), ".hidden/skip.md", "# Hidden\n");
+        write_note(
+            temp.path(),
+            PLUGIN_CONFIG_PATH,
+            r#"{"schema": {"gist": {"enabled": true}}}"#,
+        );
+        temp
+    }
+
+    #[test]
+    fn test_build_writes_contract_files() -> Result<()> {
+        let temp = setup_vault();
+        let stats = VaultIndexer::with_mode(temp.path(), "htp")?.build()?;
+
+        assert_eq!(stats.note_count, 2);
+        assert_eq!(stats.dimension, 384);
+        assert_eq!(stats.chunked_notes, 2);
+        assert!(temp.path().join(CHUNK_INDEX_DIR).exists());
+
+        let reader = PluginIndexReader::new(temp.path());
+        assert!(reader.exists());
+
+        let meta = reader.load_meta()?;
+        assert_eq!(meta.version, PLUGIN_INDEX_VERSION);
+        assert_eq!(meta.embedding_mode, "htp");
+        assert_eq!(meta.note_count, 2);
+
+        let notes = reader.load_notes()?;
+        let gpu = notes
+            .iter()
+            .find(|n| n.path == "Notes/GPU Memory.md")
+            .unwrap();
+        assert_eq!(gpu.gist, "GPU memory sharing between processes");
+        assert_eq!(gpu.fields.get("type").map(String::as_str), Some("note"));
+        assert!(!gpu.fields.contains_key("gist"));
+        assert_eq!(
+            gpu.tags.as_deref(),
+            Some(&["gpu".to_string(), "cuda".to_string()][..])
+        );
+
+        // No gist: falls back to filename as search text
+        let recipes = notes
+            .iter()
+            .find(|n| n.path == "cooking-recipes.md")
+            .unwrap();
+        assert_eq!(recipes.gist, "cooking recipes");
+
+        // notes.json keeps list fields as arrays
+        let raw: serde_json::Value = serde_json::from_str(&fs::read_to_string(
+            temp.path().join(PLUGIN_INDEX_DIR).join("notes.json"),
+        )?)?;
+        assert!(raw[0]["fields"]["tags"].is_array());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_ignores_gist_when_disabled() -> Result<()> {
+        let temp = setup_vault();
+        fs::remove_file(temp.path().join(PLUGIN_CONFIG_PATH))?;
+        VaultIndexer::with_mode(temp.path(), "htp")?.build()?;
+
+        let notes = PluginIndexReader::new(temp.path()).load_notes()?;
+        let gpu = notes
+            .iter()
+            .find(|n| n.path == "Notes/GPU Memory.md")
+            .unwrap();
+        assert_eq!(gpu.gist, "GPU Memory");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_built_index_is_searchable() -> Result<()> {
+        let temp = setup_vault();
+        VaultIndexer::with_mode(temp.path(), "htp")?.build()?;
+
+        let engine = PluginSearchEngine::load(temp.path())?;
+        let results = engine.search("GPU memory sharing", 2)?;
+        assert_eq!(results[0].path, "Notes/GPU Memory.md");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_mode_rejected() {
+        let temp = setup_vault();
+        assert!(VaultIndexer::with_mode(temp.path(), "openai").is_err());
+    }
+
+    #[test]
+    fn test_relative_path() {
+        let root = Path::new("/vault");
+        assert_eq!(
+            relative_path(root, Path::new("/vault/Notes/A.md")),
+            "Notes/A.md"
+        );
+    }
+}
@@ -0,0 +1,753 @@
+//! Keyword query syntax for BM25 search
+//!
+//! ```text
+//! "gpu sharing" AND (cuda OR title:mig*) NOT draft
+//! ```
+//!
+//! - Words match in the title, gist, tags or body. Adjacent words are OR-ed;
+//!   notes matching more of them rank higher
+//! - `"quoted phrase"` matches the words in order
+//! - `a AND b` requires both, `a OR b` either; `AND` binds tighter than `OR`
+//! - `NOT a` or `-a` excludes notes matching `a`
+//! - `( ... )` groups
+//! - `title:`, `gist:`, `tags:` or `body:` limits a word, phrase or group to
+//!   one field
+//! - `word*` matches words starting with `word`
+//!
+//! Operators are upper case (`and`, `or` and `not` are plain words). Errors
+//! carry the character position they refer to; `KeywordQuery::escaped`
+//! searches the words literally instead (lenient fallback).
+
+use super::query::QueryError;
+use super::tokenizer::is_cjk;
+use crate::core::frontmatter::FrontmatterEditor;
+use crate::core::note::Note;
+
+/// Text fields a query can be limited to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeywordField {
+    Title,
+    Gist,
+    Tags,
+    Body,
+}
+
+impl KeywordField {
+    pub const ALL: [KeywordField; 4] = [
+        KeywordField::Title,
+        KeywordField::Gist,
+        KeywordField::Tags,
+        KeywordField::Body,
+    ];
+
+    /// Parse a field name (None if unknown)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "title" => Some(KeywordField::Title),
+            "gist" => Some(KeywordField::Gist),
+            "tags" | "tag" => Some(KeywordField::Tags),
+            "body" => Some(KeywordField::Body),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeywordField::Title => "title",
+            KeywordField::Gist => "gist",
+            KeywordField::Tags => "tags",
+            KeywordField::Body => "body",
+        }
+    }
+}
+
+/// Lowercased text of a note by field, to check query parts without the
+/// index (whitespace collapsed so that phrases match across line breaks)
+pub struct NoteFields {
+    title: String,
+    gist: String,
+    tags: String,
+    body: String,
+}
+
+impl NoteFields {
+    pub fn of(note: &Note) -> Self {
+        Self {
+            title: normalize(&note.name),
+            gist: normalize(note.gist().unwrap_or_default()),
+            tags: normalize(&note.tags().join(" ")),
+            body: normalize(FrontmatterEditor::new(&note.content).body()),
+        }
+    }
+
+    /// Whether `text` appears as whole words in `field` (any field if
+    /// None); with `prefix`, its last word may continue
+    pub fn contains(&self, field: Option<KeywordField>, text: &str, prefix: bool) -> bool {
+        let text = normalize(text);
+        match field {
+            Some(field) => contains_words(self.field(field), &text, prefix),
+            None => KeywordField::ALL
+                .iter()
+                .any(|&field| contains_words(self.field(field), &text, prefix)),
+        }
+    }
+
+    fn field(&self, field: KeywordField) -> &str {
+        match field {
+            KeywordField::Title => &self.title,
+            KeywordField::Gist => &self.gist,
+            KeywordField::Tags => &self.tags,
+            KeywordField::Body => &self.body,
+        }
+    }
+}
+
+/// Parsed keyword query
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeywordNode {
+    /// A word, in one field or all (`prefix`: `word*`)
+    Term {
+        text: String,
+        field: Option<KeywordField>,
+        prefix: bool,
+    },
+    /// Words in order
+    Phrase {
+        text: String,
+        field: Option<KeywordField>,
+    },
+    /// At least one must match
+    Or(Vec<KeywordNode>),
+    /// All must match
+    And(Vec<KeywordNode>),
+    /// Excludes matching notes from the enclosing group
+    Not(Box<KeywordNode>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeywordQuery {
+    pub root: KeywordNode,
+}
+
+impl KeywordQuery {
+    /// Parse the keyword syntax
+    pub fn parse(query: &str) -> Result<Self, QueryError> {
+        Parser::new(query, false)?.parse()
+    }
+
+    /// Parse the keyword syntax as a condition on notes (for listing)
+    ///
+    /// Adjacent words are all required, and a query of only excluded terms
+    /// is allowed.
+    pub fn parse_condition(query: &str) -> Result<Self, QueryError> {
+        Parser::new(query, true)?.parse()
+    }
+
+    /// Every word as a plain term: operators, quotes, parentheses, field
+    /// scopes and wildcards are ignored
+    pub fn escaped(query: &str) -> Self {
+        let terms = query
+            .split(|c: char| c.is_whitespace() || matches!(c, '"' | '(' | ')' | '*' | ':'))
+            .map(|w| w.trim_start_matches('-'))
+            .filter(|w| !w.is_empty())
+            .map(|w| KeywordNode::Term {
+                text: w.to_string(),
+                field: None,
+                prefix: false,
+            })
+            .collect();
+        Self {
+            root: KeywordNode::Or(terms),
+        }
+    }
+
+    /// Parse, falling back to `escaped` on a syntax error (returned too)
+    pub fn parse_lenient(query: &str) -> (Self, Option<QueryError>) {
+        match Self::parse(query) {
+            Ok(parsed) => (parsed, None),
+            Err(e) => (Self::escaped(query), Some(e)),
+        }
+    }
+
+    /// Words and phrases searched for (not excluded), without syntax, for
+    /// semantic search
+    pub fn text(&self) -> String {
+        let mut words = Vec::new();
+        collect_text(&self.root, &mut words);
+        words.join(" ")
+    }
+
+    /// Whether the query excludes or requires anything (`NOT`, `AND`)
+    ///
+    /// Plain words only rank, so semantic matches without them still count.
+    pub fn has_constraints(&self) -> bool {
+        match &self.root {
+            KeywordNode::And(_) | KeywordNode::Not(_) => true,
+            KeywordNode::Or(nodes) => nodes.iter().any(|n| matches!(n, KeywordNode::Not(_))),
+            KeywordNode::Term { .. } | KeywordNode::Phrase { .. } => false,
+        }
+    }
+
+    /// Whether a note passes the query's exclusions and `AND` requirements
+    ///
+    /// Keyword search enforces these itself; semantic results are checked
+    /// with this, by substring, so `NOT` and `AND` hold in every mode.
+    pub fn admits(&self, fields: &NoteFields) -> bool {
+        match &self.root {
+            KeywordNode::Or(nodes) => nodes.iter().all(|node| match node {
+                KeywordNode::Not(inner) => !matches_node(inner, fields),
+                _ => true,
+            }),
+            KeywordNode::Term { .. } | KeywordNode::Phrase { .. } => true,
+            root => matches_node(root, fields),
+        }
+    }
+
+    /// Whether a note matches the whole query (an empty query matches all)
+    pub fn matches(&self, fields: &NoteFields) -> bool {
+        match &self.root {
+            KeywordNode::Or(nodes) if nodes.is_empty() => true,
+            root => matches_node(root, fields),
+        }
+    }
+}
+
+/// Boolean match of a node against a note's text
+fn matches_node(node: &KeywordNode, fields: &NoteFields) -> bool {
+    let group = |nodes: &[KeywordNode], all: bool| {
+        let excluded = nodes.iter().any(|n| match n {
+            KeywordNode::Not(inner) => matches_node(inner, fields),
+            _ => false,
+        });
+        let mut positive = nodes
+            .iter()
+            .filter(|n| !matches!(n, KeywordNode::Not(_)))
+            .peekable();
+        // A group of only exclusions (allowed in conditions) excludes alone
+        let found = if all || positive.peek().is_none() {
+            positive.all(|n| matches_node(n, fields))
+        } else {
+            positive.any(|n| matches_node(n, fields))
+        };
+        found && !excluded
+    };
+    match node {
+        KeywordNode::Term {
+            text,
+            field,
+            prefix,
+        } => fields.contains(*field, text, *prefix),
+        KeywordNode::Phrase { text, field } => fields.contains(*field, text, false),
+        KeywordNode::Or(nodes) => group(nodes, false),
+        KeywordNode::And(nodes) => group(nodes, true),
+        KeywordNode::Not(inner) => !matches_node(inner, fields),
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Substring match at word boundaries, like a token match in the index
+///
+/// CJK text is indexed as bigrams, so a CJK edge needs no boundary.
+fn contains_words(text: &str, words: &str, prefix: bool) -> bool {
+    let open = |neighbour: Option<char>, edge: Option<char>| {
+        neighbour.is_none_or(|c| !c.is_alphanumeric())
+            || edge.is_none_or(|c| !c.is_alphanumeric() || is_cjk(c))
+    };
+    text.match_indices(words).any(|(i, _)| {
+        let before = text[..i].chars().next_back();
+        let after = text[i + words.len()..].chars().next();
+        open(before, words.chars().next()) && (prefix || open(after, words.chars().next_back()))
+    })
+}
+
+fn collect_text<'q>(node: &'q KeywordNode, words: &mut Vec<&'q str>) {
+    match node {
+        KeywordNode::Term { text, .. } | KeywordNode::Phrase { text, .. } => words.push(text),
+        KeywordNode::Or(nodes) | KeywordNode::And(nodes) => {
+            for node in nodes {
+                collect_text(node, words);
+            }
+        }
+        KeywordNode::Not(_) => {}
+    }
+}
+
+fn error(position: usize, message: impl Into<String>) -> QueryError {
+    QueryError {
+        position,
+        message: message.into(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word { text: String, prefix: bool },
+    Phrase(String),
+    Field(KeywordField),
+    Open,
+    Close,
+    And,
+    Or,
+    Not,
+}
+
+struct Lexer {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Lexer {
+    fn new(query: &str) -> Self {
+        Self {
+            chars: query.chars().collect(),
+            pos: 0,
+        }
+    }
+
+    /// Tokens with their positions
+    fn tokens(mut self) -> Result<Vec<(Token, usize)>, QueryError> {
+        let mut tokens = Vec::new();
+        loop {
+            while self.peek().is_some_and(char::is_whitespace) {
+                self.pos += 1;
+            }
+            let position = self.pos;
+            let Some(c) = self.peek() else {
+                return Ok(tokens);
+            };
+            match c {
+                '(' => {
+                    self.pos += 1;
+                    tokens.push((Token::Open, position));
+                }
+                ')' => {
+                    self.pos += 1;
+                    tokens.push((Token::Close, position));
+                }
+                '"' => tokens.push((Token::Phrase(self.quoted()?), position)),
+                '-' if self
+                    .peek_at(1)
+                    .is_some_and(|n| !n.is_whitespace() && n != ')') =>
+                {
+                    self.pos += 1;
+                    tokens.push((Token::Not, position));
+                }
+                _ => self.word(&mut tokens)?,
+            }
+        }
+    }
+
+    /// A word, operator or `field:` (followed by its value)
+    fn word(&mut self, tokens: &mut Vec<(Token, usize)>) -> Result<(), QueryError> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| !c.is_whitespace() && !matches!(c, '(' | ')' | '"'))
+        {
+            self.pos += 1;
+        }
+        let mut text_start = start;
+        let colon = self.chars[start..self.pos].iter().position(|&c| c == ':');
+        if let Some(colon) = colon {
+            let key: String = self.chars[start..start + colon].iter().collect();
+            if let Some(field) = KeywordField::parse(&key) {
+                tokens.push((Token::Field(field), start));
+                text_start = start + colon + 1;
+                if text_start == self.pos && !matches!(self.peek(), Some('(') | Some('"')) {
+                    return Err(error(text_start, format!("Missing value after {}:", key)));
+                }
+            }
+        }
+        if text_start == self.pos {
+            return Ok(());
+        }
+
+        let text: String = self.chars[text_start..self.pos].iter().collect();
+        let token = match text.as_str() {
+            "AND" if text_start == start => Token::And,
+            "OR" if text_start == start => Token::Or,
+            "NOT" if text_start == start => Token::Not,
+            _ => {
+                let prefix = text.ends_with('*');
+                let word = text.strip_suffix('*').unwrap_or(&text);
+                if let Some(i) = word.chars().position(|c| c == '*') {
+                    return Err(error(
+                        text_start + i,
+                        "Wildcards are only supported at the end of a word (e.g. gpu*)",
+                    ));
+                }
+                if word.is_empty() {
+                    return Err(error(text_start, "Wildcard needs a word before *"));
+                }
+                Token::Word {
+                    text: word.to_string(),
+                    prefix,
+                }
+            }
+        };
+        tokens.push((token, text_start));
+        Ok(())
+    }
+
+    /// Text between double quotes (the cursor is on the opening quote)
+    fn quoted(&mut self) -> Result<String, QueryError> {
+        let open = self.pos;
+        self.pos += 1;
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c == '"' {
+                let text: String = self.chars[start..self.pos].iter().collect();
+                self.pos += 1;
+                if text.trim().is_empty() {
+                    return Err(error(open, "Empty phrase"));
+                }
+                return Ok(text);
+            }
+            self.pos += 1;
+        }
+        Err(error(open, "Unterminated quote"))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+}
+
+/// Recursive descent: `or := and (OR? and)*`, `and := unary (AND unary)*`,
+/// `unary := NOT unary | field? (word | phrase | "(" or ")")`; in condition
+/// mode `and := unary (AND? unary)*`
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    /// Query length, the position of errors at the end
+    end: usize,
+    /// Condition mode: adjacent terms are AND-ed and exclusions may stand
+    /// alone
+    condition: bool,
+}
+
+impl Parser {
+    fn new(query: &str, condition: bool) -> Result<Self, QueryError> {
+        Ok(Self {
+            tokens: Lexer::new(query).tokens()?,
+            pos: 0,
+            end: query.chars().count(),
+            condition,
+        })
+    }
+
+    fn parse(mut self) -> Result<KeywordQuery, QueryError> {
+        if self.tokens.is_empty() {
+            return Ok(KeywordQuery {
+                root: KeywordNode::Or(Vec::new()),
+            });
+        }
+        let root = self.disjunction(None)?;
+        if let Some((_, position)) = self.next() {
+            // `disjunction` only stops early at a closing parenthesis
+            return Err(error(position, "Unmatched )"));
+        }
+        if !self.condition && matches!(root, KeywordNode::Not(_)) {
+            return Err(error(0, "Query has only excluded terms"));
+        }
+        Ok(KeywordQuery { root })
+    }
+
+    fn disjunction(&mut self, field: Option<KeywordField>) -> Result<KeywordNode, QueryError> {
+        let start = self.position();
+        let mut any = vec![self.conjunction(field)?];
+        loop {
+            match self.peek() {
+                None | Some(Token::Close) => break,
+                Some(Token::Or) => {
+                    let position = self.position();
+                    self.pos += 1;
+                    self.expect_operand(position, "OR")?;
+                }
+                _ => {}
+            }
+            any.push(self.conjunction(field)?);
+        }
+        self.group(any, KeywordNode::Or, start)
+    }
+
+    fn conjunction(&mut self, field: Option<KeywordField>) -> Result<KeywordNode, QueryError> {
+        let start = self.position();
+        let mut all = vec![self.unary(field)?];
+        loop {
+            match self.peek() {
+                Some(Token::And) => {
+                    let position = self.position();
+                    self.pos += 1;
+                    self.expect_operand(position, "AND")?;
+                }
+                None | Some(Token::Close) | Some(Token::Or) => break,
+                Some(_) if self.condition => {}
+                Some(_) => break,
+            }
+            all.push(self.unary(field)?);
+        }
+        self.group(all, KeywordNode::And, start)
+    }
+
+    fn unary(&mut self, field: Option<KeywordField>) -> Result<KeywordNode, QueryError> {
+        let Some((token, position)) = self.next() else {
+            return Err(error(self.end, "Query ends unexpectedly"));
+        };
+        match token {
+            Token::Not => {
+                if matches!(self.peek(), Some(Token::Not)) {
+                    return Err(error(self.position(), "NOT cannot follow NOT"));
+                }
+                self.expect_operand(position, "NOT")?;
+                Ok(KeywordNode::Not(Box::new(self.unary(field)?)))
+            }
+            // The lexer guarantees a value follows
+            Token::Field(scope) => self.unary(Some(scope)),
+            Token::Open => {
+                if matches!(self.peek(), Some(Token::Close)) {
+                    return Err(error(position, "Empty group ()"));
+                }
+                let inner = self.disjunction(field)?;
+                match self.next() {
+                    Some((Token::Close, _)) => Ok(inner),
+                    _ => Err(error(position, "Unclosed (")),
+                }
+            }
+            Token::Word { text, prefix } => Ok(KeywordNode::Term {
+                text,
+                field,
+                prefix,
+            }),
+            Token::Phrase(text) => Ok(KeywordNode::Phrase { text, field }),
+            Token::Close => Err(error(position, "Unmatched )")),
+            Token::And => Err(error(position, "AND needs a term on both sides")),
+            Token::Or => Err(error(position, "OR needs a term on both sides")),
+        }
+    }
+
+    /// An operator must be followed by a term, phrase, group or NOT
+    fn expect_operand(&self, position: usize, operator: &str) -> Result<(), QueryError> {
+        match self.peek() {
+            None | Some(Token::Close) | Some(Token::And) | Some(Token::Or) => {
+                let message = if operator == "NOT" {
+                    "NOT needs a term after it".to_string()
+                } else {
+                    format!("{} needs a term on both sides", operator)
+                };
+                Err(error(position, message))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn next(&mut self) -> Option<(Token, usize)> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(t, _)| t)
+    }
+
+    /// Position of the next token (query end if none)
+    fn position(&self) -> usize {
+        self.tokens.get(self.pos).map_or(self.end, |(_, p)| *p)
+    }
+
+    /// One node, or a group that must search for something besides
+    /// exclusions
+    fn group(
+        &self,
+        mut nodes: Vec<KeywordNode>,
+        make: fn(Vec<KeywordNode>) -> KeywordNode,
+        start: usize,
+    ) -> Result<KeywordNode, QueryError> {
+        if nodes.len() == 1 {
+            return Ok(nodes.remove(0));
+        }
+        if !self.condition && nodes.iter().all(|n| matches!(n, KeywordNode::Not(_))) {
+            return Err(error(start, "Query has only excluded terms"));
+        }
+        Ok(make(nodes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(text: &str) -> KeywordNode {
+        KeywordNode::Term {
+            text: text.to_string(),
+            field: None,
+            prefix: false,
+        }
+    }
+
+    fn err(query: &str) -> QueryError {
+        KeywordQuery::parse(query).unwrap_err()
+    }
+
+    #[test]
+    fn test_parse_syntax() {
+        let query =
+            KeywordQuery::parse(r#""gpu sharing" AND (cuda OR title:mig*) NOT draft"#).unwrap();
+        assert_eq!(
+            query.root,
+            KeywordNode::Or(vec![
+                KeywordNode::And(vec![
+                    KeywordNode::Phrase {
+                        text: "gpu sharing".to_string(),
+                        field: None,
+                    },
+                    KeywordNode::Or(vec![
+                        term("cuda"),
+                        KeywordNode::Term {
+                            text: "mig".to_string(),
+                            field: Some(KeywordField::Title),
+                            prefix: true,
+                        },
+                    ]),
+                ]),
+                KeywordNode::Not(Box::new(term("draft"))),
+            ])
+        );
+        assert_eq!(query.text(), "gpu sharing cuda mig");
+
+        // Field scopes apply to groups; lowercase operators are words
+        let query = KeywordQuery::parse(r#"tags:(gpu "cuda core") and -body:x"#).unwrap();
+        let KeywordNode::Or(nodes) = &query.root else {
+            panic!("expected OR of the top-level terms");
+        };
+        assert_eq!(
+            nodes[0],
+            KeywordNode::Or(vec![
+                KeywordNode::Term {
+                    text: "gpu".to_string(),
+                    field: Some(KeywordField::Tags),
+                    prefix: false,
+                },
+                KeywordNode::Phrase {
+                    text: "cuda core".to_string(),
+                    field: Some(KeywordField::Tags),
+                },
+            ])
+        );
+        assert_eq!(nodes[1], term("and"));
+
+        // Natural-language queries parse as plain words
+        let query = KeywordQuery::parse("GPU memory sharing - overview: e-mail 16:9").unwrap();
+        assert_eq!(query.text(), "GPU memory sharing - overview: e-mail 16:9");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(err("gpu (cuda OR mig").position, 4);
+        assert!(err("gpu (cuda OR mig").message.contains("Unclosed"));
+        assert_eq!(err("gpu cuda)").position, 8);
+        assert_eq!(err("gpu AND").position, 4);
+        assert!(err("OR gpu").message.contains("both sides"));
+        assert_eq!(err("gpu NOT").message, "NOT needs a term after it");
+        assert_eq!(err("title: gpu").position, 6);
+        assert_eq!(err("g*pu").position, 1);
+        assert!(err("gpu *").message.contains("Wildcard"));
+        assert_eq!(err(r#"a "open"#).position, 2);
+        assert!(err("NOT draft").message.contains("only excluded"));
+        assert!(err("-a -b").message.contains("only excluded"));
+        assert!(err("a ()").message.contains("Empty group"));
+        assert!(err("gpu (cuda").to_string().ends_with("(at column 5)"));
+    }
+
+    #[test]
+    fn test_admits() {
+        let fields = NoteFields {
+            title: "gpu guide".to_string(),
+            gist: String::new(),
+            tags: "draft".to_string(),
+            body: "cuda cores".to_string(),
+        };
+        let query = |q: &str| KeywordQuery::parse(q).unwrap();
+        let admits = |q: &str| query(q).admits(&fields);
+
+        // Plain words only rank
+        assert!(!query("memory gpu").has_constraints());
+        assert!(admits("memory"));
+        assert!(!admits("gpu NOT draft"));
+        assert!(!admits("gpu -tags:draft"));
+        assert!(admits("gpu NOT title:draft"));
+        assert!(!admits("gpu AND memory"));
+        assert!(admits("\"GPU guide\" AND (memory OR cuda)"));
+        // An optional group requires nothing
+        assert!(admits("x OR (memory AND y)"));
+        assert!(!query("x (a AND b)").has_constraints());
+        assert!(query("x AND b").has_constraints());
+    }
+
+    #[test]
+    fn test_matches_condition() {
+        let fields = NoteFields {
+            title: "gpu guide".to_string(),
+            gist: String::new(),
+            tags: "draft".to_string(),
+            body: "cuda cores".to_string(),
+        };
+        let matches = |q: &str| KeywordQuery::parse_condition(q).unwrap().matches(&fields);
+
+        assert!(matches(""));
+        // Adjacent words are all required
+        assert!(matches("gpu cuda"));
+        assert!(!matches("gpu memory"));
+        assert!(matches("memory OR cuda"));
+        assert!(!matches("memory OR mig"));
+        // Exclusions may stand alone
+        assert!(!matches("NOT (draft)"));
+        assert!(matches("NOT (memory) -title:x"));
+        assert!(matches("gpu (memory OR cores) NOT mig"));
+    }
+
+    #[test]
+    fn test_contains_whole_words() {
+        let fields = NoteFields {
+            title: "start here".to_string(),
+            gist: "c++ guide".to_string(),
+            tags: String::new(),
+            body: "gpu를 메모리에서 공유".to_string(),
+        };
+        assert!(!fields.contains(None, "art", false));
+        assert!(fields.contains(None, "star", true));
+        assert!(fields.contains(None, "Start Here", false));
+        assert!(fields.contains(Some(KeywordField::Gist), "c++", false));
+        assert!(!fields.contains(Some(KeywordField::Title), "c++", false));
+        // CJK matches inside words, as bigrams do
+        assert!(fields.contains(None, "메모리", false));
+        assert!(!fields.contains(None, "gp", false));
+    }
+
+    #[test]
+    fn test_escaped() {
+        let (query, error) = KeywordQuery::parse_lenient("c++ (draft OR -title:x*");
+        assert_eq!(error.unwrap().position, 4);
+        assert_eq!(
+            query.root,
+            KeywordNode::Or(vec![
+                term("c++"),
+                term("draft"),
+                term("OR"),
+                term("title"),
+                term("x"),
+            ])
+        );
+    }
+}
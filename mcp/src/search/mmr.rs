@@ -0,0 +1,121 @@
+//! Diversity re-ranking with Maximal Marginal Relevance (MMR)
+//!
+//! Similarity-ranked results often contain near-duplicates (several daily
+//! logs about the same incident). MMR picks results one at a time, trading
+//! relevance against similarity to what was already picked:
+//!
+//! `mmr(d) = λ · relevance(d) − (1 − λ) · max sim(d, picked)`
+//!
+//! with `λ = 1 − diversity`. Relevance is the candidate score min-max scaled
+//! to [0, 1], so it is comparable to cosine similarity whatever the scoring
+//! (cosine, BM25 or fused). Similarity uses the stored gist vectors; a
+//! candidate without a vector is never considered a duplicate.
+
+use super::embedding::cosine_similarity;
+
+/// How many candidates to re-rank for `limit` results
+pub fn candidate_pool(limit: usize) -> usize {
+    (limit * 3).max(limit + 10)
+}
+
+/// Re-rank `candidates` (best first) and keep the top `limit`
+///
+/// `diversity` is clamped to [0, 1]; 0 keeps the relevance order.
+pub fn rerank<'v, T>(
+    candidates: Vec<T>,
+    limit: usize,
+    diversity: f32,
+    score: impl Fn(&T) -> f32,
+    vector: impl Fn(&T) -> Option<&'v [f32]>,
+) -> Vec<T> {
+    let diversity = diversity.clamp(0.0, 1.0);
+    if diversity == 0.0 || candidates.len() <= 1 {
+        let mut candidates = candidates;
+        candidates.truncate(limit);
+        return candidates;
+    }
+    let lambda = 1.0 - diversity;
+
+    let scores: Vec<f32> = candidates.iter().map(&score).collect();
+    let min = scores.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = scores.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let relevance: Vec<f32> = scores
+        .iter()
+        .map(|s| {
+            if max > min {
+                (s - min) / (max - min)
+            } else {
+                1.0
+            }
+        })
+        .collect();
+    let vectors: Vec<Option<&[f32]>> = candidates.iter().map(&vector).collect();
+
+    // Highest similarity of each remaining candidate to the picked ones
+    let mut max_similarity = vec![0.0f32; candidates.len()];
+    let mut remaining: Vec<usize> = (0..candidates.len()).collect();
+    let mut picked = Vec::with_capacity(limit.min(candidates.len()));
+
+    while picked.len() < limit && !remaining.is_empty() {
+        let mmr = |i: usize| lambda * relevance[i] - (1.0 - lambda) * max_similarity[i];
+        // Ties keep the original (relevance) order
+        let (pos, &best) = remaining
+            .iter()
+            .enumerate()
+            .max_by(|(_, &a), (_, &b)| mmr(a).total_cmp(&mmr(b)).then(b.cmp(&a)))
+            .expect("remaining is not empty");
+        remaining.remove(pos);
+        picked.push(best);
+
+        if let Some(best_vector) = vectors[best] {
+            for &i in &remaining {
+                if let Some(v) = vectors[i] {
+                    max_similarity[i] = max_similarity[i].max(cosine_similarity(best_vector, v));
+                }
+            }
+        }
+    }
+
+    let mut slots: Vec<Option<T>> = candidates.into_iter().map(Some).collect();
+    picked.into_iter().filter_map(|i| slots[i].take()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rerank_skips_near_duplicates() {
+        let vectors = [
+            vec![1.0, 0.0],
+            vec![0.99, 0.1],
+            vec![0.98, 0.15],
+            vec![0.0, 1.0],
+        ];
+        let candidates: Vec<(usize, f32)> = vec![(0, 0.9), (1, 0.89), (2, 0.88), (3, 0.7)];
+        let rank = |diversity: f32| -> Vec<usize> {
+            rerank(
+                candidates.clone(),
+                3,
+                diversity,
+                |c| c.1,
+                |c| Some(vectors[c.0].as_slice()),
+            )
+            .into_iter()
+            .map(|c| c.0)
+            .collect()
+        };
+
+        assert_eq!(rank(0.0), vec![0, 1, 2]);
+        // The distinct note moves up right after the best match
+        assert_eq!(rank(0.5), vec![0, 3, 1]);
+        assert_eq!(rank(1.0)[..2], [0, 3]);
+    }
+
+    #[test]
+    fn test_rerank_without_vectors_keeps_order() {
+        let candidates = vec![("a", 3.0), ("b", 2.0), ("c", 1.0)];
+        let ranked = rerank(candidates, 2, 0.7, |c| c.1, |_| None);
+        assert_eq!(ranked, vec![("a", 3.0), ("b", 2.0)]);
+    }
+}
@@ -5,11 +5,22 @@
 //! Phase 3: + Knowledge graph (future)
 
 pub mod bm25;
+pub mod chunk_index;
+pub mod chunker;
 pub mod embedder;
 pub mod embedding;
 pub mod engine;
+pub mod eval;
+pub mod expansion;
+pub mod filter;
 pub mod hybrid;
+pub mod indexer;
+pub mod keyword;
+pub mod mmr;
 pub mod plugin_index;
+pub mod query;
+pub mod related;
+pub mod tokenizer;
 pub mod vectordb;
 
 #[allow(unused_imports)]
@@ -21,8 +32,12 @@ pub use embedding::EmbeddingModel;
 #[allow(unused_imports)]
 pub use engine::{SearchEngine, SearchResult};
 #[allow(unused_imports)]
-pub use hybrid::{HybridConfig, HybridSearchEngine, SearchMode};
+pub use filter::SearchFilter;
 #[allow(unused_imports)]
-pub use plugin_index::{PluginIndexReader, PluginSearchEngine};
+pub use hybrid::{FusionMethod, HybridConfig, HybridSearchEngine, SearchMode, SearchOptions};
+#[allow(unused_imports)]
+pub use indexer::{IndexBuildStats, VaultIndexer};
+#[allow(unused_imports)]
+pub use plugin_index::{PluginIndexReader, PluginIndexWriter, PluginSearchEngine};
 #[allow(unused_imports)]
 pub use vectordb::VectorDB;
@@ -7,10 +7,13 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Deserializer, Serialize};
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::time::SystemTime;
 
 use super::embedder::{create_embedder, SearchConfig};
+use super::filter::{Filterable, SearchFilter};
 
 // ============================================================================
 // HNSW Index (copied from plugin WASM for binary compatibility)
@@ -18,6 +21,25 @@ use super::embedder::{create_embedder, SearchConfig};
 
 pub(crate) const PLUGIN_INDEX_VERSION: u32 = 1;
 
+/// Index export directory (relative to vault root)
+pub const PLUGIN_INDEX_DIR: &str = ".obsidian/plugins/elysium/index";
+/// Model2Vec model directory downloaded by the plugin (relative to vault root)
+pub const PLUGIN_MODEL_DIR: &str = ".obsidian/plugins/elysium/models/potion-multilingual-128M";
+
+/// Modification time of the index `meta.json`, rewritten by every export
+/// (None without an index)
+pub fn index_stamp(vault_path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(vault_path.join(PLUGIN_INDEX_DIR).join("meta.json"))
+        .and_then(|m| m.modified())
+        .ok()
+}
+
+// HNSW construction parameters (must match plugin WASM)
+const M: usize = 16;
+const M_MAX: usize = M;
+const M_MAX_0: usize = M * 2;
+const EF_CONSTRUCTION: usize = 200;
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct HnswIndex {
     nodes: Vec<Node>,
@@ -35,11 +57,31 @@ struct Node {
     neighbors: Vec<Vec<usize>>,
 }
 
+impl Default for HnswIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl HnswIndex {
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            entry_point: None,
+            max_level: 0,
+            id_to_idx: HashMap::new(),
+            deleted: HashSet::new(),
+        }
+    }
+
     pub fn deserialize(data: &[u8]) -> Option<Self> {
         bincode::deserialize(data).ok()
     }
 
+    pub fn serialize(&self) -> Result<Vec<u8>> {
+        bincode::serialize(self).context("Failed to serialize HNSW index")
+    }
+
     pub fn len(&self) -> usize {
         self.nodes.len() - self.deleted.len()
     }
@@ -66,6 +108,93 @@ impl HnswIndex {
         }
     }
 
+    /// Level for a new node.
+    ///
+    /// The plugin draws this from `rand::random`; here it is derived from a
+    /// hash of the id so headless rebuilds are reproducible. Both follow the
+    /// same geometric distribution, so the graph shape is equivalent.
+    fn level_for(id: &str) -> usize {
+        // FNV-1a
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for byte in id.as_bytes() {
+            hash ^= *byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        let r = ((hash >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+        let ml = 1.0 / (M as f64).ln();
+        (-r.ln() * ml).floor() as usize
+    }
+
+    /// Insert a vector (same algorithm as the plugin WASM index)
+    pub fn insert(&mut self, id: String, vector: Vec<f32>) {
+        if let Some(&existing_idx) = self.id_to_idx.get(&id) {
+            self.nodes[existing_idx].vector = vector;
+            return;
+        }
+
+        let level = Self::level_for(&id);
+        let node_idx = self.nodes.len();
+
+        self.nodes.push(Node {
+            id: id.clone(),
+            vector,
+            level,
+            neighbors: vec![Vec::new(); level + 1],
+        });
+        self.id_to_idx.insert(id, node_idx);
+
+        let Some(mut ep) = self.entry_point else {
+            self.entry_point = Some(node_idx);
+            self.max_level = level;
+            return;
+        };
+
+        let query = self.nodes[node_idx].vector.clone();
+
+        for lc in (level + 1..=self.max_level).rev() {
+            ep = self.search_layer_single(&query, ep, lc);
+        }
+
+        for lc in (0..=level.min(self.max_level)).rev() {
+            let m_max = if lc == 0 { M_MAX_0 } else { M_MAX };
+            let candidates = self.search_layer(&query, ep, EF_CONSTRUCTION, lc);
+            let selected = Self::select_neighbors(candidates, m_max);
+
+            self.nodes[node_idx].neighbors[lc] = selected.clone();
+
+            for &neighbor_idx in &selected {
+                if self.deleted.contains(&neighbor_idx) || lc > self.nodes[neighbor_idx].level {
+                    continue;
+                }
+                self.nodes[neighbor_idx].neighbors[lc].push(node_idx);
+                if self.nodes[neighbor_idx].neighbors[lc].len() > m_max {
+                    let neighbor_vec = &self.nodes[neighbor_idx].vector;
+                    let candidates: Vec<(usize, f32)> = self.nodes[neighbor_idx].neighbors[lc]
+                        .iter()
+                        .filter(|n| !self.deleted.contains(n))
+                        .map(|&n| (n, Self::distance(neighbor_vec, &self.nodes[n].vector)))
+                        .collect();
+                    self.nodes[neighbor_idx].neighbors[lc] =
+                        Self::select_neighbors(candidates, m_max);
+                }
+            }
+
+            if let Some(&closest) = selected.first() {
+                ep = closest;
+            }
+        }
+
+        if level > self.max_level {
+            self.max_level = level;
+            self.entry_point = Some(node_idx);
+        }
+    }
+
+    fn select_neighbors(mut candidates: Vec<(usize, f32)>, m: usize) -> Vec<usize> {
+        candidates.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+        candidates.into_iter().take(m).map(|(idx, _)| idx).collect()
+    }
+
     fn search_layer_single(&self, query: &[f32], ep: usize, level: usize) -> usize {
         let mut current = ep;
         let mut current_dist = Self::distance(query, &self.nodes[current].vector);
@@ -93,6 +222,22 @@ impl HnswIndex {
     }
 
     fn search_layer(&self, query: &[f32], ep: usize, ef: usize, level: usize) -> Vec<(usize, f32)> {
+        self.search_layer_filtered(query, ep, ef, level, &|_| true)
+    }
+
+    /// Best-first layer search keeping only accepted nodes as results
+    ///
+    /// Rejected nodes are still traversed so the graph stays connected for
+    /// the search; with an accept-all predicate this is the plain layer
+    /// search used for construction.
+    fn search_layer_filtered(
+        &self,
+        query: &[f32],
+        ep: usize,
+        ef: usize,
+        level: usize,
+        accept: &dyn Fn(usize) -> bool,
+    ) -> Vec<(usize, f32)> {
         use std::cmp::Ordering;
         use std::collections::BinaryHeap;
 
@@ -157,10 +302,12 @@ impl HnswIndex {
             idx: ep,
             distance: dist,
         });
-        results.push(FarCandidate {
-            idx: ep,
-            distance: dist,
-        });
+        if accept(ep) {
+            results.push(FarCandidate {
+                idx: ep,
+                distance: dist,
+            });
+        }
 
         while let Some(Candidate {
             idx: c_idx,
@@ -187,12 +334,14 @@ impl HnswIndex {
                             idx: neighbor,
                             distance: dist,
                         });
-                        results.push(FarCandidate {
-                            idx: neighbor,
-                            distance: dist,
-                        });
-                        if results.len() > ef {
-                            results.pop();
+                        if accept(neighbor) {
+                            results.push(FarCandidate {
+                                idx: neighbor,
+                                distance: dist,
+                            });
+                            if results.len() > ef {
+                                results.pop();
+                            }
                         }
                     }
                 }
@@ -209,6 +358,20 @@ impl HnswIndex {
     }
 
     pub fn search(&self, query: &[f32], k: usize, ef: usize) -> Vec<(String, f32)> {
+        self.search_filtered(query, k, ef, |_| true)
+    }
+
+    /// Search returning only ids accepted by `accept`
+    ///
+    /// Upper layers are descended as usual; the filter applies on layer 0,
+    /// where traversal continues until `ef` accepted nodes are found.
+    pub fn search_filtered(
+        &self,
+        query: &[f32],
+        k: usize,
+        ef: usize,
+        accept: impl Fn(&str) -> bool,
+    ) -> Vec<(String, f32)> {
         if self.entry_point.is_none() || self.is_empty() {
             return Vec::new();
         }
@@ -219,7 +382,8 @@ impl HnswIndex {
             ep = self.search_layer_single(query, ep, lc);
         }
 
-        let candidates = self.search_layer(query, ep, ef.max(k), 0);
+        let accept_idx = |idx: usize| !self.deleted.contains(&idx) && accept(&self.nodes[idx].id);
+        let candidates = self.search_layer_filtered(query, ep, ef.max(k), 0, &accept_idx);
 
         candidates
             .into_iter()
@@ -232,6 +396,36 @@ impl HnswIndex {
             .collect()
     }
 
+    /// Stored vector of a node (None if missing or deleted)
+    pub fn vector(&self, id: &str) -> Option<&[f32]> {
+        let idx = *self.id_to_idx.get(id)?;
+        (!self.deleted.contains(&idx)).then(|| self.nodes[idx].vector.as_slice())
+    }
+
+    /// Cosine similarity of `query` to each listed id, best first
+    ///
+    /// Exact alternative to `search_filtered` for small candidate sets.
+    pub fn score_ids<'a>(
+        &self,
+        query: &[f32],
+        ids: impl Iterator<Item = &'a str>,
+        k: usize,
+    ) -> Vec<(String, f32)> {
+        let mut scored: Vec<(String, f32)> = ids
+            .filter_map(|id| {
+                let idx = *self.id_to_idx.get(id)?;
+                if self.deleted.contains(&idx) {
+                    return None;
+                }
+                let similarity = Self::cosine_similarity(query, &self.nodes[idx].vector);
+                Some((id.to_string(), similarity))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        scored.truncate(k);
+        scored
+    }
+
     #[cfg(test)]
     #[allow(dead_code)]
     pub(crate) fn from_vectors(ids: Vec<String>, vectors: Vec<Vec<f32>>) -> Self {
@@ -300,6 +494,31 @@ pub struct NoteRecord {
     pub tags: Option<Vec<String>>,
 }
 
+impl Filterable for NoteRecord {
+    fn field(&self, name: &str) -> Option<Cow<'_, str>> {
+        self.fields.get(name).map(|v| Cow::Borrowed(v.as_str()))
+    }
+
+    fn tags(&self) -> Cow<'_, [String]> {
+        Cow::Borrowed(self.tags.as_deref().unwrap_or_default())
+    }
+}
+
+/// Note record as exported to notes.json
+///
+/// Unlike [`NoteRecord`], fields keep their original string/list shape
+/// so the written file matches what the plugin exports.
+#[derive(Debug, Clone, Serialize)]
+pub struct NoteExport {
+    pub path: String,
+    pub gist: String,
+    pub mtime: u64,
+    pub indexed: bool,
+    pub fields: HashMap<String, serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+}
+
 fn deserialize_fields<'de, D>(deserializer: D) -> Result<HashMap<String, String>, D::Error>
 where
     D: Deserializer<'de>,
@@ -347,7 +566,7 @@ pub struct PluginIndexReader {
 
 impl PluginIndexReader {
     pub fn new(vault_path: &Path) -> Self {
-        let index_dir = vault_path.join(".obsidian/plugins/elysium/index");
+        let index_dir = vault_path.join(PLUGIN_INDEX_DIR);
         Self { index_dir }
     }
 
@@ -379,6 +598,142 @@ impl PluginIndexReader {
     }
 }
 
+// ============================================================================
+// Plugin Index Writer
+// ============================================================================
+
+/// Expected embedding dimension for an embedding mode
+pub fn dimension_for_mode(embedding_mode: &str) -> usize {
+    if embedding_mode == "model2vec" {
+        super::embedder::MODEL2VEC_DIM
+    } else {
+        super::embedding::EMBEDDING_DIM
+    }
+}
+
+/// Writes index files in the same layout as the plugin export
+pub struct PluginIndexWriter {
+    index_dir: std::path::PathBuf,
+}
+
+impl PluginIndexWriter {
+    pub fn new(vault_path: &Path) -> Self {
+        let index_dir = vault_path.join(PLUGIN_INDEX_DIR);
+        Self { index_dir }
+    }
+
+    /// Validate and write hnsw.bin, notes.json and meta.json
+    ///
+    /// Each file is written to a temporary path and renamed into place,
+    /// meta.json last, so readers never see a half-written index.
+    pub fn write(&self, meta: &IndexMeta, notes: &[NoteExport], hnsw_data: &[u8]) -> Result<()> {
+        Self::validate(meta, notes, hnsw_data)?;
+
+        std::fs::create_dir_all(&self.index_dir).with_context(|| {
+            format!(
+                "Failed to create index directory: {}",
+                self.index_dir.display()
+            )
+        })?;
+
+        let notes_json =
+            serde_json::to_string_pretty(notes).context("Failed to serialize notes.json")?;
+        let meta_json =
+            serde_json::to_string_pretty(meta).context("Failed to serialize meta.json")?;
+
+        self.write_atomic("hnsw.bin", hnsw_data)?;
+        self.write_atomic("notes.json", notes_json.as_bytes())?;
+        self.write_atomic("meta.json", meta_json.as_bytes())?;
+
+        Ok(())
+    }
+
+    fn write_atomic(&self, file_name: &str, data: &[u8]) -> Result<()> {
+        let target = self.index_dir.join(file_name);
+        let tmp = self.index_dir.join(format!("{}.tmp", file_name));
+        std::fs::write(&tmp, data).with_context(|| format!("Failed to write {:?}", tmp))?;
+        std::fs::rename(&tmp, &target).with_context(|| format!("Failed to replace {:?}", target))
+    }
+
+    /// Mirrors the plugin's export contract validation
+    fn validate(meta: &IndexMeta, notes: &[NoteExport], hnsw_data: &[u8]) -> Result<()> {
+        if meta.version != PLUGIN_INDEX_VERSION {
+            anyhow::bail!(
+                "Index version mismatch: expected {}, got {}",
+                PLUGIN_INDEX_VERSION,
+                meta.version
+            );
+        }
+        if meta.embedding_mode != "htp" && meta.embedding_mode != "model2vec" {
+            anyhow::bail!("Unknown embedding mode: {}", meta.embedding_mode);
+        }
+        if meta.note_count != notes.len() {
+            anyhow::bail!(
+                "Index noteCount mismatch: meta={}, notes={}",
+                meta.note_count,
+                notes.len()
+            );
+        }
+        let expected_dimension = dimension_for_mode(&meta.embedding_mode);
+        if meta.dimension != expected_dimension {
+            anyhow::bail!(
+                "Index dimension mismatch: expected {}, got {}",
+                expected_dimension,
+                meta.dimension
+            );
+        }
+        if meta.index_size != hnsw_data.len() {
+            anyhow::bail!(
+                "Index size mismatch: meta={}, data={}",
+                meta.index_size,
+                hnsw_data.len()
+            );
+        }
+        if !notes.is_empty() && hnsw_data.is_empty() {
+            anyhow::bail!("Index data is empty but notes are present");
+        }
+
+        let mut seen = HashSet::new();
+        for note in notes {
+            if note.path.is_empty() {
+                anyhow::bail!("Index note missing path");
+            }
+            if !seen.insert(note.path.as_str()) {
+                anyhow::bail!("Duplicate note path in index: {}", note.path);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Create the embedder matching an index embedding mode
+///
+/// Uses the model downloaded by the plugin if present, otherwise falls
+/// back to the default Model2Vec model from HuggingFace Hub.
+pub fn create_embedder_for_mode(
+    vault_path: &Path,
+    embedding_mode: &str,
+) -> Result<Box<dyn Embedder>> {
+    let model_path = if embedding_mode == "model2vec" {
+        let plugin_model_path = vault_path.join(PLUGIN_MODEL_DIR);
+        if plugin_model_path.exists() {
+            Some(plugin_model_path.to_string_lossy().to_string())
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+
+    let search_config = SearchConfig {
+        use_advanced: embedding_mode == "model2vec",
+        model_path,
+        model_id: None,
+    };
+    create_embedder(&search_config)
+}
+
 // ============================================================================
 // Plugin Search Engine
 // ============================================================================
@@ -386,6 +741,13 @@ impl PluginIndexReader {
 use super::embedder::Embedder;
 use super::engine::SearchResult;
 
+/// Search breadth on the HNSW base layer
+const HNSW_EF: usize = 50;
+
+/// Filters matching at most this many notes are scored exactly instead of
+/// traversing the graph
+const BRUTE_FORCE_MAX: usize = 1000;
+
 pub struct PluginSearchEngine {
     hnsw: HnswIndex,
     notes: HashMap<String, NoteRecord>,
@@ -416,25 +778,7 @@ impl PluginSearchEngine {
         let hnsw = reader.load_hnsw()?;
 
         // Create embedder matching plugin's embedding mode
-        // Use model downloaded by plugin if advanced search is enabled
-        let model_path = if meta.embedding_mode == "model2vec" {
-            let plugin_model_path =
-                vault_path.join(".obsidian/plugins/elysium/models/potion-multilingual-128M");
-            if plugin_model_path.exists() {
-                Some(plugin_model_path.to_string_lossy().to_string())
-            } else {
-                None
-            }
-        } else {
-            None
-        };
-
-        let search_config = SearchConfig {
-            use_advanced: meta.embedding_mode == "model2vec",
-            model_path,
-            model_id: None,
-        };
-        let embedder = create_embedder(&search_config)?;
+        let embedder = create_embedder_for_mode(vault_path, &meta.embedding_mode)?;
 
         // Verify dimension matches
         if embedder.dimension() != meta.dimension {
@@ -458,11 +802,52 @@ impl PluginSearchEngine {
         })
     }
 
+    /// Unfiltered query search
+    #[allow(dead_code)]
     pub fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
-        let query_embedding = self.embedder.embed(query)?;
-        let results = self.hnsw.search(&query_embedding, limit, 50);
+        let query_embedding = self.embed_query(query)?;
+        Ok(self.search_vector(&query_embedding, limit, &SearchFilter::default()))
+    }
+
+    /// Embed a query with the index's embedder
+    pub fn embed_query(&self, query: &str) -> Result<Vec<f32>> {
+        self.embedder.embed(query)
+    }
+
+    /// Search gist embeddings with an already embedded query
+    ///
+    /// With a filter, notes matching it are scored exactly when they are
+    /// few (or a small share of the index, where graph traversal would visit
+    /// most nodes anyway); otherwise the HNSW traversal skips non-matching
+    /// nodes. Either way up to `limit` matching notes are returned.
+    pub fn search_vector(
+        &self,
+        query_embedding: &[f32],
+        limit: usize,
+        filter: &SearchFilter,
+    ) -> Vec<SearchResult> {
+        let results = if filter.is_empty() {
+            self.hnsw.search(query_embedding, limit, HNSW_EF)
+        } else {
+            let candidates: Vec<&str> = self
+                .notes
+                .values()
+                .filter(|n| filter.matches(&n.path, *n))
+                .map(|n| n.path.as_str())
+                .collect();
+            if candidates.len() <= BRUTE_FORCE_MAX || candidates.len() * 10 <= self.notes.len() {
+                self.hnsw
+                    .score_ids(query_embedding, candidates.into_iter(), limit)
+            } else {
+                let allowed: HashSet<&str> = candidates.into_iter().collect();
+                self.hnsw
+                    .search_filtered(query_embedding, limit, HNSW_EF.max(limit), |id| {
+                        allowed.contains(id)
+                    })
+            }
+        };
 
-        Ok(results
+        results
             .into_iter()
             .filter_map(|(path, score)| {
                 let note = self.notes.get(&path)?;
@@ -480,15 +865,26 @@ impl PluginSearchEngine {
                     note_type: note.fields.get("type").cloned(),
                     area: note.fields.get("area").cloned(),
                     score,
+                    heading: None,
                 })
             })
-            .collect())
+            .collect()
+    }
+
+    /// Embedder matching the index's embedding mode
+    pub fn embedder(&self) -> &dyn Embedder {
+        self.embedder.as_ref()
     }
 
     pub fn get_note(&self, path: &str) -> Option<&NoteRecord> {
         self.notes.get(path)
     }
 
+    /// Gist embedding of a note from the HNSW index
+    pub fn vector(&self, path: &str) -> Option<&[f32]> {
+        self.hnsw.vector(path)
+    }
+
     /// Iterate over all note records
     pub fn iter_notes(&self) -> impl Iterator<Item = &NoteRecord> {
         self.notes.values()
@@ -499,7 +895,6 @@ impl PluginSearchEngine {
         self.notes.len()
     }
 
-    #[allow(dead_code)]
     pub fn embedding_mode(&self) -> &str {
         &self.meta.embedding_mode
     }
@@ -509,7 +904,6 @@ impl PluginSearchEngine {
         self.meta.dimension
     }
 
-    #[allow(dead_code)]
     pub fn exported_at(&self) -> u64 {
         self.meta.exported_at
     }
@@ -537,4 +931,31 @@ mod tests {
         assert_eq!(deserialized.len(), 0);
         assert!(deserialized.is_empty());
     }
+
+    #[test]
+    fn test_filtered_search_matches_exact_scoring() {
+        let mut index = HnswIndex::new();
+        for i in 0..300 {
+            let vector: Vec<f32> = (0..8).map(|d| ((i * 7 + d * 13) as f32).sin()).collect();
+            index.insert(format!("note{}", i), vector);
+        }
+        let query: Vec<f32> = (0..8).map(|d| (d as f32).cos()).collect();
+
+        // Only every tenth note passes the filter
+        let accept = |id: &str| id.trim_start_matches("note").parse::<usize>().unwrap() % 10 == 0;
+        let filtered = index.search_filtered(&query, 10, 50, accept);
+        assert_eq!(filtered.len(), 10);
+        assert!(filtered.iter().all(|(id, _)| accept(id)));
+
+        let ids: Vec<String> = (0..300).step_by(10).map(|i| format!("note{}", i)).collect();
+        let exact = index.score_ids(&query, ids.iter().map(String::as_str), 10);
+        assert_eq!(exact.len(), 10);
+        assert_eq!(filtered[0].0, exact[0].0);
+
+        // Accept-all filter is the plain search
+        assert_eq!(
+            index.search_filtered(&query, 5, 50, |_| true),
+            index.search(&query, 5, 50)
+        );
+    }
 }
@@ -0,0 +1,913 @@
+//! Query DSL shared by `vault_search` and `vault_list_notes`
+//!
+//! ```text
+//! type:project area:work tag:gpu status:!archived modified:>2026-01-01 "exact phrase" -excluded sort:modified desc
+//! ```
+//!
+//! - `key:value` filters a frontmatter field (`type`, `area`, `status` or any
+//!   other key without `elysium_` prefix); `key:a,b` matches either value
+//! - `tag:x` requires a tag (nested tags match), `folder:x` a folder prefix;
+//!   `tag:x*`, `tag:"a b"` and `tag:(...)` search tag text instead
+//! - `key:!value` or `-key:value` excludes matching notes
+//! - `modified:` / `created:` compare dates: `>`, `>=`, `<`, `<=` or a day
+//! - `"exact phrase"` must appear in the note (outside groups, unless the
+//!   query has a top-level `OR`); `-word` / `-"phrase"` must not
+//! - `sort:key [asc|desc]` orders results by `relevance`, `modified`,
+//!   `created`, `title`, `path` or a frontmatter field
+//! - `title:x`, `gist:x` and `body:x` match text in that field only;
+//!   `-title:x` excludes it
+//! - `NOT x` excludes like `-x`; `AND`, `OR` and `( ... )` are keyword
+//!   syntax (see `super::keyword`), not search text
+//!
+//! Remaining words are the search text. Values may be quoted
+//! (`folder:"Work Notes"`). A word whose key is not an identifier (`16:9`,
+//! `https://...`) is plain text.
+
+use chrono::NaiveDate;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Range;
+
+use super::filter::{Condition, Filterable, SearchFilter};
+use super::keyword::{KeywordField, NoteFields};
+use crate::core::note::Note;
+
+/// Parse error with the character position it refers to
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryError {
+    /// 0-based character offset in the query
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at column {})", self.message, self.position + 1)
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateField {
+    Modified,
+    Created,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Before,
+    OnOrBefore,
+    On,
+    OnOrAfter,
+    After,
+}
+
+/// `modified:>2026-01-01`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DateCondition {
+    pub field: DateField,
+    pub comparison: Comparison,
+    pub date: NaiveDate,
+}
+
+impl DateCondition {
+    pub fn matches(&self, note: &Note) -> bool {
+        let date = match self.field {
+            DateField::Modified => note.modified.date_naive(),
+            DateField::Created => note.created.date_naive(),
+        };
+        match self.comparison {
+            Comparison::Before => date < self.date,
+            Comparison::OnOrBefore => date <= self.date,
+            Comparison::On => date == self.date,
+            Comparison::OnOrAfter => date >= self.date,
+            Comparison::After => date > self.date,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SortKey {
+    Relevance,
+    Modified,
+    Created,
+    Title,
+    Path,
+    /// Frontmatter field (without `elysium_` prefix)
+    Field(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sort {
+    pub key: SortKey,
+    pub descending: bool,
+}
+
+impl Sort {
+    /// Stable sort of `items` by their notes
+    ///
+    /// Items without a note, or without the sorted field, go last in either
+    /// direction. `Relevance` keeps the incoming order (reversed for `asc`).
+    pub fn apply<'n, T>(&self, items: &mut [T], note_of: impl Fn(&T) -> Option<&'n Note>) {
+        if self.key == SortKey::Relevance {
+            if !self.descending {
+                items.reverse();
+            }
+            return;
+        }
+
+        items.sort_by(|a, b| {
+            let key = |item| note_of(item).and_then(|note| self.sort_value(note));
+            match (key(a), key(b)) {
+                (Some(a), Some(b)) if self.descending => b.cmp(&a),
+                (Some(a), Some(b)) => a.cmp(&b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+        });
+    }
+
+    fn sort_value(&self, note: &Note) -> Option<SortValue> {
+        Some(match &self.key {
+            SortKey::Relevance => return None,
+            SortKey::Modified => SortValue::Date(note.modified.timestamp_millis()),
+            SortKey::Created => SortValue::Date(note.created.timestamp_millis()),
+            SortKey::Title => SortValue::Text(note.name.to_lowercase()),
+            SortKey::Path => SortValue::Text(note.path.to_string_lossy().to_lowercase()),
+            SortKey::Field(name) => SortValue::Text(note.field(name)?.to_lowercase()),
+        })
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum SortValue {
+    Date(i64),
+    Text(String),
+}
+
+/// Parsed query
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NoteQuery {
+    /// Field, tag and folder conditions
+    pub filter: SearchFilter,
+    pub dates: Vec<DateCondition>,
+    /// Free words
+    pub terms: Vec<String>,
+    /// Quoted phrases
+    pub phrases: Vec<String>,
+    /// Phrases that must appear: those outside groups, unless the query
+    /// has a top-level `OR`
+    pub required_phrases: Vec<String>,
+    /// Words or phrases that must not appear
+    pub excluded: Vec<String>,
+    /// `title:`, `gist:` and `body:` words or phrases, and tag text
+    /// (`tags:gp*`, `tags:"a b"`)
+    pub scoped: Vec<ScopedText>,
+    /// Scoped words or phrases that must not appear in their field
+    pub excluded_scoped: Vec<ScopedText>,
+    pub sort: Option<Sort>,
+    /// The query with filters, sort keys and exclusions blanked out: search
+    /// text with keyword syntax, at its original character positions
+    pub search_text: String,
+}
+
+impl NoteQuery {
+    pub fn parse(query: &str) -> Result<Self, QueryError> {
+        Parser::new(query).parse()
+    }
+
+    /// Words searched for, without syntax: free words, quoted phrases and
+    /// scoped text
+    pub fn text(&self) -> String {
+        self.terms
+            .iter()
+            .cloned()
+            .chain(self.phrases.iter().map(|p| format!("\"{}\"", p)))
+            .chain(self.scoped.iter().map(|s| s.text.clone()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    pub fn has_text(&self) -> bool {
+        !self.terms.is_empty() || !self.phrases.is_empty() || !self.scoped.is_empty()
+    }
+
+    /// Whether a note passes the date, phrase and exclusion constraints
+    ///
+    /// Semantic search cannot enforce these, so `vault_search` checks them
+    /// against the note catalog before searching. Phrases and exclusions
+    /// match whole words in the title, gist, tags or body.
+    pub fn matches_constraints(&self, note: &Note) -> bool {
+        if !self.dates.iter().all(|d| d.matches(note)) {
+            return false;
+        }
+        if self.required_phrases.is_empty()
+            && self.excluded.is_empty()
+            && self.excluded_scoped.is_empty()
+        {
+            return true;
+        }
+        let fields = NoteFields::of(note);
+        let contains = |t: &String| fields.contains(None, t, false);
+        self.required_phrases.iter().all(contains)
+            && !self.excluded.iter().any(contains)
+            && !self.excluded_scoped.iter().any(|s| s.matches(&fields))
+    }
+
+    pub fn has_constraints(&self) -> bool {
+        !self.dates.is_empty()
+            || !self.required_phrases.is_empty()
+            || !self.excluded.is_empty()
+            || !self.excluded_scoped.is_empty()
+    }
+
+    /// Whether a note passes the filters and constraints (for listing)
+    ///
+    /// The search text is checked separately, as a keyword condition
+    /// (`KeywordQuery::parse_condition` of `search_text`).
+    pub fn matches(&self, path: &str, note: &Note) -> bool {
+        self.filter.matches(path, note) && self.matches_constraints(note)
+    }
+}
+
+/// Search text limited to one field (`title:x`, `-gist:"a b"`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScopedText {
+    pub field: KeywordField,
+    pub text: String,
+}
+
+impl ScopedText {
+    fn matches(&self, fields: &NoteFields) -> bool {
+        let text = self.text.trim_end_matches('*');
+        fields.contains(Some(self.field), text, text.len() < self.text.len())
+    }
+}
+
+/// Fields with dedicated syntax; other keys are frontmatter fields
+const DATE_KEYS: [&str; 2] = ["modified", "created"];
+const TAG_KEYS: [&str; 2] = ["tag", "tags"];
+const FOLDER_KEYS: [&str; 2] = ["folder", "in"];
+/// Field scopes of keyword search
+const TEXT_KEYS: [&str; 3] = ["title", "gist", "body"];
+const SORT_KEYS: &str = "relevance, modified, created, title, path or a frontmatter field";
+
+enum Token {
+    Word {
+        text: String,
+        negated: bool,
+    },
+    Phrase {
+        text: String,
+        negated: bool,
+        /// Inside parentheses
+        grouped: bool,
+    },
+    KeyValue {
+        key: String,
+        value: String,
+        negated: bool,
+        position: usize,
+        value_position: usize,
+    },
+    /// `title:value`
+    Scoped {
+        field: KeywordField,
+        text: String,
+        negated: bool,
+    },
+    /// Keyword syntax, left to keyword search
+    Syntax(Syntax),
+}
+
+enum Syntax {
+    Open,
+    Close,
+    Or,
+    /// `AND`, a `NOT` or `-` before a group or a field scope before a group
+    Other,
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(query: &str) -> Self {
+        Self {
+            chars: query.chars().collect(),
+            pos: 0,
+        }
+    }
+
+    fn parse(mut self) -> Result<NoteQuery, QueryError> {
+        let mut tokens = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let start = self.pos;
+            let Some(token) = self.next_token()? else {
+                break;
+            };
+            tokens.push((token, start..self.pos));
+        }
+
+        // Phrases in groups or OR-ed at the top level are optional
+        let mut depth = 0usize;
+        let mut top_level_or = false;
+        for (token, _) in &mut tokens {
+            match token {
+                Token::Syntax(Syntax::Open) => depth += 1,
+                Token::Syntax(Syntax::Close) => depth = depth.saturating_sub(1),
+                Token::Syntax(Syntax::Or) if depth == 0 => top_level_or = true,
+                Token::Phrase { grouped, .. } => *grouped = depth > 0,
+                _ => {}
+            }
+        }
+
+        let mut query = NoteQuery::default();
+        // Spans that are not search text
+        let mut consumed: Vec<Range<usize>> = Vec::new();
+        let mut tokens = tokens.into_iter().peekable();
+        while let Some((token, span)) = tokens.next() {
+            match token {
+                Token::Word { text, negated } | Token::Phrase { text, negated, .. } if negated => {
+                    consumed.push(span);
+                    query.excluded.push(text);
+                }
+                Token::Word { text, .. } => query.terms.push(text),
+                Token::Phrase { text, grouped, .. } => {
+                    if !grouped && !top_level_or {
+                        query.required_phrases.push(text.clone());
+                    }
+                    query.phrases.push(text);
+                }
+                Token::Scoped {
+                    field,
+                    text,
+                    negated,
+                } => {
+                    let scoped = ScopedText { field, text };
+                    if negated {
+                        consumed.push(span);
+                        query.excluded_scoped.push(scoped);
+                    } else {
+                        query.scoped.push(scoped);
+                    }
+                }
+                Token::Syntax(_) => {}
+                Token::KeyValue {
+                    key,
+                    value,
+                    negated,
+                    position,
+                    value_position,
+                } => {
+                    consumed.push(span);
+                    if key == "sort" {
+                        if query.sort.is_some() {
+                            return Err(error(position, "Only one sort: is allowed"));
+                        }
+                        let descending = match tokens.peek() {
+                            Some((
+                                Token::Word {
+                                    text,
+                                    negated: false,
+                                    ..
+                                },
+                                span,
+                            )) if text.eq_ignore_ascii_case("asc")
+                                || text.eq_ignore_ascii_case("desc") =>
+                            {
+                                let descending = text.eq_ignore_ascii_case("desc");
+                                consumed.push(span.clone());
+                                tokens.next();
+                                Some(descending)
+                            }
+                            _ => None,
+                        };
+                        query.sort = Some(parse_sort(&value, value_position, descending)?);
+                    } else if DATE_KEYS.contains(&key.as_str()) {
+                        if negated {
+                            return Err(error(
+                                position,
+                                format!("{}: cannot be negated; use < or > instead", key),
+                            ));
+                        }
+                        query.dates.push(parse_date(&key, &value, value_position)?);
+                    } else {
+                        let (value, negated) = match value.strip_prefix('!') {
+                            Some(rest) => (rest.to_string(), !negated),
+                            None => (value, negated),
+                        };
+                        let condition = parse_condition(&key, &value, value_position)?;
+                        if negated {
+                            query.filter.exclude.push(condition);
+                        } else {
+                            query.filter.include.push(condition);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut chars = self.chars;
+        for span in consumed {
+            chars[span].fill(' ');
+        }
+        query.search_text = chars.into_iter().collect();
+        Ok(query)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>, QueryError> {
+        self.skip_whitespace();
+        let Some(c) = self.peek() else {
+            return Ok(None);
+        };
+
+        let position = self.pos;
+        let syntax = match c {
+            '(' => Some(Syntax::Open),
+            ')' => Some(Syntax::Close),
+            '-' if self.peek_at(1) == Some('(') => Some(Syntax::Other),
+            _ => None,
+        };
+        if let Some(syntax) = syntax {
+            self.pos += 1;
+            return Ok(Some(Token::Syntax(syntax)));
+        }
+        let negated = c == '-' && self.peek_at(1).is_some_and(|n| !n.is_whitespace());
+        if negated {
+            self.pos += 1;
+        }
+
+        if self.peek() == Some('"') {
+            let text = self.quoted()?;
+            if text.trim().is_empty() {
+                return Err(error(position, "Empty phrase"));
+            }
+            return Ok(Some(Token::Phrase {
+                text,
+                negated,
+                grouped: false,
+            }));
+        }
+
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || c == '(' || c == ')' {
+                break;
+            }
+            if c == ':' {
+                let key: String = self.chars[start..self.pos].iter().collect();
+                if is_key(&key) {
+                    self.pos += 1;
+                    let value_position = self.pos;
+                    let lower = key.to_lowercase();
+                    let tag_key = TAG_KEYS.contains(&lower.as_str());
+                    let text_key = TEXT_KEYS.contains(&lower.as_str());
+                    if (text_key || tag_key) && self.peek() == Some('(') {
+                        // `title:(a b)`: the group's words are search text
+                        return Ok(Some(Token::Syntax(Syntax::Other)));
+                    }
+                    let quoted = self.peek() == Some('"');
+                    let value = self.value()?;
+                    if value.is_empty() || value.starts_with("//") {
+                        if is_known_key(&key.to_lowercase()) {
+                            return Err(error(
+                                value_position,
+                                format!("Missing value for {}:", key),
+                            ));
+                        }
+                        let text = format!("{}:{}", key, value);
+                        return Ok(Some(Token::Word { text, negated }));
+                    }
+                    // `tags:gp*` and `tags:"a b"` search tag text; `tags:x` is
+                    // the tag filter
+                    if text_key || (tag_key && (quoted || value.contains('*'))) {
+                        let field = KeywordField::parse(&key).expect("text keys are fields");
+                        return Ok(Some(Token::Scoped {
+                            field,
+                            text: value,
+                            negated,
+                        }));
+                    }
+                    return Ok(Some(Token::KeyValue {
+                        key: key.to_lowercase(),
+                        value,
+                        negated,
+                        position,
+                        value_position,
+                    }));
+                }
+            }
+            self.pos += 1;
+        }
+
+        let text: String = self.chars[start..self.pos].iter().collect();
+        if !negated {
+            match text.as_str() {
+                "AND" => return Ok(Some(Token::Syntax(Syntax::Other))),
+                "OR" => return Ok(Some(Token::Syntax(Syntax::Or))),
+                "NOT" => return self.not(),
+                _ => {}
+            }
+        }
+        Ok(Some(Token::Word { text, negated }))
+    }
+
+    /// `NOT x` excludes like `-x`; before a group or operator it is left to
+    /// keyword search
+    fn not(&mut self) -> Result<Option<Token>, QueryError> {
+        let after = self.pos;
+        let token = match self.next_token()? {
+            Some(Token::Word { text, .. }) => Token::Word {
+                text,
+                negated: true,
+            },
+            Some(Token::Phrase { text, .. }) => Token::Phrase {
+                text,
+                negated: true,
+                grouped: false,
+            },
+            Some(Token::Scoped { field, text, .. }) => Token::Scoped {
+                field,
+                text,
+                negated: true,
+            },
+            Some(Token::KeyValue {
+                key,
+                value,
+                position,
+                value_position,
+                ..
+            }) => Token::KeyValue {
+                key,
+                value,
+                negated: true,
+                position,
+                value_position,
+            },
+            Some(Token::Syntax(_)) | None => {
+                self.pos = after;
+                Token::Syntax(Syntax::Other)
+            }
+        };
+        Ok(Some(token))
+    }
+
+    /// Value after `key:` (quoted or up to the next whitespace or `)`)
+    fn value(&mut self) -> Result<String, QueryError> {
+        if self.peek() == Some('"') {
+            return self.quoted();
+        }
+        let start = self.pos;
+        while self.peek().is_some_and(|c| !c.is_whitespace() && c != ')') {
+            self.pos += 1;
+        }
+        Ok(self.chars[start..self.pos].iter().collect())
+    }
+
+    /// Text between double quotes (the cursor is on the opening quote)
+    fn quoted(&mut self) -> Result<String, QueryError> {
+        let open = self.pos;
+        self.pos += 1;
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c == '"' {
+                let text = self.chars[start..self.pos].iter().collect();
+                self.pos += 1;
+                return Ok(text);
+            }
+            self.pos += 1;
+        }
+        Err(error(open, "Unterminated quote"))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+}
+
+fn error(position: usize, message: impl Into<String>) -> QueryError {
+    QueryError {
+        position,
+        message: message.into(),
+    }
+}
+
+/// Keys start with a letter and contain letters, digits, `_` or `-`
+fn is_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn is_known_key(key: &str) -> bool {
+    matches!(key, "type" | "area" | "status" | "sort")
+        || DATE_KEYS.contains(&key)
+        || TAG_KEYS.contains(&key)
+        || FOLDER_KEYS.contains(&key)
+        || TEXT_KEYS.contains(&key)
+}
+
+fn parse_condition(key: &str, value: &str, position: usize) -> Result<Condition, QueryError> {
+    if value.is_empty() {
+        return Err(error(position, format!("Missing value for {}:", key)));
+    }
+    if value.starts_with(['<', '>']) {
+        return Err(error(
+            position,
+            format!(
+                "Comparison is only supported for {}; {}: matches values exactly",
+                DATE_KEYS.join(" and "),
+                key
+            ),
+        ));
+    }
+
+    if TAG_KEYS.contains(&key) || FOLDER_KEYS.contains(&key) {
+        if value.contains(',') {
+            return Err(error(
+                position,
+                format!("{}: takes one value; repeat it to require several", key),
+            ));
+        }
+        return Ok(if TAG_KEYS.contains(&key) {
+            Condition::Tag(value.to_string())
+        } else {
+            Condition::Folder(value.to_string())
+        });
+    }
+
+    let values: Vec<String> = value
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(String::from)
+        .collect();
+    if values.is_empty() {
+        return Err(error(position, format!("Missing value for {}:", key)));
+    }
+    Ok(Condition::Field {
+        name: key.trim_start_matches("elysium_").to_string(),
+        values,
+    })
+}
+
+fn parse_date(key: &str, value: &str, position: usize) -> Result<DateCondition, QueryError> {
+    let (comparison, date) = if let Some(rest) = value.strip_prefix(">=") {
+        (Comparison::OnOrAfter, rest)
+    } else if let Some(rest) = value.strip_prefix("<=") {
+        (Comparison::OnOrBefore, rest)
+    } else if let Some(rest) = value.strip_prefix('>') {
+        (Comparison::After, rest)
+    } else if let Some(rest) = value.strip_prefix('<') {
+        (Comparison::Before, rest)
+    } else {
+        (Comparison::On, value.strip_prefix('=').unwrap_or(value))
+    };
+    let date_position = position + (value.chars().count() - date.chars().count());
+
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| {
+        error(
+            date_position,
+            format!("Invalid date '{}' for {}: (expected YYYY-MM-DD)", date, key),
+        )
+    })?;
+    let field = if key == "created" {
+        DateField::Created
+    } else {
+        DateField::Modified
+    };
+
+    Ok(DateCondition {
+        field,
+        comparison,
+        date,
+    })
+}
+
+fn parse_sort(value: &str, position: usize, descending: Option<bool>) -> Result<Sort, QueryError> {
+    let key = match value.to_lowercase().as_str() {
+        "relevance" | "score" => SortKey::Relevance,
+        "modified" => SortKey::Modified,
+        "created" => SortKey::Created,
+        "title" | "name" => SortKey::Title,
+        "path" => SortKey::Path,
+        other if is_key(other) => SortKey::Field(other.trim_start_matches("elysium_").to_string()),
+        _ => {
+            return Err(error(
+                position,
+                format!("Unknown sort key '{}' (use {})", value, SORT_KEYS),
+            ))
+        }
+    };
+    // Newest / best first by default, alphabetical otherwise
+    let default_descending = matches!(
+        key,
+        SortKey::Relevance | SortKey::Modified | SortKey::Created
+    );
+
+    Ok(Sort {
+        key,
+        descending: descending.unwrap_or(default_descending),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_full_query() {
+        let query = NoteQuery::parse(
+            r#"type:project area:work tag:gpu status:!archived modified:>2026-01-01 "exact phrase" -excluded memory sort:modified desc"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            query.filter.include,
+            vec![
+                Condition::Field {
+                    name: "type".to_string(),
+                    values: vec!["project".to_string()],
+                },
+                Condition::Field {
+                    name: "area".to_string(),
+                    values: vec!["work".to_string()],
+                },
+                Condition::Tag("gpu".to_string()),
+            ]
+        );
+        assert_eq!(
+            query.filter.exclude,
+            vec![Condition::Field {
+                name: "status".to_string(),
+                values: vec!["archived".to_string()],
+            }]
+        );
+        assert_eq!(
+            query.dates,
+            vec![DateCondition {
+                field: DateField::Modified,
+                comparison: Comparison::After,
+                date: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            }]
+        );
+        assert_eq!(query.phrases, vec!["exact phrase"]);
+        assert_eq!(query.required_phrases, vec!["exact phrase"]);
+        assert_eq!(query.excluded, vec!["excluded"]);
+        assert_eq!(query.terms, vec!["memory"]);
+        assert_eq!(
+            query.sort,
+            Some(Sort {
+                key: SortKey::Modified,
+                descending: true,
+            })
+        );
+        assert_eq!(query.text(), "memory \"exact phrase\"");
+    }
+
+    #[test]
+    fn test_parse_values_and_plain_text() {
+        let query = NoteQuery::parse(
+            r#"-folder:"Old Notes" type:note,term ratio 16:9 https://x.org sort:title"#,
+        )
+        .unwrap();
+        assert_eq!(
+            query.filter.exclude,
+            vec![Condition::Folder("Old Notes".to_string())]
+        );
+        assert_eq!(
+            query.filter.include,
+            vec![Condition::Field {
+                name: "type".to_string(),
+                values: vec!["note".to_string(), "term".to_string()],
+            }]
+        );
+        assert_eq!(query.terms, vec!["ratio", "16:9", "https://x.org"]);
+        assert_eq!(
+            query.sort,
+            Some(Sort {
+                key: SortKey::Title,
+                descending: false,
+            })
+        );
+
+        // Plain natural-language queries are unaffected
+        let query = NoteQuery::parse("GPU memory sharing - overview").unwrap();
+        assert_eq!(query.text(), "GPU memory sharing - overview");
+        assert!(query.filter.is_empty());
+    }
+
+    #[test]
+    fn test_search_text() {
+        let query = NoteQuery::parse(
+            r#"(gpu OR title:"mig guide") tag:x -draft -body:old sort:title desc"#,
+        )
+        .unwrap();
+        // Filters, sort keys and exclusions are blanked at their positions
+        assert_eq!(
+            query.search_text,
+            r#"(gpu OR title:"mig guide")                                       "#
+        );
+        assert_eq!(query.terms, vec!["gpu"]);
+        assert_eq!(
+            query.scoped,
+            vec![ScopedText {
+                field: KeywordField::Title,
+                text: "mig guide".to_string(),
+            }]
+        );
+        assert_eq!(query.excluded, vec!["draft"]);
+        assert_eq!(
+            query.excluded_scoped,
+            vec![ScopedText {
+                field: KeywordField::Body,
+                text: "old".to_string(),
+            }]
+        );
+        assert_eq!(query.filter.include, vec![Condition::Tag("x".to_string())]);
+        assert!(NoteQuery::parse("title:").is_err());
+
+        // NOT excludes like `-`, except before a group
+        let query = NoteQuery::parse("NOT draft a AND b NOT (c OR body:(d)) NOT").unwrap();
+        assert_eq!(query.excluded, vec!["draft"]);
+        assert_eq!(query.terms, vec!["a", "b", "c", "d"]);
+        assert_eq!(
+            query.search_text,
+            "          a AND b NOT (c OR body:(d)) NOT"
+        );
+    }
+
+    #[test]
+    fn test_tag_scopes() {
+        let query = NoteQuery::parse(r#"tags:gp* tag:x -tags:"a b" tags:(y OR z)"#).unwrap();
+        assert_eq!(query.filter.include, vec![Condition::Tag("x".to_string())]);
+        assert_eq!(
+            query.scoped,
+            vec![ScopedText {
+                field: KeywordField::Tags,
+                text: "gp*".to_string(),
+            }]
+        );
+        assert_eq!(
+            query.excluded_scoped,
+            vec![ScopedText {
+                field: KeywordField::Tags,
+                text: "a b".to_string(),
+            }]
+        );
+        assert_eq!(
+            query.search_text,
+            r#"tags:gp*                   tags:(y OR z)"#
+        );
+    }
+
+    #[test]
+    fn test_required_phrases() {
+        let required = |q: &str| NoteQuery::parse(q).unwrap().required_phrases;
+
+        assert_eq!(required(r#""a b" AND gpu "c d""#), vec!["a b", "c d"]);
+        // Phrases in groups or alternatives are optional
+        assert!(required(r#"(gpu OR "mig guide")"#).is_empty());
+        assert!(required(r#"-("a b" x) gpu"#).is_empty());
+        assert!(required(r#""a b" gpu OR cuda"#).is_empty());
+        assert_eq!(required(r#"(x OR y) "a b""#), vec!["a b"]);
+        assert_eq!(
+            NoteQuery::parse(r#"(gpu OR "mig guide")"#).unwrap().phrases,
+            vec!["mig guide"]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = |q: &str| NoteQuery::parse(q).unwrap_err();
+
+        assert_eq!(err(r#"gpu "open phrase"#).position, 4);
+        assert!(err(r#"gpu "open phrase"#).message.contains("Unterminated"));
+        assert_eq!(err("type: gpu").position, 5);
+        let e = err("modified:>2026-13-01");
+        assert_eq!(e.position, 10);
+        assert!(e.to_string().contains("YYYY-MM-DD"));
+        assert!(e.to_string().ends_with("(at column 11)"));
+        assert!(err("sort:modified sort:title").message.contains("one sort"));
+        assert!(err("sort:?").message.contains("Unknown sort key"));
+        assert!(err("area:>work").message.contains("Comparison"));
+        assert!(err("tag:a,b").message.contains("repeat"));
+        assert!(err("-modified:2026-01-01").message.contains("negated"));
+    }
+}
@@ -0,0 +1,150 @@
+//! Related-note ranking: gist similarity plus metadata and link-graph boosts
+//!
+//! Same weighting as the deprecated `SearchEngine` (`0.7 · similarity +
+//! 0.3 · metadata`), for the plugin index. Metadata adds 0.5 per matching
+//! type or area and up to 0.5 for graph proximity: a direct link (either
+//! direction), otherwise the overlap of linked notes, averaged with the
+//! overlap of tags.
+
+use std::collections::HashSet;
+
+const SEMANTIC_WEIGHT: f32 = 0.7;
+const METADATA_WEIGHT: f32 = 0.3;
+const TYPE_BOOST: f32 = 0.5;
+const AREA_BOOST: f32 = 0.5;
+const GRAPH_BOOST: f32 = 0.5;
+
+/// Which signals boost related notes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RelatedBoost {
+    pub boost_type: bool,
+    pub boost_area: bool,
+    /// Shared links and tags with the source note
+    pub boost_graph: bool,
+}
+
+impl RelatedBoost {
+    pub fn is_active(&self) -> bool {
+        self.boost_type || self.boost_area || self.boost_graph
+    }
+}
+
+/// What boosting compares between the source note and a candidate
+#[derive(Debug, Clone, Default)]
+pub struct NoteSignals<'a> {
+    /// Vault-relative path
+    pub path: &'a str,
+    pub note_type: Option<&'a str>,
+    pub area: Option<&'a str>,
+    pub tags: Vec<String>,
+    /// Notes linked from or to this note (vault-relative paths)
+    pub links: HashSet<String>,
+}
+
+/// Graph proximity in [0, 1]: link score and tag overlap, averaged
+///
+/// The link score is 1 for a direct link, otherwise the Jaccard overlap of
+/// linked notes. Tags compare case-insensitively.
+pub fn graph_proximity(source: &NoteSignals, candidate: &NoteSignals) -> f32 {
+    let link_score =
+        if source.links.contains(candidate.path) || candidate.links.contains(source.path) {
+            1.0
+        } else {
+            jaccard(&source.links, &candidate.links)
+        };
+    let lowercase =
+        |tags: &[String]| -> HashSet<String> { tags.iter().map(|t| t.to_lowercase()).collect() };
+    let tag_score = jaccard(&lowercase(&source.tags), &lowercase(&candidate.tags));
+
+    (link_score + tag_score) / 2.0
+}
+
+/// Similarity combined with the enabled boosts
+///
+/// Without boosts the similarity is returned unchanged.
+pub fn boosted_score(
+    similarity: f32,
+    source: &NoteSignals,
+    candidate: &NoteSignals,
+    boost: RelatedBoost,
+) -> f32 {
+    if !boost.is_active() {
+        return similarity;
+    }
+
+    let same = |a: Option<&str>, b: Option<&str>| match (a, b) {
+        (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+        _ => false,
+    };
+    let mut metadata_score = 0.0;
+    if boost.boost_type && same(source.note_type, candidate.note_type) {
+        metadata_score += TYPE_BOOST;
+    }
+    if boost.boost_area && same(source.area, candidate.area) {
+        metadata_score += AREA_BOOST;
+    }
+    if boost.boost_graph {
+        metadata_score += GRAPH_BOOST * graph_proximity(source, candidate);
+    }
+
+    SEMANTIC_WEIGHT * similarity + METADATA_WEIGHT * metadata_score
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f32 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f32 / union as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signals<'a>(
+        path: &'a str,
+        note_type: &'a str,
+        tags: &[&str],
+        links: &[&str],
+    ) -> NoteSignals<'a> {
+        NoteSignals {
+            path,
+            note_type: Some(note_type),
+            area: Some("work"),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            links: links.iter().map(|l| l.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_boosted_score() {
+        let source = signals("a.md", "project", &["gpu"], &["b.md", "hub.md"]);
+        let linked = signals("b.md", "note", &[], &[]);
+        let sibling = signals("c.md", "Project", &["GPU", "cuda"], &["hub.md"]);
+        let stranger = signals("d.md", "log", &[], &["other.md"]);
+
+        let none = RelatedBoost::default();
+        assert_eq!(boosted_score(0.8, &source, &stranger, none), 0.8);
+
+        let by_type = RelatedBoost {
+            boost_type: true,
+            ..Default::default()
+        };
+        assert!((boosted_score(0.8, &source, &sibling, by_type) - (0.56 + 0.15)).abs() < 1e-6);
+        assert!((boosted_score(0.8, &source, &stranger, by_type) - 0.56).abs() < 1e-6);
+
+        // Direct link; shared hub (1/2) and half of the tags
+        assert_eq!(graph_proximity(&source, &linked), 0.5);
+        assert_eq!(graph_proximity(&source, &sibling), 0.5);
+        assert_eq!(graph_proximity(&source, &stranger), 0.0);
+        let by_graph = RelatedBoost {
+            boost_graph: true,
+            ..Default::default()
+        };
+        assert!(
+            boosted_score(0.5, &source, &linked, by_graph)
+                > boosted_score(0.5, &source, &stranger, by_graph)
+        );
+    }
+}
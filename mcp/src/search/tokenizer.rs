@@ -0,0 +1,219 @@
+//! BM25 tokenizers
+//!
+//! tantivy's default tokenizer splits on whitespace and punctuation only, so
+//! Korean words with particles (`GPU를`, `메모리에서`) and unsegmented
+//! Chinese/Japanese text become single tokens that queries rarely match.
+//!
+//! The `cjk` tokenizer keeps the default behavior for other scripts and
+//! splits runs of Hangul, Han, Hiragana and Katakana into overlapping
+//! character bigrams (`메모리에서` -> `메모 모리 리에 에서`), and a script
+//! change starts a new token (`GPU를` -> `gpu 를`). Queries are split the
+//! same way and bigrams are OR-ed (see `expand_word`), so a query word
+//! still matches with a different particle attached.
+//!
+//! Which tokenizer an index uses is chosen from config (`search.tokenizer`)
+//! and recorded in the index state; changing it rebuilds the index.
+
+use tantivy::tokenizer::{
+    LowerCaser, RemoveLongFilter, TextAnalyzer, Token, TokenStream, Tokenizer,
+};
+use tantivy::Index;
+
+/// Same limit as tantivy's default analyzer
+const MAX_TOKEN_BYTES: usize = 40;
+
+/// Tokenizer for BM25 text fields
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TokenizerKind {
+    /// tantivy default: split on whitespace and punctuation, lowercase
+    #[default]
+    Default,
+    /// Default plus character bigrams for CJK scripts
+    Cjk,
+}
+
+impl TokenizerKind {
+    pub const NAMES: &'static str = "default, cjk";
+
+    /// Parse a tokenizer name (None if unknown)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "default" => Some(TokenizerKind::Default),
+            "cjk" | "ngram" | "bigram" => Some(TokenizerKind::Cjk),
+            _ => None,
+        }
+    }
+
+    /// Name the analyzer is registered under in the index
+    pub fn name(&self) -> &'static str {
+        match self {
+            TokenizerKind::Default => "default",
+            TokenizerKind::Cjk => "elysium_cjk",
+        }
+    }
+}
+
+/// Register the custom analyzers with an index (needed before indexing or
+/// querying fields that use them)
+pub fn register(index: &Index) {
+    index.tokenizers().register(
+        TokenizerKind::Cjk.name(),
+        TextAnalyzer::builder(CjkTokenizer)
+            .filter(RemoveLongFilter::limit(MAX_TOKEN_BYTES))
+            .filter(LowerCaser)
+            .build(),
+    );
+}
+
+/// Split a query word so its CJK runs become separate bigram terms
+///
+/// `GPU를` gives `GPU` and `를`; `메모리` gives `메모` and `모리`.
+pub fn expand_word(word: &str) -> Vec<String> {
+    let chars: Vec<char> = word.chars().collect();
+    let mut parts = Vec::new();
+    for run in chars.chunk_by(|a, b| is_cjk(*a) == is_cjk(*b)) {
+        if is_cjk(run[0]) {
+            parts.extend(bigrams(run));
+        } else {
+            parts.push(run.iter().collect());
+        }
+    }
+    parts
+}
+
+/// Whether a character belongs to a script written without spaces between
+/// words (or, for Korean, with particles attached)
+pub(crate) fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{1100}'..='\u{11FF}'     // Hangul Jamo
+        | '\u{3040}'..='\u{30FF}'   // Hiragana, Katakana
+        | '\u{3130}'..='\u{318F}'   // Hangul Compatibility Jamo
+        | '\u{3400}'..='\u{4DBF}'   // CJK Extension A
+        | '\u{4E00}'..='\u{9FFF}'   // CJK Unified Ideographs
+        | '\u{AC00}'..='\u{D7AF}'   // Hangul Syllables
+        | '\u{F900}'..='\u{FAFF}'   // CJK Compatibility Ideographs
+        | '\u{FF66}'..='\u{FF9F}'   // Halfwidth Katakana
+        | '\u{20000}'..='\u{2FA1F}' // CJK Extensions B-F
+    )
+}
+
+/// Overlapping character bigrams (a single character stays a unigram)
+fn bigrams(chars: &[char]) -> Vec<String> {
+    if chars.len() == 1 {
+        return vec![chars[0].to_string()];
+    }
+    chars.windows(2).map(|w| w.iter().collect()).collect()
+}
+
+/// Splits on non-alphanumerics and script changes; CJK runs become bigrams
+#[derive(Clone, Default)]
+pub struct CjkTokenizer;
+
+pub struct CjkTokenStream {
+    tokens: Vec<Token>,
+    /// Index of the current token plus one (0: before the first)
+    next: usize,
+}
+
+impl Tokenizer for CjkTokenizer {
+    type TokenStream<'a> = CjkTokenStream;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> CjkTokenStream {
+        let mut tokens = Vec::new();
+        let mut push = |text: String, offset_from: usize, offset_to: usize| {
+            tokens.push(Token {
+                offset_from,
+                offset_to,
+                position: tokens.len(),
+                text,
+                position_length: 1,
+            });
+        };
+
+        let mut chars = text.char_indices().peekable();
+        while let Some((start, c)) = chars.next() {
+            if !c.is_alphanumeric() {
+                continue;
+            }
+            let cjk = is_cjk(c);
+            let mut run = vec![(start, c)];
+            while let Some(&(i, next)) = chars.peek() {
+                if !next.is_alphanumeric() || is_cjk(next) != cjk {
+                    break;
+                }
+                run.push((i, next));
+                chars.next();
+            }
+            let end_of = |(i, c): (usize, char)| i + c.len_utf8();
+
+            if !cjk {
+                let end = end_of(run[run.len() - 1]);
+                push(text[start..end].to_string(), start, end);
+            } else if run.len() == 1 {
+                push(c.to_string(), start, end_of(run[0]));
+            } else {
+                for pair in run.windows(2) {
+                    let end = end_of(pair[1]);
+                    push(text[pair[0].0..end].to_string(), pair[0].0, end);
+                }
+            }
+        }
+
+        CjkTokenStream { tokens, next: 0 }
+    }
+}
+
+impl TokenStream for CjkTokenStream {
+    fn advance(&mut self) -> bool {
+        if self.next < self.tokens.len() {
+            self.next += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn token(&self) -> &Token {
+        &self.tokens[self.next - 1]
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.tokens[self.next - 1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(text: &str) -> Vec<String> {
+        let mut analyzer = TextAnalyzer::builder(CjkTokenizer)
+            .filter(LowerCaser)
+            .build();
+        let mut stream = analyzer.token_stream(text);
+        let mut tokens = Vec::new();
+        while let Some(token) = stream.next() {
+            tokens.push(token.text.clone());
+        }
+        tokens
+    }
+
+    #[test]
+    fn test_cjk_tokens() {
+        assert_eq!(
+            tokens("GPU를 메모리에서"),
+            vec!["gpu", "를", "메모", "모리", "리에", "에서"]
+        );
+        assert_eq!(tokens("東京タワー"), vec!["東京", "京タ", "タワ", "ワー"]);
+        assert_eq!(tokens("Hello, world-2"), vec!["hello", "world", "2"]);
+    }
+
+    #[test]
+    fn test_expand_word() {
+        assert_eq!(expand_word("GPU를"), vec!["GPU", "를"]);
+        assert_eq!(expand_word("메모리"), vec!["메모", "모리"]);
+        assert_eq!(expand_word("커널"), vec!["커널"]);
+        assert_eq!(expand_word("plain"), vec!["plain"]);
+        assert!(expand_word("").is_empty());
+    }
+}
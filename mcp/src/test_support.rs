@@ -0,0 +1,11 @@
+//! Shared helpers for unit tests
+
+use std::fs;
+use std::path::Path;
+
+/// Write a note at a vault-relative path, creating parent folders
+pub fn write_note(root: &Path, rel: &str, content: &str) {
+    let path = root.join(rel);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
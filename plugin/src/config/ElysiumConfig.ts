@@ -31,10 +31,18 @@ export interface SchemaConfig {
   tags: { maxCount: number; lowercase: boolean };
 }
 
+export interface FolderRule {
+  field: string;
+  values: string[];
+  folders: string[];
+}
+
 export interface FoldersConfig {
   notes: string;
   projects: string;
   archive: string;
+  /** Folder placement rules checked by the MCP audit (kept as-is) */
+  rules?: FolderRule[];
 }
 
 export interface ElysiumConfigData {
@@ -186,6 +194,7 @@ export class ElysiumConfig {
         notes: parsed.folders?.notes ?? DEFAULT_CONFIG.folders.notes,
         projects: parsed.folders?.projects ?? DEFAULT_CONFIG.folders.projects,
         archive: parsed.folders?.archive ?? DEFAULT_CONFIG.folders.archive,
+        ...(parsed.folders?.rules ? { rules: parsed.folders.rules } : {}),
       },
       inbox: {
         enabled: parsed.inbox?.enabled ?? DEFAULT_CONFIG.inbox.enabled,